@@ -0,0 +1,13 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "grpc")]
+    {
+        // protoc comes with protoc-bin-vendored rather than from the system or the network.
+        let mut config = prost_build::Config::new();
+        config.protoc_executable(protoc_bin_vendored::protoc_bin_path()?);
+        tonic_prost_build::configure()
+            .build_client(false)
+            .compile_with_config(config, &["proto/quest_lock/v1/locks.proto"], &["proto"])?;
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+    Ok(())
+}
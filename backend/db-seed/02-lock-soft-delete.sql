@@ -0,0 +1,2 @@
+ALTER TABLE locks ADD COLUMN deleted_at timestamp with time zone;
+CREATE INDEX idx_locks_deleted_at ON public.locks USING btree (deleted_at) WHERE deleted_at IS NOT NULL;
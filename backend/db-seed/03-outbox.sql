@@ -0,0 +1,15 @@
+CREATE TABLE outbox(
+    id uuid NOT NULL,
+    event_type text NOT NULL,
+    aggregate_id uuid NOT NULL,
+    payload jsonb NOT NULL,
+    status text NOT NULL DEFAULT 'pending',
+    attempts integer NOT NULL DEFAULT 0,
+    last_error text,
+    available_at timestamp with time zone NOT NULL DEFAULT now(),
+    created_at timestamp with time zone NOT NULL DEFAULT now(),
+    processed_at timestamp with time zone,
+    PRIMARY KEY(id)
+);
+CREATE INDEX idx_outbox_pending ON public.outbox USING btree (available_at) WHERE status = 'pending';
+CREATE INDEX idx_outbox_aggregate_id ON public.outbox USING btree (aggregate_id);
@@ -0,0 +1,14 @@
+CREATE TABLE personal_access_tokens(
+    id uuid NOT NULL,
+    user_id text NOT NULL,
+    name text NOT NULL,
+    token_hash text NOT NULL,
+    scopes text[] NOT NULL,
+    expires_at timestamp with time zone NOT NULL,
+    last_used_at timestamp with time zone,
+    revoked_at timestamp with time zone,
+    created_at timestamp with time zone NOT NULL DEFAULT now(),
+    PRIMARY KEY(id)
+);
+CREATE UNIQUE INDEX idx_personal_access_tokens_token_hash ON public.personal_access_tokens USING btree (token_hash);
+CREATE INDEX idx_personal_access_tokens_user_id ON public.personal_access_tokens USING btree (user_id);
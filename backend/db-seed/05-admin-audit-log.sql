@@ -0,0 +1,12 @@
+CREATE TABLE admin_audit_log(
+    id uuid NOT NULL,
+    operator text NOT NULL,
+    action text NOT NULL,
+    target_id text NOT NULL,
+    reason text,
+    details jsonb NOT NULL,
+    created_at timestamp with time zone NOT NULL DEFAULT now(),
+    PRIMARY KEY(id)
+);
+CREATE INDEX idx_admin_audit_log_operator ON public.admin_audit_log USING btree (operator);
+CREATE INDEX idx_admin_audit_log_target_id ON public.admin_audit_log USING btree (target_id);
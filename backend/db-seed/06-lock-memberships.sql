@@ -0,0 +1,12 @@
+CREATE TABLE lock_memberships(
+    id uuid NOT NULL,
+    lock_id uuid NOT NULL REFERENCES locks(id) ON DELETE CASCADE,
+    user_id text NOT NULL,
+    role text NOT NULL,
+    invited_by text NOT NULL,
+    created_at timestamp with time zone NOT NULL DEFAULT now(),
+    accepted_at timestamp with time zone,
+    PRIMARY KEY(id)
+);
+CREATE UNIQUE INDEX idx_lock_memberships_lock_id_user_id ON public.lock_memberships USING btree (lock_id, user_id);
+CREATE INDEX idx_lock_memberships_user_id ON public.lock_memberships USING btree (user_id);
@@ -0,0 +1,7 @@
+CREATE TABLE account_erasures(
+    user_id text NOT NULL,
+    requested_at timestamp with time zone NOT NULL DEFAULT now(),
+    scheduled_for timestamp with time zone NOT NULL,
+    PRIMARY KEY(user_id)
+);
+CREATE INDEX idx_account_erasures_scheduled_for ON public.account_erasures USING btree (scheduled_for);
@@ -0,0 +1,12 @@
+CREATE TABLE idempotency_keys(
+    user_id text NOT NULL,
+    idempotency_key text NOT NULL,
+    fingerprint text NOT NULL,
+    response_status smallint,
+    response_content_type text,
+    response_body bytea,
+    created_at timestamp with time zone NOT NULL DEFAULT now(),
+    expires_at timestamp with time zone NOT NULL,
+    PRIMARY KEY(user_id, idempotency_key)
+);
+CREATE INDEX idx_idempotency_keys_expires_at ON public.idempotency_keys USING btree (expires_at);
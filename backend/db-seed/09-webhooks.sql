@@ -0,0 +1,28 @@
+CREATE TABLE webhook_subscriptions(
+    id uuid NOT NULL,
+    user_id text NOT NULL,
+    url text NOT NULL,
+    event_types text[] NOT NULL,
+    secret text NOT NULL,
+    created_at timestamp with time zone NOT NULL DEFAULT now(),
+    PRIMARY KEY(id)
+);
+CREATE INDEX idx_webhook_subscriptions_user_id ON public.webhook_subscriptions USING btree (user_id);
+
+CREATE TABLE webhook_deliveries(
+    id uuid NOT NULL,
+    subscription_id uuid NOT NULL REFERENCES webhook_subscriptions(id) ON DELETE CASCADE,
+    user_id text NOT NULL,
+    event_id uuid NOT NULL,
+    payload jsonb NOT NULL,
+    status text NOT NULL DEFAULT 'PENDING',
+    attempts integer NOT NULL DEFAULT 0,
+    last_error text,
+    available_at timestamp with time zone NOT NULL DEFAULT now(),
+    created_at timestamp with time zone NOT NULL DEFAULT now(),
+    processed_at timestamp with time zone,
+    PRIMARY KEY(id)
+);
+CREATE UNIQUE INDEX idx_webhook_deliveries_subscription_id_event_id ON public.webhook_deliveries USING btree (subscription_id, event_id);
+CREATE INDEX idx_webhook_deliveries_pending ON public.webhook_deliveries USING btree (available_at) WHERE status = 'PENDING';
+CREATE INDEX idx_webhook_deliveries_user_id_status ON public.webhook_deliveries USING btree (user_id, status);
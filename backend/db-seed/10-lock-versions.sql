@@ -0,0 +1,3 @@
+-- Every change to a lock or its quests moves the lock to the next version. Lock queries derive
+-- their ETag from it, and commands sent with If-Match only apply while it is unchanged.
+ALTER TABLE locks ADD COLUMN version bigint NOT NULL DEFAULT 1;
@@ -0,0 +1,3 @@
+-- Headers of the stored response that a replay has to repeat, e.g. ETag and Location, as a JSON
+-- array of [name, value] pairs.
+ALTER TABLE idempotency_keys ADD COLUMN response_headers jsonb;
@@ -0,0 +1,3 @@
+-- Handlers that already handled an event. A retry after another handler failed skips them, so
+-- their side effects, e.g. queued webhook deliveries, aren't repeated.
+ALTER TABLE outbox ADD COLUMN handled_by text[] NOT NULL DEFAULT '{}';
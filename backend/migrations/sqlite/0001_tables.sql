@@ -0,0 +1,26 @@
+-- SQLite equivalent of db-seed/01-tables.sql
+CREATE TABLE locks(
+    id text NOT NULL,
+    user_id text NOT NULL,
+    label text,
+    total_shares integer NOT NULL,
+    threshold integer NOT NULL,
+    created_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY(id)
+);
+CREATE INDEX idx_locks_user_id ON locks (user_id);
+CREATE INDEX idx_locks_lock_id_user_id ON locks (id, user_id);
+
+CREATE TABLE quests(
+    id text NOT NULL,
+    lock_id text NOT NULL REFERENCES locks(id) ON DELETE CASCADE,
+    share text NOT NULL,
+    quest_type text NOT NULL,
+    status text NOT NULL,
+    "data" text NOT NULL DEFAULT '{}',
+    created_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY(id)
+);
+CREATE INDEX idx_quests_lock_id ON quests (lock_id);
@@ -0,0 +1,3 @@
+-- SQLite equivalent of db-seed/02-lock-soft-delete.sql
+ALTER TABLE locks ADD COLUMN deleted_at text;
+CREATE INDEX idx_locks_deleted_at ON locks (deleted_at) WHERE deleted_at IS NOT NULL;
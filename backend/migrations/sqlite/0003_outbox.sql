@@ -0,0 +1,16 @@
+-- SQLite equivalent of db-seed/03-outbox.sql
+CREATE TABLE outbox(
+    id text NOT NULL,
+    event_type text NOT NULL,
+    aggregate_id text NOT NULL,
+    payload text NOT NULL,
+    status text NOT NULL DEFAULT 'pending',
+    attempts integer NOT NULL DEFAULT 0,
+    last_error text,
+    available_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    created_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    processed_at text,
+    PRIMARY KEY(id)
+);
+CREATE INDEX idx_outbox_pending ON outbox (available_at) WHERE status = 'pending';
+CREATE INDEX idx_outbox_aggregate_id ON outbox (aggregate_id);
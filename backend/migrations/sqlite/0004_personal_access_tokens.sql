@@ -0,0 +1,15 @@
+-- SQLite equivalent of db-seed/04-personal-access-tokens.sql
+CREATE TABLE personal_access_tokens(
+    id text NOT NULL,
+    user_id text NOT NULL,
+    name text NOT NULL,
+    token_hash text NOT NULL,
+    scopes text NOT NULL,
+    expires_at text NOT NULL,
+    last_used_at text,
+    revoked_at text,
+    created_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY(id)
+);
+CREATE UNIQUE INDEX idx_personal_access_tokens_token_hash ON personal_access_tokens (token_hash);
+CREATE INDEX idx_personal_access_tokens_user_id ON personal_access_tokens (user_id);
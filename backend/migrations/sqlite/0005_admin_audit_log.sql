@@ -0,0 +1,13 @@
+-- SQLite equivalent of db-seed/05-admin-audit-log.sql
+CREATE TABLE admin_audit_log(
+    id text NOT NULL,
+    operator text NOT NULL,
+    action text NOT NULL,
+    target_id text NOT NULL,
+    reason text,
+    details text NOT NULL,
+    created_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY(id)
+);
+CREATE INDEX idx_admin_audit_log_operator ON admin_audit_log (operator);
+CREATE INDEX idx_admin_audit_log_target_id ON admin_audit_log (target_id);
@@ -0,0 +1,13 @@
+-- SQLite equivalent of db-seed/06-lock-memberships.sql
+CREATE TABLE lock_memberships(
+    id text NOT NULL,
+    lock_id text NOT NULL REFERENCES locks(id) ON DELETE CASCADE,
+    user_id text NOT NULL,
+    role text NOT NULL,
+    invited_by text NOT NULL,
+    created_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    accepted_at text,
+    PRIMARY KEY(id)
+);
+CREATE UNIQUE INDEX idx_lock_memberships_lock_id_user_id ON lock_memberships (lock_id, user_id);
+CREATE INDEX idx_lock_memberships_user_id ON lock_memberships (user_id);
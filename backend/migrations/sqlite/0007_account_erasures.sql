@@ -0,0 +1,8 @@
+-- SQLite equivalent of db-seed/07-account-erasures.sql
+CREATE TABLE account_erasures(
+    user_id text NOT NULL,
+    requested_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    scheduled_for text NOT NULL,
+    PRIMARY KEY(user_id)
+);
+CREATE INDEX idx_account_erasures_scheduled_for ON account_erasures (scheduled_for);
@@ -0,0 +1,13 @@
+-- SQLite equivalent of db-seed/08-idempotency-keys.sql
+CREATE TABLE idempotency_keys(
+    user_id text NOT NULL,
+    idempotency_key text NOT NULL,
+    fingerprint text NOT NULL,
+    response_status integer,
+    response_content_type text,
+    response_body blob,
+    created_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    expires_at text NOT NULL,
+    PRIMARY KEY(user_id, idempotency_key)
+);
+CREATE INDEX idx_idempotency_keys_expires_at ON idempotency_keys (expires_at);
@@ -0,0 +1,29 @@
+-- SQLite equivalent of db-seed/09-webhooks.sql
+CREATE TABLE webhook_subscriptions(
+    id text NOT NULL,
+    user_id text NOT NULL,
+    url text NOT NULL,
+    event_types text NOT NULL,
+    secret text NOT NULL,
+    created_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY(id)
+);
+CREATE INDEX idx_webhook_subscriptions_user_id ON webhook_subscriptions (user_id);
+
+CREATE TABLE webhook_deliveries(
+    id text NOT NULL,
+    subscription_id text NOT NULL REFERENCES webhook_subscriptions(id) ON DELETE CASCADE,
+    user_id text NOT NULL,
+    event_id text NOT NULL,
+    payload text NOT NULL,
+    status text NOT NULL DEFAULT 'PENDING',
+    attempts integer NOT NULL DEFAULT 0,
+    last_error text,
+    available_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    created_at text NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    processed_at text,
+    PRIMARY KEY(id)
+);
+CREATE UNIQUE INDEX idx_webhook_deliveries_subscription_id_event_id ON webhook_deliveries (subscription_id, event_id);
+CREATE INDEX idx_webhook_deliveries_pending ON webhook_deliveries (available_at) WHERE status = 'PENDING';
+CREATE INDEX idx_webhook_deliveries_user_id_status ON webhook_deliveries (user_id, status);
@@ -0,0 +1,2 @@
+-- SQLite equivalent of db-seed/10-lock-versions.sql
+ALTER TABLE locks ADD COLUMN version integer NOT NULL DEFAULT 1;
@@ -0,0 +1,2 @@
+-- SQLite equivalent of db-seed/11-idempotency-response-headers.sql
+ALTER TABLE idempotency_keys ADD COLUMN response_headers text;
@@ -0,0 +1,2 @@
+-- SQLite equivalent of db-seed/12-outbox-handled-by.sql, a JSON array of handler names
+ALTER TABLE outbox ADD COLUMN handled_by text NOT NULL DEFAULT '[]';
@@ -0,0 +1,144 @@
+//! Conditional requests on locks, see `application::precondition`.
+//!
+//! Lock queries answer with a strong ETag and honour `If-None-Match`, so a dashboard polling
+//! its locks gets an empty 304 for as long as nothing changed. Commands take the ETag of the
+//! lock they change in `If-Match`.
+use axum::{
+    http::{
+        HeaderMap, StatusCode,
+        header::{ETAG, IF_MATCH, IF_NONE_MATCH},
+    },
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+
+use crate::application::{dtos::lock::LockDTO, precondition::LockPrecondition};
+
+/// The ETag of a single lock, its version and the caller's role. Owners and viewers get
+/// different representations of the same version, so they can't share a tag.
+pub fn lock_etag(lock: &LockDTO) -> String {
+    format!("\"{}-{}\"", lock.version, lock.role)
+}
+
+/// The ETag of a list of locks. It changes when a lock is added, removed or changed, or the
+/// caller's role on one of them does.
+pub fn locks_etag(locks: &[LockDTO]) -> String {
+    let mut hasher = Sha256::new();
+    for lock in locks {
+        hasher.update(format!("{}:{}:{};", lock.id, lock.version, lock.role));
+    }
+    format!("\"{}\"", hex::encode(&hasher.finalize()[..16]))
+}
+
+/// The entity tags listed in a conditional header, `None` when it wasn't sent.
+fn entity_tags(headers: &HeaderMap, name: &axum::http::HeaderName) -> Option<Vec<String>> {
+    if !headers.contains_key(name) {
+        return None;
+    }
+    let tags = headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect();
+    Some(tags)
+}
+
+/// Answer a lock query: 304 when `If-None-Match` already names `etag`, otherwise `body`. Both
+/// carry the ETag.
+pub fn conditional_response(
+    headers: &HeaderMap,
+    etag: String,
+    body: impl IntoResponse,
+) -> Response {
+    // If-None-Match uses the weak comparison, a W/ prefix doesn't matter.
+    let not_modified = entity_tags(headers, &IF_NONE_MATCH).is_some_and(|tags| {
+        tags.iter()
+            .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+    });
+    if not_modified {
+        return (StatusCode::NOT_MODIFIED, [(ETAG, etag)]).into_response();
+    }
+    ([(ETAG, etag)], body).into_response()
+}
+
+/// The precondition a command's `If-Match` puts on its lock.
+pub fn lock_precondition(headers: &HeaderMap) -> LockPrecondition {
+    let Some(tags) = entity_tags(headers, &IF_MATCH) else {
+        return LockPrecondition::Any;
+    };
+    if tags.iter().any(|tag| tag == "*") {
+        return LockPrecondition::Any;
+    }
+    // If-Match uses the strong comparison, weak tags and tags that were never ours can't
+    // match any version. Only the caller's role follows the version, a command is checked
+    // against the version alone.
+    let versions = tags
+        .iter()
+        .filter_map(|tag| {
+            let tag = tag.strip_prefix('"')?.strip_suffix('"')?;
+            let (version, _role) = tag.split_once('-')?;
+            version.parse().ok()
+        })
+        .collect();
+    LockPrecondition::Versions(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn if_match(values: &[&'static str]) -> LockPrecondition {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(IF_MATCH, HeaderValue::from_static(value));
+        }
+        lock_precondition(&headers)
+    }
+
+    #[test]
+    fn no_if_match_or_a_wildcard_allows_any_version() {
+        assert_eq!(if_match(&[]), LockPrecondition::Any);
+        assert_eq!(if_match(&["*"]), LockPrecondition::Any);
+        assert_eq!(if_match(&["\"3-OWNER\", *"]), LockPrecondition::Any);
+    }
+
+    #[test]
+    fn tags_are_checked_by_version_whatever_the_role() {
+        assert_eq!(
+            if_match(&["\"3-OWNER\""]),
+            LockPrecondition::Versions(vec![3])
+        );
+        assert_eq!(
+            if_match(&["\"3-OWNER\", \"4-VIEWER\"", "\"5-EDITOR\""]),
+            LockPrecondition::Versions(vec![3, 4, 5])
+        );
+    }
+
+    #[test]
+    fn weak_and_malformed_tags_match_no_version() {
+        for value in [
+            "W/\"3-OWNER\"",
+            "3-OWNER",
+            "\"3\"",
+            "\"three-OWNER\"",
+            "\"3-OWNER",
+            "\"\"",
+        ] {
+            assert_eq!(
+                if_match(&[value]),
+                LockPrecondition::Versions(vec![]),
+                "{value}"
+            );
+        }
+        assert_eq!(
+            if_match(&["W/\"3-OWNER\", \"4-OWNER\""]),
+            LockPrecondition::Versions(vec![4])
+        );
+    }
+}
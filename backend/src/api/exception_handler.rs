@@ -6,16 +6,17 @@ use axum::{
 
 use tracing::error;
 
-use crate::application::exceptions::AppError;
+use crate::application::exceptions::{AppError, ErrorCode};
 
-use super::schemas::responses::{ApiResponse, RestApiResponse};
+use super::schemas::responses::RestApiResponse;
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
-        let status = match self {
-            AppError::ValidationError(_) => StatusCode::BAD_REQUEST,
+impl AppError {
+    /// The HTTP status the error is answered with.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::ValidationError(..) => StatusCode::BAD_REQUEST,
             AppError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::NotFound(..) => StatusCode::NOT_FOUND,
             AppError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::WrongCredentials => StatusCode::UNAUTHORIZED,
             AppError::MissingCredentials => StatusCode::BAD_REQUEST,
@@ -23,15 +24,18 @@ impl IntoResponse for AppError {
             AppError::TokenCreation => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::UserNotFound => StatusCode::NOT_FOUND,
             AppError::Unauthorised(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(..) => StatusCode::FORBIDDEN,
+            AppError::Conflict(..) => StatusCode::CONFLICT,
+            AppError::PreconditionFailed(..) => StatusCode::PRECONDITION_FAILED,
             AppError::InvalidQuestShare => StatusCode::BAD_REQUEST,
-        };
-        let body = axum::Json(ApiResponse::<()> {
-            status: status.as_u16(),
-            message: self.to_string(),
-            data: None,
-        });
-
-        (status, body).into_response()
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        RestApiResponse::<()>::failure(self.status().as_u16(), self.code(), self.to_string())
+            .into_response()
     }
 }
 
@@ -45,7 +49,11 @@ pub async fn handle_error(error: BoxError) -> impl IntoResponse {
     let message = error.to_string();
     error!(?status, %message, "Request failed");
 
-    let body = RestApiResponse::<()>::failure(status.as_u16(), message);
+    let code = if status == StatusCode::REQUEST_TIMEOUT {
+        ErrorCode::RequestTimeout
+    } else {
+        ErrorCode::InternalError
+    };
 
-    (status, body)
+    RestApiResponse::<()>::failure(status.as_u16(), code, message)
 }
@@ -0,0 +1,393 @@
+use std::marker::PhantomData;
+
+use axum::{
+    Json,
+    extract::{
+        FromRequest, FromRequestParts, Request,
+        rejection::{JsonDataError, JsonRejection},
+    },
+    http::{HeaderValue, StatusCode, header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use serde::de::DeserializeOwned;
+use serde_path_to_error::Segment;
+use validator::{Validate, ValidationErrors, ValidationErrorsKind};
+
+use crate::{
+    api::schemas::responses::{ApiResponse, ErrorDetail},
+    application::{
+        exceptions::{AppError, ErrorCode},
+        policy::authorize_admin,
+    },
+    domain::auth::entity::{AuthMethod, Principal},
+    setup::app_state::AppState,
+};
+
+const REALM: &str = "quest-lock";
+
+/// A scope a route requires the caller's token to carry, see [`AuthenticatedUser`].
+pub trait RequiredScope: Send + Sync {
+    const SCOPE: Option<&'static str>;
+}
+
+/// No scope requirement, any valid token is accepted.
+pub struct AnyScope;
+
+impl RequiredScope for AnyScope {
+    const SCOPE: Option<&'static str> = None;
+}
+
+/// Routes that read locks.
+pub struct ReadLocks;
+
+impl RequiredScope for ReadLocks {
+    const SCOPE: Option<&'static str> = Some("locks:read");
+}
+
+/// Routes that create, change or delete locks and their quests.
+pub struct WriteLocks;
+
+impl RequiredScope for WriteLocks {
+    const SCOPE: Option<&'static str> = Some("locks:write");
+}
+
+/// Routes that record progress on a quest.
+pub struct AttemptQuests;
+
+impl RequiredScope for AttemptQuests {
+    const SCOPE: Option<&'static str> = Some("quests:attempt");
+}
+
+/// The caller, authenticated from the `Authorization: Bearer` header through
+/// `AppState.auth_service`. Handlers that take it can't run for an unauthenticated request.
+///
+/// The principal is kept in the request extensions, so a middleware that authenticated the
+/// request already doesn't cause a second verification, or a second use of a personal access
+/// token.
+///
+/// Routes that need a scope name it as the type parameter, e.g.
+/// `AuthenticatedUser<WriteLocks>`.
+pub struct AuthenticatedUser<S: RequiredScope = AnyScope> {
+    pub principal: Principal,
+    _scope: PhantomData<S>,
+}
+
+impl<S: RequiredScope> AuthenticatedUser<S> {
+    /// Id of the user, the token subject.
+    pub fn user_id(&self) -> String {
+        self.principal.subject.clone()
+    }
+
+    /// Reject personal access tokens, for routes only an interactive session may use.
+    pub fn require_session(&self) -> Result<(), AppError> {
+        match self.principal.method {
+            AuthMethod::Jwt => Ok(()),
+            AuthMethod::PersonalAccessToken { .. } => Err(AppError::Forbidden(
+                ErrorCode::SessionRequired,
+                "Personal access tokens cannot be used for this request".to_string(),
+            )),
+        }
+    }
+}
+
+/// An operator, authenticated like [`AuthenticatedUser`] and carrying the admin role. Only an
+/// interactive session qualifies, personal access tokens are refused.
+pub struct AdminUser {
+    pub principal: Principal,
+}
+
+/// Why a request could not be authenticated, answered as described in RFC 6750 section 3.
+#[derive(Debug)]
+pub enum AuthRejection {
+    /// No bearer token was sent, the challenge carries no error code.
+    MissingToken,
+    /// The Authorization header isn't a well formed bearer credential.
+    InvalidRequest,
+    InvalidToken(String),
+    InsufficientScope(&'static str),
+    Error(AppError),
+}
+
+impl IntoResponse for AuthRejection {
+    fn into_response(self) -> Response {
+        let (status, code, message, challenge) = match self {
+            AuthRejection::Error(err) => return err.into_response(),
+            AuthRejection::MissingToken => (
+                StatusCode::UNAUTHORIZED,
+                ErrorCode::Unauthenticated,
+                "Missing bearer token".to_string(),
+                format!(r#"Bearer realm="{REALM}""#),
+            ),
+            AuthRejection::InvalidRequest => (
+                StatusCode::BAD_REQUEST,
+                ErrorCode::MalformedAuthorization,
+                "Malformed Authorization header".to_string(),
+                format!(r#"Bearer realm="{REALM}", error="invalid_request""#),
+            ),
+            AuthRejection::InvalidToken(message) => (
+                StatusCode::UNAUTHORIZED,
+                ErrorCode::InvalidToken,
+                message.clone(),
+                format!(
+                    r#"Bearer realm="{REALM}", error="invalid_token", error_description="{}""#,
+                    message.replace(['"', '\\'], "'")
+                ),
+            ),
+            AuthRejection::InsufficientScope(scope) => (
+                StatusCode::FORBIDDEN,
+                ErrorCode::InsufficientScope,
+                format!("Token is missing the '{scope}' scope"),
+                format!(r#"Bearer realm="{REALM}", error="insufficient_scope", scope="{scope}""#),
+            ),
+        };
+
+        let body = axum::Json(ApiResponse::<()>::failure(status.as_u16(), code, message));
+        let mut response = (status, body).into_response();
+        if let Ok(challenge) = HeaderValue::from_str(&challenge) {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, challenge);
+        }
+        response
+    }
+}
+
+fn bearer_token(parts: &Parts) -> Result<&str, AuthRejection> {
+    let header = match parts.headers.get(header::AUTHORIZATION) {
+        Some(header) => header,
+        None => return Err(AuthRejection::MissingToken),
+    };
+    let header = header.to_str().map_err(|_| AuthRejection::InvalidRequest)?;
+
+    match header.split_once(' ') {
+        Some((scheme, token)) if scheme.eq_ignore_ascii_case("bearer") => {
+            let token = token.trim();
+            if token.is_empty() {
+                Err(AuthRejection::InvalidRequest)
+            } else {
+                Ok(token)
+            }
+        }
+        // Another scheme is the same as no bearer token at all.
+        _ => Err(AuthRejection::MissingToken),
+    }
+}
+
+impl<S: RequiredScope> FromRequestParts<AppState> for AuthenticatedUser<S> {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let principal = match parts.extensions.get::<Principal>() {
+            Some(principal) => principal.clone(),
+            None => {
+                let token = bearer_token(parts)?;
+                let verified = state.auth_service.verify(token).await;
+                let principal = verified.map_err(|err| match err {
+                    AppError::Unauthorised(message) => AuthRejection::InvalidToken(message),
+                    err => AuthRejection::Error(err),
+                })?;
+                parts.extensions.insert(principal.clone());
+                principal
+            }
+        };
+
+        if let Some(scope) = S::SCOPE
+            && !principal.allows(scope)
+        {
+            return Err(AuthRejection::InsufficientScope(scope));
+        }
+
+        Ok(Self {
+            principal,
+            _scope: PhantomData,
+        })
+    }
+}
+
+impl FromRequestParts<AppState> for AdminUser {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let user = AuthenticatedUser::<AnyScope>::from_request_parts(parts, state).await?;
+        user.require_session().map_err(AuthRejection::Error)?;
+        authorize_admin(&user.principal).map_err(AuthRejection::Error)?;
+
+        Ok(Self {
+            principal: user.principal,
+        })
+    }
+}
+
+/// A JSON request body, like `axum::Json` but rejected in the API's envelope. When the body
+/// doesn't have the expected shape the offending field is reported as a JSON pointer.
+pub struct JsonBody<T>(pub T);
+
+impl<T, S> FromRequest<S> for JsonBody<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = JsonBodyRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(JsonBodyRejection)?;
+        Ok(Self(value))
+    }
+}
+
+/// Why a JSON request body could not be read. The status is the one axum picks: 415 without a
+/// JSON content type, 400 for a body that isn't JSON and 422 for one of the wrong shape.
+#[derive(Debug)]
+pub struct JsonBodyRejection(JsonRejection);
+
+impl IntoResponse for JsonBodyRejection {
+    fn into_response(self) -> Response {
+        let status = self.0.status();
+        let (code, details) = match &self.0 {
+            JsonRejection::JsonDataError(err) => (
+                ErrorCode::InvalidBody,
+                data_error_detail(err).into_iter().collect(),
+            ),
+            JsonRejection::MissingJsonContentType(_) => (ErrorCode::UnsupportedMediaType, vec![]),
+            _ => (ErrorCode::MalformedBody, vec![]),
+        };
+
+        let body = ApiResponse::<()>::failure(status.as_u16(), code, self.0.body_text())
+            .with_details(details);
+        (status, axum::Json(body)).into_response()
+    }
+}
+
+/// The field serde gave up on, found in the error chain axum keeps from `serde_path_to_error`.
+fn data_error_detail(err: &JsonDataError) -> Option<ErrorDetail> {
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        if let Some(err) = err.downcast_ref::<serde_path_to_error::Error<serde_json::Error>>() {
+            let message = err.inner().to_string();
+            let mut field = json_pointer(err.path());
+            // serde stops at the object missing a field, point at the field itself.
+            if let Some(name) = message
+                .strip_prefix("missing field `")
+                .and_then(|rest| rest.split_once('`'))
+                .map(|(name, _)| name)
+            {
+                field = format!("{field}/{}", escape_pointer_token(name));
+            }
+            return Some(ErrorDetail { field, message });
+        }
+        source = err.source();
+    }
+    None
+}
+
+/// A JSON request body that is also checked against its `Validate` rules. Every violation is
+/// reported in one 422 response, each with the JSON pointer of the field it is about.
+pub struct ValidatedJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ValidatedJsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let JsonBody(value) = JsonBody::<T>::from_request(req, state)
+            .await
+            .map_err(ValidatedJsonRejection::Body)?;
+        value.validate().map_err(ValidatedJsonRejection::Invalid)?;
+        Ok(Self(value))
+    }
+}
+
+/// Why a [`ValidatedJson`] body was refused.
+#[derive(Debug)]
+pub enum ValidatedJsonRejection {
+    Body(JsonBodyRejection),
+    Invalid(ValidationErrors),
+}
+
+impl IntoResponse for ValidatedJsonRejection {
+    fn into_response(self) -> Response {
+        let errors = match self {
+            ValidatedJsonRejection::Body(rejection) => return rejection.into_response(),
+            ValidatedJsonRejection::Invalid(errors) => errors,
+        };
+
+        let details = violations(&errors, "");
+        let status = StatusCode::UNPROCESSABLE_ENTITY;
+        let body = ApiResponse::<()>::failure(
+            status.as_u16(),
+            ErrorCode::ValidationFailed,
+            "The request failed validation",
+        )
+        .with_details(details);
+        (status, axum::Json(body)).into_response()
+    }
+}
+
+/// One detail per violation, sorted by field. `pointer` locates the validated value in the body,
+/// "" for the body itself.
+pub fn violations(errors: &ValidationErrors, pointer: &str) -> Vec<ErrorDetail> {
+    let mut details = Vec::new();
+    collect_violations(errors, pointer, &mut details);
+    details.sort_by(|a, b| a.field.cmp(&b.field));
+    details
+}
+
+/// Flatten the nested errors of `validator` into one detail per violation. Struct level errors
+/// (`__all__`) are reported against the struct, or against the field named by their `field` param.
+fn collect_violations(errors: &ValidationErrors, pointer: &str, details: &mut Vec<ErrorDetail>) {
+    for (field, kind) in errors.errors() {
+        let pointer = if field == "__all__" {
+            pointer.to_string()
+        } else {
+            format!("{pointer}/{}", escape_pointer_token(field))
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                for error in errors {
+                    let field = match error.params.get("field").and_then(|field| field.as_str()) {
+                        Some(field) => format!("{pointer}/{}", escape_pointer_token(field)),
+                        None => pointer.clone(),
+                    };
+                    let message = match &error.message {
+                        Some(message) => message.to_string(),
+                        None => format!("Failed the '{}' check", error.code),
+                    };
+                    details.push(ErrorDetail { field, message });
+                }
+            }
+            ValidationErrorsKind::Struct(errors) => collect_violations(errors, &pointer, details),
+            ValidationErrorsKind::List(errors) => {
+                for (index, errors) in errors {
+                    collect_violations(errors, &format!("{pointer}/{index}"), details);
+                }
+            }
+        }
+    }
+}
+
+/// Render a deserialisation path as a JSON pointer (RFC 6901), the root being "".
+pub fn json_pointer(path: &serde_path_to_error::Path) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            Segment::Seq { index } => format!("/{index}"),
+            Segment::Map { key } => format!("/{}", escape_pointer_token(key)),
+            Segment::Enum { variant } => format!("/{variant}"),
+            Segment::Unknown => "/?".to_string(),
+        })
+        .collect()
+}
+
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
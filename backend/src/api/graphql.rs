@@ -0,0 +1,272 @@
+//! A read-only GraphQL view of the caller's locks, for clients that pick the fields each screen
+//! needs. Everything is resolved through `LockQueryServiceTrait`, so the same ownership checks
+//! and redaction apply as to the REST queries.
+//!
+//! Quest shares are not part of the schema at all. Queries nested deeper or costing more than
+//! `GRAPHQL_MAX_DEPTH` and `GRAPHQL_MAX_COMPLEXITY` are refused before they run.
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, ErrorExtensions, ID, Object, Schema, SimpleObject,
+};
+use axum::{Extension, Json, Router, extract::State, http::HeaderMap, routing::post};
+use chrono::{DateTime, Utc};
+
+use crate::{
+    api::extractors::{AuthenticatedUser, JsonBody, ReadLocks},
+    api::routes::lock_queries::read_preference,
+    application::{
+        dtos::{lock::LockDTO, quest::QuestDTO},
+        exceptions::AppError,
+        services::lock_query_service::LockQueryServiceTrait,
+    },
+    domain::{
+        auth::entity::Principal,
+        event::entity::{DomainEvent, DomainEventKind},
+        lock::repository::ReadPreference,
+        quest::enums::QuestStatus,
+    },
+    setup::{app_state::AppState, config::Config},
+};
+
+/// Where the GraphQL endpoint is served.
+pub const GRAPHQL_PATH: &str = "/api/v1/graphql";
+
+pub type QuestLockSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// The caller a query is resolved for.
+struct Viewer {
+    principal: Principal,
+    read: ReadPreference,
+    locks: Arc<dyn LockQueryServiceTrait>,
+}
+
+/// The error as a GraphQL error, with the REST error code as `extensions.code`.
+fn graphql_error(err: AppError) -> async_graphql::Error {
+    let code = serde_json::to_value(err.code())
+        .ok()
+        .and_then(|code| code.as_str().map(str::to_string))
+        .unwrap_or_default();
+    async_graphql::Error::new(err.to_string()).extend_with(|_, extensions| {
+        extensions.set("code", code);
+    })
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The caller's own locks and the locks shared with them, oldest first. With `shared`,
+    /// only the locks shared with them.
+    async fn locks(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] shared: bool,
+    ) -> async_graphql::Result<Vec<LockObject>> {
+        let viewer = ctx.data::<Viewer>()?;
+        let locks = if shared {
+            viewer
+                .locks
+                .get_shared_locks(&viewer.principal, viewer.read)
+                .await
+        } else {
+            viewer.locks.get_locks(&viewer.principal, viewer.read).await
+        }
+        .map_err(graphql_error)?;
+
+        Ok(locks.into_iter().map(LockObject).collect())
+    }
+
+    /// A lock the caller owns or that is shared with them.
+    async fn lock(&self, ctx: &Context<'_>, id: ID) -> async_graphql::Result<LockObject> {
+        let viewer = ctx.data::<Viewer>()?;
+        let lock = viewer
+            .locks
+            .get_lock_by_id(&viewer.principal, id.to_string(), viewer.read)
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(LockObject(lock))
+    }
+}
+
+pub struct LockObject(LockDTO);
+
+#[Object(name = "Lock")]
+impl LockObject {
+    async fn id(&self) -> ID {
+        ID(self.0.id.clone())
+    }
+
+    async fn label(&self) -> Option<&str> {
+        self.0.label.as_deref()
+    }
+
+    async fn total_shares(&self) -> u8 {
+        self.0.total_shares
+    }
+
+    async fn threshold(&self) -> u8 {
+        self.0.threshold
+    }
+
+    /// Moves on with every change to the lock or its quests.
+    async fn version(&self) -> i64 {
+        self.0.version
+    }
+
+    /// The caller's role, VIEWER for a lock shared with them.
+    async fn role(&self) -> &str {
+        &self.0.role
+    }
+
+    async fn quests(&self) -> Vec<QuestObject> {
+        self.0.quests.iter().cloned().map(QuestObject).collect()
+    }
+
+    async fn progress(&self) -> Progress {
+        let completed = self
+            .0
+            .quests
+            .iter()
+            .filter(|quest| quest.status == QuestStatus::COMPLETED.to_string())
+            .count() as u8;
+        Progress {
+            completed,
+            required: self.0.threshold,
+            unlockable: completed >= self.0.threshold,
+        }
+    }
+
+    /// What happened to the lock, oldest first. Each lock's history is a query of its own, so
+    /// it costs more than the other fields.
+    #[graphql(complexity = "10 + child_complexity")]
+    async fn history(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<EventObject>> {
+        let viewer = ctx.data::<Viewer>()?;
+        let events = viewer
+            .locks
+            .get_lock_history(&viewer.principal, self.0.id.clone(), viewer.read)
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(events.into_iter().map(EventObject).collect())
+    }
+}
+
+/// How far the lock is from being unlockable.
+#[derive(SimpleObject)]
+pub struct Progress {
+    /// Quests completed so far.
+    completed: u8,
+    /// Quests that need to be completed, the lock's threshold.
+    required: u8,
+    unlockable: bool,
+}
+
+/// A quest without its share, which is never sent over GraphQL.
+pub struct QuestObject(QuestDTO);
+
+#[Object(name = "Quest")]
+impl QuestObject {
+    async fn id(&self) -> ID {
+        ID(self.0.id.clone())
+    }
+
+    async fn lock_id(&self) -> ID {
+        ID(self.0.lock_id.clone())
+    }
+
+    async fn quest_type(&self) -> &str {
+        &self.0.quest_type
+    }
+
+    async fn status(&self) -> &str {
+        &self.0.status
+    }
+
+    /// Empty for viewers of a shared lock.
+    async fn data(&self) -> &HashMap<String, String> {
+        &self.0.data
+    }
+}
+
+pub struct EventObject(DomainEvent);
+
+#[Object(name = "LockEvent")]
+impl EventObject {
+    async fn id(&self) -> ID {
+        ID(self.0.id.to_string())
+    }
+
+    /// The event type, e.g. `quest.status_changed`.
+    #[graphql(name = "type")]
+    async fn event_type(&self) -> &str {
+        self.0.event_type()
+    }
+
+    async fn occurred_at(&self) -> DateTime<Utc> {
+        self.0.occurred_at
+    }
+
+    /// Set for quest events.
+    async fn quest_id(&self) -> Option<ID> {
+        match &self.0.kind {
+            DomainEventKind::QuestPlanned { quest_id, .. }
+            | DomainEventKind::QuestStatusChanged { quest_id, .. } => {
+                Some(ID(quest_id.to_string()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Set for `quest.planned`.
+    async fn quest_type(&self) -> Option<String> {
+        match &self.0.kind {
+            DomainEventKind::QuestPlanned { quest_type, .. } => Some(quest_type.to_string()),
+            _ => None,
+        }
+    }
+
+    /// The quest's previous status, set for `quest.status_changed`.
+    async fn from(&self) -> Option<String> {
+        match &self.0.kind {
+            DomainEventKind::QuestStatusChanged { from, .. } => Some(from.to_string()),
+            _ => None,
+        }
+    }
+
+    /// The quest's new status, set for `quest.status_changed`.
+    async fn to(&self) -> Option<String> {
+        match &self.0.kind {
+            DomainEventKind::QuestStatusChanged { to, .. } => Some(to.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Run a query for the caller. Errors are reported in the GraphQL response, with a 200.
+pub async fn graphql_handler(
+    State(state): State<AppState>,
+    Extension(schema): Extension<QuestLockSchema>,
+    user: AuthenticatedUser<ReadLocks>,
+    headers: HeaderMap,
+    JsonBody(request): JsonBody<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    let viewer = Viewer {
+        principal: user.principal,
+        read: read_preference(&headers),
+        locks: state.lock_query_service.clone(),
+    };
+    Json(schema.execute(request.data(viewer)).await)
+}
+
+pub fn graphql_router(config: &Config) -> Router<AppState> {
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(config.graphql_max_depth)
+        .limit_complexity(config.graphql_max_complexity)
+        .finish();
+
+    Router::new()
+        .route(GRAPHQL_PATH, post(graphql_handler))
+        .layer(Extension(schema))
+}
@@ -0,0 +1,222 @@
+//! `Idempotency-Key` support for the command routes.
+//!
+//! A client that can't tell whether a command went through, e.g. after a timeout, sends it again
+//! with the same key. The first response is stored for the user and key, and sent back for the
+//! retry instead of running the command twice. The key can't be reused for a different request.
+
+use axum::{
+    body::Body,
+    extract::{FromRequestParts, Request, State},
+    http::{
+        HeaderMap, HeaderName, HeaderValue, Method, StatusCode,
+        header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, LOCATION},
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http_body_util::BodyExt;
+use sha2::{Digest, Sha256};
+use tracing::error;
+
+use crate::{
+    api::{
+        extractors::{AnyScope, AuthenticatedUser},
+        schemas::responses::RestApiResponse,
+    },
+    application::{exceptions::ErrorCode, services::idempotency_service::IdempotencyOutcome},
+    domain::idempotency::entity::StoredResponse,
+    setup::app_state::AppState,
+};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Set on a response that was replayed rather than produced by running the command.
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+const MAX_KEY_LENGTH: usize = 255;
+
+/// Response headers kept with the body, a retry needs them as much as the first caller did.
+const REPLAYED_HEADERS: [HeaderName; 2] = [ETAG, LOCATION];
+
+fn parse_key(headers: &HeaderMap) -> Option<Result<String, ()>> {
+    let value = headers.get(IDEMPOTENCY_KEY_HEADER)?;
+    let valid = value.to_str().ok().filter(|key| {
+        !key.is_empty() && key.len() <= MAX_KEY_LENGTH && key.bytes().all(|b| b.is_ascii_graphic())
+    });
+    Some(valid.map(str::to_string).ok_or(()))
+}
+
+/// Identifies the request a key was used for: the method, the path with its query, and the body.
+fn fingerprint(method: &Method, path_and_query: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str());
+    hasher.update(b"\n");
+    hasher.update(path_and_query);
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let mut res = Response::new(Body::from(stored.body));
+    *res.status_mut() = status;
+    if let Some(content_type) = stored
+        .content_type
+        .and_then(|value| HeaderValue::from_str(&value).ok())
+    {
+        res.headers_mut().insert(CONTENT_TYPE, content_type);
+    }
+    for (name, value) in stored.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            res.headers_mut().insert(name, value);
+        }
+    }
+    res.headers_mut()
+        .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    res
+}
+
+/// Only successful responses are kept, a failed command can be retried with the same key.
+/// Responses marked `no-store` are never kept, they carry secrets.
+fn should_store(res: &Response) -> bool {
+    res.status().is_success()
+        && !res
+            .headers()
+            .get(CACHE_CONTROL)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.contains("no-store"))
+}
+
+/// Middleware honouring `Idempotency-Key` on every method but GET, HEAD and OPTIONS.
+///
+/// Requests without the header, or that can't be authenticated, go through untouched, the
+/// route rejects the latter itself. The caller is authenticated once, the route's extractor
+/// reuses the principal found here.
+pub async fn idempotency_middleware(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        return next.run(req).await;
+    }
+    let key = match parse_key(req.headers()) {
+        None => return next.run(req).await,
+        Some(Ok(key)) => key,
+        Some(Err(())) => {
+            return RestApiResponse::<()>::failure(
+                StatusCode::BAD_REQUEST.as_u16(),
+                ErrorCode::InvalidIdempotencyKey,
+                format!(
+                    "The Idempotency-Key header must be 1 to {MAX_KEY_LENGTH} visible ASCII characters"
+                ),
+            )
+            .into_response();
+        }
+    };
+
+    let (mut parts, body) = req.into_parts();
+    let Ok(user) = AuthenticatedUser::<AnyScope>::from_request_parts(&mut parts, &state).await
+    else {
+        return next.run(Request::from_parts(parts, body)).await;
+    };
+    let user_id = user.user_id();
+
+    let body = match body.collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(err) => {
+            return RestApiResponse::<()>::failure(
+                StatusCode::BAD_REQUEST.as_u16(),
+                ErrorCode::MalformedBody,
+                format!("Failed to read the request body: {err}"),
+            )
+            .into_response();
+        }
+    };
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or_else(|| parts.uri.path());
+    let fingerprint = fingerprint(&parts.method, path_and_query, &body);
+
+    let outcome = match state
+        .idempotency_service
+        .begin(user_id.clone(), key.clone(), fingerprint)
+        .await
+    {
+        Ok(outcome) => outcome,
+        Err(err) => return err.into_response(),
+    };
+    match outcome {
+        IdempotencyOutcome::Proceed => {}
+        IdempotencyOutcome::Replay(stored) => return replay(stored),
+        IdempotencyOutcome::Mismatch => {
+            return RestApiResponse::<()>::failure(
+                StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+                ErrorCode::IdempotencyKeyReused,
+                "The idempotency key was already used for a different request".to_string(),
+            )
+            .into_response();
+        }
+        IdempotencyOutcome::InProgress => {
+            return RestApiResponse::<()>::failure(
+                StatusCode::CONFLICT.as_u16(),
+                ErrorCode::IdempotencyKeyInProgress,
+                "A request with this idempotency key is still being handled".to_string(),
+            )
+            .into_response();
+        }
+    }
+
+    let res = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    if !should_store(&res) {
+        if let Err(err) = state.idempotency_service.release(user_id, key).await {
+            error!("Failed to release idempotency key: {err}");
+        }
+        return res;
+    }
+
+    let (res_parts, res_body) = res.into_parts();
+    let res_bytes = match res_body.collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(err) => {
+            error!("Failed to read response body: {err}");
+            if let Err(err) = state.idempotency_service.release(user_id, key).await {
+                error!("Failed to release idempotency key: {err}");
+            }
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let stored = StoredResponse {
+        status: res_parts.status.as_u16(),
+        content_type: res_parts
+            .headers
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string),
+        headers: REPLAYED_HEADERS
+            .iter()
+            .filter_map(|name| {
+                let value = res_parts.headers.get(name)?.to_str().ok()?;
+                Some((name.as_str().to_string(), value.to_string()))
+            })
+            .collect(),
+        body: res_bytes.to_vec(),
+    };
+    // The command went through either way, a retry just runs it again if this fails.
+    if let Err(err) = state
+        .idempotency_service
+        .complete(user_id, key, stored)
+        .await
+    {
+        error!("Failed to store idempotent response: {err}");
+    }
+
+    Response::from_parts(res_parts, Body::from(res_bytes))
+}
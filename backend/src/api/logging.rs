@@ -0,0 +1,285 @@
+use std::{collections::HashSet, sync::Arc, time::Instant};
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::{Request, State},
+    http::{HeaderMap, Response, StatusCode, header::CONTENT_LENGTH, header::CONTENT_TYPE},
+    middleware::Next,
+    response::IntoResponse,
+};
+use http_body_util::{BodyExt, LengthLimitError, Limited};
+use serde_json::Value;
+use tracing::{Level, debug, enabled, info};
+
+use crate::{
+    api::schemas::responses::RestApiResponse, application::exceptions::ErrorCode,
+    setup::config::Config,
+};
+
+const REDACTED: &str = "[REDACTED]";
+
+/// What the request/response logging middleware may buffer and what it has to keep out of the
+/// logs. Field and header names are matched case-insensitively.
+#[derive(Clone, Debug)]
+pub struct LogRedaction {
+    fields: Arc<HashSet<String>>,
+    headers: Arc<HashSet<String>>,
+    max_request_body_bytes: usize,
+    max_logged_body_bytes: usize,
+}
+
+impl LogRedaction {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            fields: Arc::new(names(&config.log_redacted_fields)),
+            headers: Arc::new(names(&config.log_redacted_headers)),
+            max_request_body_bytes: config.max_request_body_bytes,
+            max_logged_body_bytes: config.log_max_body_bytes,
+        }
+    }
+
+    pub fn max_request_body_bytes(&self) -> usize {
+        self.max_request_body_bytes
+    }
+
+    /// The headers as they may be logged, sensitive values masked.
+    fn headers(&self, headers: &HeaderMap) -> Vec<(String, String)> {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if self.headers.contains(name.as_str()) {
+                    REDACTED.to_string()
+                } else {
+                    String::from_utf8_lossy(value.as_bytes()).into_owned()
+                };
+                (name.to_string(), value)
+            })
+            .collect()
+    }
+
+    /// The body as it may be logged. Only JSON is inspected, anything else could hold a secret
+    /// we can't find, so just its size is logged.
+    fn body(&self, bytes: &[u8]) -> String {
+        if bytes.is_empty() {
+            return String::new();
+        }
+        match serde_json::from_slice::<Value>(bytes) {
+            Ok(mut value) => {
+                self.redact(&mut value);
+                value.to_string()
+            }
+            Err(_) => format!("<{} bytes, not JSON>", bytes.len()),
+        }
+    }
+
+    fn redact(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if self.fields.contains(&key.to_ascii_lowercase()) {
+                        *value = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact(value);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact(item)),
+            _ => {}
+        }
+    }
+}
+
+fn names(list: &str) -> HashSet<String> {
+    list.split(',')
+        .map(|name| name.trim().to_ascii_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Logs every request and response. Bodies are only buffered for logging when debug logging is
+/// on, and then only when they fit in `LOG_MAX_BODY_BYTES`. Request bodies over
+/// `MAX_REQUEST_BODY_BYTES` are refused with a 413 before a handler sees them.
+pub async fn log_request_response(
+    State(redaction): State<LogRedaction>,
+    req: Request,
+    next: Next,
+) -> Response<Body> {
+    let method = req.method().clone();
+    let uri = req.uri().clone();
+    let debug_enabled = enabled!(Level::DEBUG);
+
+    info!("--> {} {}", method, uri);
+
+    let (req_parts, req_body) = req.into_parts();
+    if debug_enabled {
+        debug!(
+            "request headers = {:?}",
+            redaction.headers(&req_parts.headers)
+        );
+    }
+
+    let declared_length = req_parts
+        .headers
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared_length.is_some_and(|length| length > redaction.max_request_body_bytes) {
+        info!("<-- 413 {} {} (request body too large)", method, uri);
+        return payload_too_large(redaction.max_request_body_bytes);
+    }
+
+    let req_bytes = match Limited::new(req_body, redaction.max_request_body_bytes)
+        .collect()
+        .await
+    {
+        Ok(collected) => collected.to_bytes(),
+        Err(err) if err.is::<LengthLimitError>() => {
+            info!("<-- 413 {} {} (request body too large)", method, uri);
+            return payload_too_large(redaction.max_request_body_bytes);
+        }
+        Err(err) => {
+            return RestApiResponse::<()>::failure(
+                StatusCode::BAD_REQUEST.as_u16(),
+                ErrorCode::MalformedBody,
+                format!("failed to read request body: {err}"),
+            )
+            .into_response();
+        }
+    };
+    if debug_enabled && req_bytes.len() <= redaction.max_logged_body_bytes {
+        debug!("request body = {}", redaction.body(&req_bytes));
+    }
+    let req = Request::from_parts(req_parts, Body::from(req_bytes));
+
+    let start = Instant::now();
+    let res = next.run(req).await;
+    let latency = start.elapsed();
+
+    let status = res.status();
+
+    // A stream only ends when the client goes away, it can't be buffered.
+    let is_stream = res
+        .headers()
+        .get(CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(b"text/event-stream"));
+    if is_stream {
+        info!("<-- {} {} {} (stream)", status.as_u16(), method, uri);
+        return res;
+    }
+
+    info!(
+        "<-- {} {} {} {}",
+        status.as_u16(),
+        method,
+        uri,
+        latency.as_micros()
+    );
+
+    if !debug_enabled {
+        return res;
+    }
+    debug!("response headers = {:?}", redaction.headers(res.headers()));
+
+    // Only buffer bodies known to be small, anything else is passed through as it streams.
+    let fits = res
+        .body()
+        .size_hint()
+        .upper()
+        .is_some_and(|upper| upper <= redaction.max_logged_body_bytes as u64);
+    if !fits {
+        debug!(
+            "response body not logged, it may be larger than {} bytes",
+            redaction.max_logged_body_bytes
+        );
+        return res;
+    }
+
+    let (res_parts, res_body) = res.into_parts();
+    let res_bytes: Bytes = match res_body.collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(err) => {
+            return RestApiResponse::<()>::failure(
+                StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+                ErrorCode::InternalError,
+                format!("failed to read response body: {err}"),
+            )
+            .into_response();
+        }
+    };
+    debug!("response body = {}", redaction.body(&res_bytes));
+
+    Response::from_parts(res_parts, Body::from(res_bytes))
+}
+
+fn payload_too_large(limit: usize) -> Response<Body> {
+    RestApiResponse::<()>::failure(
+        StatusCode::PAYLOAD_TOO_LARGE.as_u16(),
+        ErrorCode::PayloadTooLarge,
+        format!("Request body is larger than {limit} bytes"),
+    )
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+    use serde_json::json;
+
+    use super::*;
+
+    fn redaction() -> LogRedaction {
+        LogRedaction::from_config(&Config {
+            log_redacted_fields: "Share, secret".to_string(),
+            log_redacted_headers: "Authorization, X-Api-Key".to_string(),
+            ..Config::default()
+        })
+    }
+
+    #[test]
+    fn nested_fields_are_redacted() {
+        let body = json!({
+            "label": "bike",
+            "SHARE": "top level",
+            "quests": [
+                {"share": "in an array", "data": {"secret": "nested", "release_date": "2030-01-01"}},
+            ],
+        });
+        let logged: Value =
+            serde_json::from_str(&redaction().body(body.to_string().as_bytes())).unwrap();
+
+        assert_eq!(
+            logged,
+            json!({
+                "label": "bike",
+                "SHARE": REDACTED,
+                "quests": [
+                    {"share": REDACTED, "data": {"secret": REDACTED, "release_date": "2030-01-01"}},
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn bodies_other_than_json_are_not_logged() {
+        assert_eq!(redaction().body(b"share=secret"), "<12 bytes, not JSON>");
+    }
+
+    #[test]
+    fn headers_are_masked_whatever_their_case() {
+        let mut headers = HeaderMap::new();
+        headers.insert("AUTHORIZATION", HeaderValue::from_static("Bearer token"));
+        headers.insert("x-api-key", HeaderValue::from_static("key"));
+        headers.insert("Accept", HeaderValue::from_static("application/json"));
+
+        let mut logged = redaction().headers(&headers);
+        logged.sort();
+        assert_eq!(
+            logged,
+            vec![
+                ("accept".to_string(), "application/json".to_string()),
+                ("authorization".to_string(), REDACTED.to_string()),
+                ("x-api-key".to_string(), REDACTED.to_string()),
+            ]
+        );
+    }
+}
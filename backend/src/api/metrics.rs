@@ -0,0 +1,52 @@
+use std::time::Instant;
+
+use axum::{
+    Router,
+    extract::{MatchedPath, Request, State},
+    http::header::CONTENT_TYPE,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+
+use crate::setup::app_state::AppState;
+
+pub const METRICS_PATH: &str = "/metrics";
+
+/// Route label of requests that matched no route, so unknown paths can't grow the label set.
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// The `/metrics` route. It isn't authenticated, so it is only served on the listener of
+/// `METRICS_PORT`, never with the API.
+pub fn metrics_router() -> Router<AppState> {
+    Router::new().route(METRICS_PATH, get(metrics_handler))
+}
+
+async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        state.metrics_service.render(),
+    )
+}
+
+/// Counts and times every request by method, matched route and status.
+pub async fn track_metrics(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let method = req.method().clone();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+
+    let start = Instant::now();
+    let res = next.run(req).await;
+
+    state.metrics_service.observe_request(
+        method.as_str(),
+        &route,
+        res.status().as_u16(),
+        start.elapsed(),
+    );
+
+    res
+}
@@ -1,4 +1,11 @@
+pub mod conditional;
 pub mod exception_handler;
+pub mod extractors;
+pub mod graphql;
+pub mod idempotency;
+pub mod logging;
+pub mod metrics;
+pub mod openapi;
 pub mod router;
 pub mod routes;
 pub mod schemas;
@@ -0,0 +1,149 @@
+use utoipa::{
+    Modify, OpenApi,
+    openapi::{
+        ContentBuilder, Object, Ref, RefOr, Required, ResponseBuilder, Type,
+        path::{Operation, ParameterBuilder, ParameterIn},
+        security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    },
+};
+
+use crate::api::router::api_router;
+
+/// Where the OpenAPI document is served.
+pub const OPENAPI_PATH: &str = "/api/v1/openapi.json";
+
+/// Where the interactive documentation is served.
+pub const DOCS_PATH: &str = "/api/v1/docs";
+
+/// Name of the security scheme the paths refer to.
+pub const BEARER_AUTH: &str = "bearer";
+
+/// The parts of the document that don't come from the handlers. Paths and schemas are
+/// collected from the `#[utoipa::path]` attributes of the handlers as they are routed.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Quest Lock API",
+        description = "Locks split a secret into shares, each released by completing a quest."
+    ),
+    modifiers(&BearerAuth, &WithoutLicense),
+    tags(
+        (name = "locks", description = "Creating, reading and deleting locks"),
+        (name = "quests", description = "Reading quests and recording progress"),
+        (name = "memberships", description = "Sharing locks with other users"),
+        (name = "events", description = "Live lock and quest changes"),
+        (name = "tokens", description = "Personal access tokens"),
+        (name = "webhooks", description = "Signed event deliveries to the user's own endpoints"),
+        (name = "account", description = "Data export and account erasure"),
+        (name = "admin", description = "Support operations, written to the audit log"),
+        (name = "health", description = "Service health"),
+    )
+)]
+pub struct ApiDoc;
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            BEARER_AUTH,
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some(
+                        "An access token of the identity provider, or a personal access token. \
+                         Personal access tokens only reach routes that list one of their scopes.",
+                    ))
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// The package declares no license, drop the empty one utoipa fills in from the manifest.
+struct WithoutLicense;
+
+impl Modify for WithoutLicense {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi.info.license = None;
+    }
+}
+
+/// Documents the `Idempotency-Key` header on every command, see `api::idempotency`. Applied to
+/// the routed document, the paths don't exist yet when `ApiDoc` is built.
+pub struct IdempotencyKeys;
+
+impl IdempotencyKeys {
+    fn document(operation: &mut Operation) {
+        let header = ParameterBuilder::new()
+            .name("Idempotency-Key")
+            .parameter_in(ParameterIn::Header)
+            .required(Required::False)
+            .description(Some(
+                "Unique key for the request, 1 to 255 visible ASCII characters. A retry with \
+                 the same key gets the first response again instead of repeating the command.",
+            ))
+            .schema(Some(Object::with_type(Type::String)))
+            .build();
+        operation
+            .parameters
+            .get_or_insert_with(Vec::new)
+            .push(header);
+
+        let responses = &mut operation.responses.responses;
+        for (status, description) in [
+            ("400", "Invalid idempotency key"),
+            (
+                "409",
+                "A request with the same idempotency key is still being handled",
+            ),
+            (
+                "422",
+                "The idempotency key was already used for a different request",
+            ),
+        ] {
+            match responses.get_mut(status) {
+                Some(RefOr::T(response)) => {
+                    response.description = format!("{}, or: {description}", response.description);
+                }
+                Some(RefOr::Ref(_)) => {}
+                None => {
+                    let response = ResponseBuilder::new()
+                        .description(description)
+                        .content(
+                            "application/json",
+                            ContentBuilder::new()
+                                .schema(Some(Ref::from_schema_name("ErrorResponse")))
+                                .build(),
+                        )
+                        .build();
+                    responses.insert(status.to_string(), RefOr::T(response));
+                }
+            }
+        }
+    }
+}
+
+impl Modify for IdempotencyKeys {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        for item in openapi.paths.paths.values_mut() {
+            for operation in [
+                &mut item.post,
+                &mut item.put,
+                &mut item.patch,
+                &mut item.delete,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                Self::document(operation);
+            }
+        }
+    }
+}
+
+/// The OpenAPI document for every route under `/api/v1`.
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    api_router().into_openapi()
+}
@@ -1,28 +1,47 @@
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 use crate::{
-    api::routes::{lock_commands::lock_commands_router, lock_queries::lock_queries_router},
+    api::graphql::graphql_router,
+    api::idempotency::{
+        IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER, idempotency_middleware,
+    },
+    api::logging::{LogRedaction, log_request_response},
+    api::metrics::track_metrics,
+    api::openapi::{ApiDoc, DOCS_PATH, IdempotencyKeys, OPENAPI_PATH},
+    api::routes::{
+        account::account_router,
+        events::{LAST_EVENT_ID_HEADER, events_router},
+        lock_commands::lock_commands_router,
+        lock_queries::{READ_CONSISTENCY_HEADER, lock_queries_router},
+        memberships::memberships_router,
+        quest_commands::quest_commands_router,
+        quest_queries::quest_queries_router,
+        tokens::tokens_router,
+        webhooks::webhooks_router,
+    },
     setup::app_state::AppState,
 };
 
 use super::{exception_handler::handle_error, routes::admin::admin_router};
-use http::header::{AUTHORIZATION, CONTENT_TYPE, ACCEPT, ORIGIN};
-use http_body_util::BodyExt;
+use http::header::{
+    ACCEPT, AUTHORIZATION, CONTENT_TYPE, ETAG, HeaderName, IF_MATCH, IF_NONE_MATCH, ORIGIN,
+    WWW_AUTHENTICATE,
+};
 
 use axum::{
     Router,
-    body::{Body, Bytes},
     error_handling::HandleErrorLayer,
-    extract::Request,
-    http::{HeaderValue, Method, Response, StatusCode},
-    middleware::{self, Next},
-    response::IntoResponse,
+    extract::DefaultBodyLimit,
+    http::{HeaderValue, Method},
+    middleware,
 };
 
 use tower::ServiceBuilder;
 use tower_http::cors::CorsLayer;
 use tower_http::services::ServeDir;
-use tracing::{debug, info};
+use utoipa::{Modify, OpenApi};
+use utoipa_axum::router::OpenApiRouter;
+use utoipa_swagger_ui::SwaggerUi;
 
 pub fn create_router(state: AppState) -> Router {
     let origins: Vec<HeaderValue> = state.config.cors_origins
@@ -40,79 +59,66 @@ pub fn create_router(state: AppState) -> Router {
     let cors = CorsLayer::new()
         .allow_origin(origins)
         .allow_methods([Method::GET, Method::POST, Method::PATCH, Method::DELETE, Method::OPTIONS])
-        .allow_headers([AUTHORIZATION, CONTENT_TYPE, ACCEPT, ORIGIN])
+        .allow_headers([
+            AUTHORIZATION,
+            CONTENT_TYPE,
+            ACCEPT,
+            ORIGIN,
+            IF_MATCH,
+            IF_NONE_MATCH,
+            HeaderName::from_static(READ_CONSISTENCY_HEADER),
+            HeaderName::from_static(IDEMPOTENCY_KEY_HEADER),
+            HeaderName::from_static(LAST_EVENT_ID_HEADER),
+        ])
+        .expose_headers([
+            WWW_AUTHENTICATE,
+            ETAG,
+            HeaderName::from_static(IDEMPOTENT_REPLAYED_HEADER),
+        ])
         .allow_credentials(true);
 
+    let redaction = LogRedaction::from_config(&state.config);
     let middleware_stack = ServiceBuilder::new()
+        .layer(middleware::from_fn_with_state(state.clone(), track_metrics))
         .layer(HandleErrorLayer::new(handle_error))
         .timeout(Duration::from_secs(1800))
         .layer(cors)
-        .layer(middleware::from_fn(log_request_response));
-
-    let app_routes = Router::new()
-        .merge(admin_router())
-        .merge(lock_queries_router())
-        .merge(lock_commands_router());
+        .layer(DefaultBodyLimit::max(redaction.max_request_body_bytes()))
+        .layer(middleware::from_fn_with_state(
+            redaction,
+            log_request_response,
+        ));
+
+    let (api_routes, openapi) = api_router().split_for_parts();
+    let api_routes = api_routes.layer(middleware::from_fn_with_state(
+        state.clone(),
+        idempotency_middleware,
+    ));
 
     Router::new()
-        .nest("/api/v1", app_routes)
+        .merge(api_routes)
+        .merge(graphql_router(&state.config))
+        .merge(SwaggerUi::new(DOCS_PATH).url(OPENAPI_PATH, openapi))
         .fallback_service(ServeDir::new("assets"))
         .layer(middleware_stack)
         .with_state(state)
 }
 
-async fn log_request_response(
-    req: Request,
-    next: Next,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let method = req.method().clone();
-    let uri = req.uri().clone();
-
-    info!("--> {} {}", method, uri);
-
-    let (req_parts, req_body) = req.into_parts();
-    let req_bytes = buffer_and_print("request", req_body).await?;
-    let req = Request::from_parts(req_parts, Body::from(req_bytes));
-
-    let start = Instant::now();
-    let res = next.run(req).await;
-    let latency = start.elapsed();
-
-    let status = res.status();
-
-    let (res_parts, res_body) = res.into_parts();
-    let res_bytes = buffer_and_print("response", res_body).await?;
-    let res = Response::from_parts(res_parts, Body::from(res_bytes));
-
-    info!(
-        "<-- {} {} {} {}",
-        status.as_u16(),
-        method,
-        uri,
-        latency.as_micros()
-    );
-
-    Ok(res)
-}
-
-async fn buffer_and_print<B>(direction: &str, body: B) -> Result<Bytes, (StatusCode, String)>
-where
-    B: axum::body::HttpBody<Data = Bytes>,
-    B::Error: std::fmt::Display,
-{
-    let bytes = match body.collect().await {
-        Ok(collected) => collected.to_bytes(),
-        Err(err) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                format!("failed to read {direction} body: {err}"),
-            ));
-        }
-    };
-
-    if let Ok(body) = std::str::from_utf8(&bytes) {
-        debug!("{direction} body = {body:?}");
-    }
-
-    Ok(bytes)
+/// The routes under `/api/v1`, along with the OpenAPI document their handlers describe.
+pub fn api_router() -> OpenApiRouter<AppState> {
+    let app_routes = OpenApiRouter::new()
+        .merge(account_router())
+        .merge(admin_router())
+        .merge(events_router())
+        .merge(lock_queries_router())
+        .merge(lock_commands_router())
+        .merge(quest_queries_router())
+        .merge(quest_commands_router())
+        .merge(memberships_router())
+        .merge(tokens_router())
+        .merge(webhooks_router());
+
+    let mut router = OpenApiRouter::with_openapi(ApiDoc::openapi()).nest("/api/v1", app_routes);
+    IdempotencyKeys.modify(router.get_openapi_mut());
+    router
 }
@@ -0,0 +1,132 @@
+use axum::{
+    extract::State,
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    api::extractors::{AuthenticatedUser, JsonBody},
+    api::schemas::{
+        requests::EraseAccountRequest,
+        responses::{ApiResponse, ErrorResponse, RestApiResponse},
+    },
+    application::{
+        dtos::account::{AccountErasureDTO, AccountExportDTO},
+        exceptions::AppError,
+    },
+    setup::app_state::AppState,
+};
+
+// Export and erasure hand out or destroy everything the user has, so they are only available
+// to a signed in session, never to a personal access token.
+
+#[utoipa::path(
+    get,
+    path = "/me/export",
+    tag = "account",
+    responses(
+        (status = 200, description = "Everything stored about the caller, as an attachment", body = ApiResponse<AccountExportDTO>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot be used", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn export_account_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    user.require_session()?;
+    let export = state.account_service.export(&user.principal).await?;
+
+    Ok((
+        [(
+            header::CONTENT_DISPOSITION,
+            "attachment; filename=\"quest-lock-export.json\"",
+        )],
+        RestApiResponse::success(export),
+    ))
+}
+
+/// Schedule the erasure of all of the caller's data, once the grace period has passed.
+#[utoipa::path(
+    delete,
+    path = "/me",
+    tag = "account",
+    request_body = EraseAccountRequest,
+    responses(
+        (status = 202, description = "The erasure is scheduled", body = ApiResponse<AccountErasureDTO>),
+        (status = 400, description = "Wrong confirmation, or an erasure is already scheduled", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot be used", body = ErrorResponse),
+        (status = 422, description = "The body doesn't have the expected shape", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn erase_account_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    JsonBody(payload): JsonBody<EraseAccountRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    user.require_session()?;
+    let erasure = state
+        .account_service
+        .request_erasure(&user.principal, payload.confirmation)
+        .await?;
+
+    Ok(RestApiResponse::accepted(erasure))
+}
+
+#[utoipa::path(
+    get,
+    path = "/me/erasure",
+    tag = "account",
+    responses(
+        (status = 200, description = "The scheduled erasure", body = ApiResponse<AccountErasureDTO>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot be used", body = ErrorResponse),
+        (status = 404, description = "No erasure is scheduled", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_erasure_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    user.require_session()?;
+    let erasure = state.account_service.get_erasure(&user.principal).await?;
+
+    Ok(RestApiResponse::success(erasure))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/me/erasure",
+    tag = "account",
+    responses(
+        (status = 204, description = "The erasure was cancelled"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot be used", body = ErrorResponse),
+        (status = 404, description = "No erasure is scheduled", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn cancel_erasure_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    user.require_session()?;
+    state
+        .account_service
+        .cancel_erasure(&user.principal)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn account_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(erase_account_handler))
+        .routes(routes!(export_account_handler))
+        .routes(routes!(get_erasure_handler, cancel_erasure_handler))
+}
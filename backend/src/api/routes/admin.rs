@@ -1,12 +1,149 @@
-use axum::{Json, Router, response::IntoResponse, routing::get};
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
 
-use crate::setup::app_state::AppState;
+use crate::{
+    api::{
+        extractors::{AdminUser, JsonBody},
+        schemas::{
+            requests::{CancelLockDeletionRequest, ForceQuestStatusRequest},
+            responses::{ApiResponse, ErrorResponse, RestApiResponse},
+        },
+    },
+    application::{
+        dtos::admin::{AdminLockDTO, AdminQuestDTO},
+        exceptions::AppError,
+    },
+    setup::app_state::AppState,
+};
 
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses((status = 200, description = "The service is up", body = ApiResponse<Object>, example = json!({"status": 200, "message": "success", "data": {"status": "ok"}, "error": null})))
+)]
 pub async fn health_check_handler() -> impl IntoResponse {
     let response = serde_json::json!({"status": "ok"});
-    Json(response)
+    RestApiResponse::success(response)
 }
 
-pub fn admin_router() -> Router<AppState> {
-    Router::new().route("/health", get(health_check_handler))
+/// The user's locks, including soft deleted ones that have not been purged yet.
+#[utoipa::path(
+    get,
+    path = "/admin/users/{user_id}/locks",
+    tag = "admin",
+    params(("user_id" = String, Path, description = "User id")),
+    responses(
+        (status = 200, description = "The locks, without shares or quest data", body = ApiResponse<Vec<AdminLockDTO>>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "The caller is not an operator", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_user_locks_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    Path(user_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let locks = state
+        .admin_service
+        .get_locks_by_user(&admin.principal, user_id)
+        .await?;
+    Ok(RestApiResponse::success(locks))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/quests/{quest_id}",
+    tag = "admin",
+    params(("quest_id" = String, Path, description = "Quest id")),
+    responses(
+        (status = 200, description = "The quest, without share or data", body = ApiResponse<AdminQuestDTO>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "The caller is not an operator", body = ErrorResponse),
+        (status = 404, description = "No such quest", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_quest_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    Path(quest_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let quest = state
+        .admin_service
+        .get_quest(&admin.principal, quest_id)
+        .await?;
+    Ok(RestApiResponse::success(quest))
+}
+
+/// Set a quest's status, bypassing the usual transition rules.
+#[utoipa::path(
+    patch,
+    path = "/admin/quests/{quest_id}/status",
+    tag = "admin",
+    params(("quest_id" = String, Path, description = "Quest id")),
+    request_body = ForceQuestStatusRequest,
+    responses(
+        (status = 200, description = "The updated quest", body = ApiResponse<AdminQuestDTO>),
+        (status = 400, description = "Missing reason, unknown status or no change", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "The caller is not an operator", body = ErrorResponse),
+        (status = 404, description = "No such quest", body = ErrorResponse),
+        (status = 422, description = "The body doesn't have the expected shape", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn force_quest_status_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    Path(quest_id): Path<String>,
+    JsonBody(payload): JsonBody<ForceQuestStatusRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let quest = state
+        .admin_service
+        .force_quest_status(&admin.principal, quest_id, payload.status, payload.reason)
+        .await?;
+    Ok(RestApiResponse::success(quest))
+}
+
+/// Restore a soft deleted lock, even past the window in which its owner could.
+#[utoipa::path(
+    post,
+    path = "/admin/locks/{lock_id}/restore",
+    tag = "admin",
+    params(("lock_id" = String, Path, description = "Lock id")),
+    request_body = CancelLockDeletionRequest,
+    responses(
+        (status = 200, description = "The restored lock", body = ApiResponse<AdminLockDTO>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "The caller is not an operator", body = ErrorResponse),
+        (status = 404, description = "No such deleted lock", body = ErrorResponse),
+        (status = 422, description = "The body doesn't have the expected shape", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn cancel_lock_deletion_handler(
+    State(state): State<AppState>,
+    admin: AdminUser,
+    Path(lock_id): Path<String>,
+    JsonBody(payload): JsonBody<CancelLockDeletionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let lock = state
+        .admin_service
+        .cancel_lock_deletion(&admin.principal, lock_id, payload.reason)
+        .await?;
+    Ok(RestApiResponse::success(lock))
+}
+
+pub fn admin_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(health_check_handler))
+        .routes(routes!(get_user_locks_handler))
+        .routes(routes!(get_quest_handler))
+        .routes(routes!(force_quest_status_handler))
+        .routes(routes!(cancel_lock_deletion_handler))
 }
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::{
+        IntoResponse,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use futures_util::StreamExt;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+use crate::{
+    api::extractors::{AuthenticatedUser, ReadLocks},
+    api::schemas::responses::ErrorResponse,
+    application::{exceptions::AppError, services::event_stream_service::StreamedEvent},
+    setup::app_state::AppState,
+};
+
+/// Sent by `EventSource` when it reconnects, the id of the last event it received.
+pub const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+/// Name of the event telling the client that it may have missed events.
+const RESET_EVENT: &str = "stream.reset";
+
+fn to_sse(item: StreamedEvent) -> Result<Event, axum::Error> {
+    match item {
+        StreamedEvent::Event(event) => Event::default()
+            .id(event.id.to_string())
+            .event(event.event_type())
+            .json_data(&event),
+        StreamedEvent::Reset => Ok(Event::default()
+            .event(RESET_EVENT)
+            .data(format!(r#"{{"type":"{RESET_EVENT}"}}"#))),
+    }
+}
+
+/// Live changes to the caller's locks and quests, including the locks shared with them.
+///
+/// Each event is named after its type, e.g. `quest.status_changed`, with the event as JSON data.
+/// A `stream.reset` event means events were missed and the client should reload its locks.
+#[utoipa::path(
+    get,
+    path = "/events",
+    tag = "events",
+    params(
+        ("last-event-id" = Option<String>, Header, description = "Resume after this event, sent by `EventSource` on reconnect"),
+    ),
+    responses(
+        (status = 200, description = "A stream of server-sent events, with a comment as heartbeat", content_type = "text/event-stream", body = String),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Token lacks the locks:read scope", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:read"]))
+)]
+pub async fn events_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ReadLocks>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    // An id that isn't ours is treated like one that left the buffer.
+    let last_event_id = headers
+        .get(LAST_EVENT_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| !v.is_empty())
+        .map(|v| Uuid::try_parse(v).unwrap_or_default());
+
+    let events = state
+        .event_stream_service
+        .subscribe(&user.principal, last_event_id)
+        .await?;
+
+    Ok(
+        Sse::new(events.map(to_sse)).keep_alive(KeepAlive::new().interval(Duration::from_secs(
+            state.config.event_stream_heartbeat_secs,
+        ))),
+    )
+}
+
+pub fn events_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(events_handler))
+}
@@ -1,9 +1,23 @@
-use axum::{Json, Router, extract::State, response::IntoResponse, routing::post};
-use axum_auth::AuthBearer;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode, header::ETAG},
+    response::{IntoResponse, Response},
+};
 use base64::prelude::*;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use validator::Validate;
 
 use crate::{
-    api::schemas::requests::CreateLockRequest, application::exceptions::AppError,
+    api::conditional::{lock_etag, lock_precondition},
+    api::extractors::{AuthenticatedUser, ValidatedJson, WriteLocks, violations},
+    api::schemas::{
+        requests::{CreateLockRequest, CreateLocksRequest, CreateQuestRequest},
+        responses::{ApiResponse, BatchItem, ErrorResponse, RestApiResponse},
+    },
+    application::{
+        dtos::lock::{BatchMode, LockDTO, NewLock, NewQuest},
+        exceptions::{AppError, ErrorCode},
+    },
     setup::app_state::AppState,
 };
 
@@ -17,26 +31,43 @@ pub fn deserialize_quest_share(share: String) -> Result<String, AppError> {
     }
 }
 
-pub async fn create_lock_handler(
-    State(state): State<AppState>,
-    AuthBearer(token): AuthBearer,
-    Json(payload): Json<CreateLockRequest>,
-) -> Result<impl IntoResponse, AppError> {
-    let user_id = state.auth_service.verify(&token).await?;
-    let quests: Result<Vec<_>, AppError> = payload
-        .quests
+/// The share, quest type and data of each quest, with the shares decoded.
+fn decode_quests(quests: Vec<CreateQuestRequest>) -> Result<Vec<NewQuest>, AppError> {
+    quests
         .into_iter()
         .map(|quest| {
             let share = deserialize_quest_share(quest.share)?;
             Ok((share, quest.quest_type, quest.data))
         })
-        .collect();
-    let quests = quests?;
+        .collect()
+}
+
+#[utoipa::path(
+    post,
+    path = "/lock/",
+    tag = "locks",
+    request_body = CreateLockRequest,
+    responses(
+        (status = 200, description = "The created lock", body = ApiResponse<LockDTO>,
+            headers(("ETag" = String, description = "The lock's version, for If-Match"))),
+        (status = 400, description = "Invalid lock or share", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Token lacks the locks:write scope", body = ErrorResponse),
+        (status = 422, description = "The body doesn't have the expected shape or failed validation, see error.details", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:write"]))
+)]
+pub async fn create_lock_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<WriteLocks>,
+    ValidatedJson(payload): ValidatedJson<CreateLockRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let quests = decode_quests(payload.quests)?;
 
     let lock = state
         .lock_service
         .create_lock_with_quests(
-            user_id.clone(),
+            &user.principal,
             payload.label,
             payload.total_shares,
             payload.threshold,
@@ -44,9 +75,164 @@ pub async fn create_lock_handler(
         )
         .await?;
 
-    Ok(Json(lock))
+    Ok(([(ETAG, lock_etag(&lock))], RestApiResponse::success(lock)))
+}
+
+/// Create several locks in one call. In `atomic` mode every lock is created or none is, and one
+/// invalid lock fails the request. In `partial` mode each lock is created on its own and gets
+/// its own result.
+#[utoipa::path(
+    post,
+    path = "/lock/batch",
+    tag = "locks",
+    request_body = CreateLocksRequest,
+    responses(
+        (status = 200, description = "A result per lock, in the order sent", body = ApiResponse<Vec<BatchItem<LockDTO>>>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Token lacks the locks:write scope", body = ErrorResponse),
+        (status = 422, description = "The body doesn't have the expected shape, or in atomic mode a lock failed validation, see error.details", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:write"]))
+)]
+pub async fn create_locks_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<WriteLocks>,
+    ValidatedJson(payload): ValidatedJson<CreateLocksRequest>,
+) -> Result<Response, AppError> {
+    let mode = payload.mode;
+    // The items that failed before reaching the service, None where a lock is to be created.
+    let mut items = Vec::with_capacity(payload.locks.len());
+    let mut new_locks = Vec::new();
+    let mut invalid = Vec::new();
+    for (index, lock) in payload.locks.into_iter().enumerate() {
+        if let Err(errors) = lock.validate() {
+            let details = violations(&errors, &format!("/locks/{index}"));
+            invalid.extend(details.iter().cloned());
+            items.push(Some(BatchItem::failure(
+                None,
+                StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+                ErrorCode::ValidationFailed,
+                "The lock failed validation",
+                details,
+            )));
+            continue;
+        }
+        match decode_quests(lock.quests) {
+            Ok(quests) => {
+                new_locks.push(NewLock {
+                    label: lock.label,
+                    total_shares: lock.total_shares,
+                    threshold: lock.threshold,
+                    quests,
+                });
+                items.push(None);
+            }
+            Err(err) if mode == BatchMode::Atomic => return Err(err),
+            Err(err) => items.push(Some(BatchItem::error(None, err))),
+        }
+    }
+
+    if mode == BatchMode::Atomic && !invalid.is_empty() {
+        let status = StatusCode::UNPROCESSABLE_ENTITY;
+        let body = ApiResponse::<()>::failure(
+            status.as_u16(),
+            ErrorCode::ValidationFailed,
+            "The request failed validation",
+        )
+        .with_details(invalid);
+        return Ok(RestApiResponse(body).into_response());
+    }
+
+    let mut created = state
+        .lock_service
+        .create_locks(&user.principal, new_locks, mode)
+        .await?
+        .into_iter();
+    let items: Vec<BatchItem<LockDTO>> = items
+        .into_iter()
+        .filter_map(|item| {
+            item.or_else(|| {
+                created.next().map(|result| match result {
+                    Ok(lock) => BatchItem::success(lock.id.clone(), lock),
+                    Err(err) => BatchItem::error(None, err),
+                })
+            })
+        })
+        .collect();
+
+    Ok(RestApiResponse::success(items).into_response())
+}
+
+/// Soft delete a lock, it can be restored until the retention window has passed.
+#[utoipa::path(
+    delete,
+    path = "/lock/{lock_id}",
+    tag = "locks",
+    params(
+        ("lock_id" = String, Path, description = "Lock id"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the lock, the command only applies while the lock is still at that version"),
+    ),
+    responses(
+        (status = 204, description = "The lock was deleted"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not allowed to delete the lock", body = ErrorResponse),
+        (status = 404, description = "No such lock", body = ErrorResponse),
+        (status = 409, description = "The lock was changed by a concurrent request", body = ErrorResponse),
+        (status = 412, description = "The lock changed since the ETag in If-Match", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:write"]))
+)]
+pub async fn delete_lock_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<WriteLocks>,
+    headers: HeaderMap,
+    Path(lock_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    state
+        .lock_service
+        .delete_lock(&user.principal, lock_id, lock_precondition(&headers))
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/lock/{lock_id}/restore",
+    tag = "locks",
+    params(
+        ("lock_id" = String, Path, description = "Lock id"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the lock, the command only applies while the lock is still at that version"),
+    ),
+    responses(
+        (status = 200, description = "The restored lock", body = ApiResponse<LockDTO>,
+            headers(("ETag" = String, description = "The lock's version, for If-Match"))),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not allowed to restore the lock", body = ErrorResponse),
+        (status = 404, description = "No such deleted lock, or it is past its retention window", body = ErrorResponse),
+        (status = 409, description = "The lock was changed by a concurrent request", body = ErrorResponse),
+        (status = 412, description = "The lock changed since the ETag in If-Match", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:write"]))
+)]
+pub async fn restore_lock_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<WriteLocks>,
+    headers: HeaderMap,
+    Path(lock_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let lock = state
+        .lock_service
+        .restore_lock(&user.principal, lock_id, lock_precondition(&headers))
+        .await?;
+
+    Ok(([(ETAG, lock_etag(&lock))], RestApiResponse::success(lock)))
 }
 
-pub fn lock_commands_router() -> Router<AppState> {
-    Router::new().route("/lock/", post(create_lock_handler))
+pub fn lock_commands_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(create_lock_handler))
+        .routes(routes!(create_locks_handler))
+        .routes(routes!(delete_lock_handler))
+        .routes(routes!(restore_lock_handler))
 }
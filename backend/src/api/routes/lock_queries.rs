@@ -1,37 +1,194 @@
 use axum::{
-    Json, Router,
     extract::{Path, State},
+    http::HeaderMap,
     response::IntoResponse,
-    routing::get,
 };
-use axum_auth::AuthBearer;
+use utoipa_axum::{router::OpenApiRouter, routes};
 
-use crate::{application::exceptions::AppError, setup::app_state::AppState};
+use crate::{
+    api::conditional::{conditional_response, lock_etag, locks_etag},
+    api::extractors::{AuthenticatedUser, ReadLocks, ValidatedJson},
+    api::schemas::{
+        requests::LockBatchQueryRequest,
+        responses::{ApiResponse, BatchItem, ErrorResponse, RestApiResponse},
+    },
+    application::{dtos::lock::LockDTO, exceptions::AppError},
+    domain::lock::repository::ReadPreference,
+    setup::app_state::AppState,
+};
+
+/// Clients that need to see their own writes send `X-Read-Consistency: strong` to read from
+/// the primary, everything else may be served by the read replica.
+pub const READ_CONSISTENCY_HEADER: &str = "x-read-consistency";
 
+pub fn read_preference(headers: &HeaderMap) -> ReadPreference {
+    match headers
+        .get(READ_CONSISTENCY_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        Some(value) if value.eq_ignore_ascii_case("strong") => ReadPreference::Primary,
+        _ => ReadPreference::Replica,
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/lock-query/{lock_id}",
+    tag = "locks",
+    params(
+        ("lock_id" = String, Path, description = "Lock id"),
+        ("x-read-consistency" = Option<String>, Header, description = "`strong` to read from the primary"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a previous response, answered with 304 while it still holds"),
+    ),
+    responses(
+        (status = 200, description = "The lock, redacted for viewers", body = ApiResponse<LockDTO>,
+            headers(("ETag" = String, description = "The lock's version, for If-None-Match and If-Match"))),
+        (status = 304, description = "The lock didn't change since the ETag in If-None-Match",
+            headers(("ETag" = String, description = "The lock's version"))),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Token lacks the locks:read scope", body = ErrorResponse),
+        (status = 404, description = "No such lock", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:read"]))
+)]
 pub async fn get_lock_by_id_handler(
     State(state): State<AppState>,
-    AuthBearer(token): AuthBearer,
+    user: AuthenticatedUser<ReadLocks>,
+    headers: HeaderMap,
     Path(lock_id): Path<String>,
 ) -> Result<impl IntoResponse, AppError> {
-    let user_id = state.auth_service.verify(&token).await?;
     let lock = state
         .lock_query_service
-        .get_lock_by_id(user_id, lock_id)
+        .get_lock_by_id(&user.principal, lock_id, read_preference(&headers))
         .await?;
-    Ok(Json(lock))
+    let etag = lock_etag(&lock);
+    Ok(conditional_response(
+        &headers,
+        etag,
+        RestApiResponse::success(lock),
+    ))
+}
+
+/// Several locks by id, each answered the way `GET /lock-query/{lock_id}` would answer it.
+#[utoipa::path(
+    post,
+    path = "/lock-query/batch",
+    tag = "locks",
+    params(
+        ("x-read-consistency" = Option<String>, Header, description = "`strong` to read from the primary"),
+    ),
+    request_body = LockBatchQueryRequest,
+    responses(
+        (status = 200, description = "A result per id, in the order asked for, locks redacted for viewers", body = ApiResponse<Vec<BatchItem<LockDTO>>>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Token lacks the locks:read scope", body = ErrorResponse),
+        (status = 422, description = "The body doesn't have the expected shape or failed validation, see error.details", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:read"]))
+)]
+pub async fn get_locks_batch_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ReadLocks>,
+    headers: HeaderMap,
+    ValidatedJson(payload): ValidatedJson<LockBatchQueryRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let results = state
+        .lock_query_service
+        .get_locks_by_ids(
+            &user.principal,
+            payload.ids.clone(),
+            read_preference(&headers),
+        )
+        .await;
+    let items: Vec<BatchItem<LockDTO>> = payload
+        .ids
+        .into_iter()
+        .zip(results)
+        .map(|(id, result)| match result {
+            Ok(lock) => BatchItem::success(id, lock),
+            Err(err) => BatchItem::error(Some(id), err),
+        })
+        .collect();
+
+    Ok(RestApiResponse::success(items))
 }
 
+/// The caller's own locks and the locks shared with them.
+#[utoipa::path(
+    get,
+    path = "/lock-query/",
+    tag = "locks",
+    params(
+        ("x-read-consistency" = Option<String>, Header, description = "`strong` to read from the primary"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a previous response, answered with 304 while it still holds"),
+    ),
+    responses(
+        (status = 200, description = "The locks, oldest first", body = ApiResponse<Vec<LockDTO>>,
+            headers(("ETag" = String, description = "Changes whenever the list does, for If-None-Match"))),
+        (status = 304, description = "The list didn't change since the ETag in If-None-Match",
+            headers(("ETag" = String, description = "The list's ETag"))),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Token lacks the locks:read scope", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:read"]))
+)]
 pub async fn get_locks_handler(
     State(state): State<AppState>,
-    AuthBearer(token): AuthBearer,
+    user: AuthenticatedUser<ReadLocks>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, AppError> {
-    let user_id = state.auth_service.verify(&token).await?;
-    let locks = state.lock_query_service.get_locks(user_id).await?;
-    Ok(Json(locks))
+    let locks = state
+        .lock_query_service
+        .get_locks(&user.principal, read_preference(&headers))
+        .await?;
+    let etag = locks_etag(&locks);
+    Ok(conditional_response(
+        &headers,
+        etag,
+        RestApiResponse::success(locks),
+    ))
+}
+
+/// Only the locks shared with the caller.
+#[utoipa::path(
+    get,
+    path = "/lock-query/shared",
+    tag = "locks",
+    params(
+        ("x-read-consistency" = Option<String>, Header, description = "`strong` to read from the primary"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a previous response, answered with 304 while it still holds"),
+    ),
+    responses(
+        (status = 200, description = "The locks, redacted, oldest first", body = ApiResponse<Vec<LockDTO>>,
+            headers(("ETag" = String, description = "Changes whenever the list does, for If-None-Match"))),
+        (status = 304, description = "The list didn't change since the ETag in If-None-Match",
+            headers(("ETag" = String, description = "The list's ETag"))),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Token lacks the locks:read scope", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:read"]))
+)]
+pub async fn get_shared_locks_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ReadLocks>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let locks = state
+        .lock_query_service
+        .get_shared_locks(&user.principal, read_preference(&headers))
+        .await?;
+    let etag = locks_etag(&locks);
+    Ok(conditional_response(
+        &headers,
+        etag,
+        RestApiResponse::success(locks),
+    ))
 }
 
-pub fn lock_queries_router() -> Router<AppState> {
-    Router::new()
-        .route("/lock-query/{lock_id}", get(get_lock_by_id_handler))
-        .route("/lock-query/", get(get_locks_handler))
+pub fn lock_queries_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(get_shared_locks_handler))
+        .routes(routes!(get_locks_batch_handler))
+        .routes(routes!(get_lock_by_id_handler))
+        .routes(routes!(get_locks_handler))
 }
@@ -0,0 +1,154 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    api::{
+        extractors::{AuthenticatedUser, JsonBody, ReadLocks, WriteLocks},
+        schemas::{
+            requests::InviteMemberRequest,
+            responses::{ApiResponse, ErrorResponse, RestApiResponse},
+        },
+    },
+    application::{dtos::membership::LockMembershipDTO, exceptions::AppError},
+    setup::app_state::AppState,
+};
+
+/// Invite a user to follow the lock. The invitation grants nothing until it is accepted.
+#[utoipa::path(
+    post,
+    path = "/lock/{lock_id}/members",
+    tag = "memberships",
+    params(("lock_id" = String, Path, description = "Lock id")),
+    request_body = InviteMemberRequest,
+    responses(
+        (status = 201, description = "The pending membership", body = ApiResponse<LockMembershipDTO>),
+        (status = 400, description = "Invalid role, or the user is already a member", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not allowed to share the lock", body = ErrorResponse),
+        (status = 404, description = "No such lock", body = ErrorResponse),
+        (status = 422, description = "The body doesn't have the expected shape", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:write"]))
+)]
+pub async fn invite_member_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<WriteLocks>,
+    Path(lock_id): Path<String>,
+    JsonBody(payload): JsonBody<InviteMemberRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let membership = state
+        .membership_service
+        .invite_member(&user.principal, lock_id, payload.user_id, payload.role)
+        .await?;
+    Ok(RestApiResponse::created(membership))
+}
+
+#[utoipa::path(
+    get,
+    path = "/lock/{lock_id}/members",
+    tag = "memberships",
+    params(("lock_id" = String, Path, description = "Lock id")),
+    responses(
+        (status = 200, description = "The lock's memberships, pending or accepted", body = ApiResponse<Vec<LockMembershipDTO>>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not allowed to share the lock", body = ErrorResponse),
+        (status = 404, description = "No such lock", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:read"]))
+)]
+pub async fn get_members_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ReadLocks>,
+    Path(lock_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let memberships = state
+        .membership_service
+        .get_members(&user.principal, lock_id)
+        .await?;
+    Ok(RestApiResponse::success(memberships))
+}
+
+#[utoipa::path(
+    get,
+    path = "/me/invitations",
+    tag = "memberships",
+    responses(
+        (status = 200, description = "Invitations the caller has not accepted yet", body = ApiResponse<Vec<LockMembershipDTO>>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Token lacks the locks:read scope", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:read"]))
+)]
+pub async fn get_invitations_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ReadLocks>,
+) -> Result<impl IntoResponse, AppError> {
+    let invitations = state
+        .membership_service
+        .get_invitations(&user.principal)
+        .await?;
+    Ok(RestApiResponse::success(invitations))
+}
+
+#[utoipa::path(
+    post,
+    path = "/me/invitations/{membership_id}/accept",
+    tag = "memberships",
+    params(("membership_id" = String, Path, description = "Membership id")),
+    responses(
+        (status = 200, description = "The accepted membership", body = ApiResponse<LockMembershipDTO>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Token lacks the locks:write scope", body = ErrorResponse),
+        (status = 404, description = "No such pending invitation for the caller", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:write"]))
+)]
+pub async fn accept_invitation_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<WriteLocks>,
+    Path(membership_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let membership = state
+        .membership_service
+        .accept_invitation(&user.principal, membership_id)
+        .await?;
+    Ok(RestApiResponse::success(membership))
+}
+
+/// Leave a lock, or remove a member from one the caller owns.
+#[utoipa::path(
+    delete,
+    path = "/memberships/{membership_id}",
+    tag = "memberships",
+    params(("membership_id" = String, Path, description = "Membership id")),
+    responses(
+        (status = 204, description = "The membership was removed"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not allowed to remove the membership", body = ErrorResponse),
+        (status = 404, description = "No such membership", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:write"]))
+)]
+pub async fn remove_membership_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<WriteLocks>,
+    Path(membership_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    state
+        .membership_service
+        .remove_membership(&user.principal, membership_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn memberships_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(get_members_handler, invite_member_handler))
+        .routes(routes!(get_invitations_handler))
+        .routes(routes!(accept_invitation_handler))
+        .routes(routes!(remove_membership_handler))
+}
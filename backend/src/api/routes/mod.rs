@@ -1,3 +1,10 @@
+pub mod account;
 pub mod admin;
+pub mod events;
 pub mod lock_commands;
 pub mod lock_queries;
+pub mod memberships;
+pub mod quest_commands;
+pub mod quest_queries;
+pub mod tokens;
+pub mod webhooks;
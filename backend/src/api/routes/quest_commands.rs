@@ -0,0 +1,65 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, header::ETAG},
+    response::IntoResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    api::conditional::{lock_etag, lock_precondition},
+    api::extractors::{AttemptQuests, AuthenticatedUser, JsonBody},
+    api::schemas::{
+        requests::UpdateQuestStatusRequest,
+        responses::{ApiResponse, ErrorResponse, RestApiResponse},
+    },
+    application::{dtos::quest::QuestDTO, exceptions::AppError},
+    setup::app_state::AppState,
+};
+
+/// Start, abandon or restart a quest.
+///
+/// A quest can't be completed through this route, only by its verification or by an operator.
+#[utoipa::path(
+    patch,
+    path = "/quest/{quest_id}/status",
+    tag = "quests",
+    params(
+        ("quest_id" = String, Path, description = "Quest id"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the quest's lock, the update only applies while the lock is still at that version"),
+    ),
+    request_body = UpdateQuestStatusRequest,
+    responses(
+        (status = 200, description = "The updated quest", body = ApiResponse<QuestDTO>,
+            headers(("ETag" = String, description = "The lock's new version, for If-Match"))),
+        (status = 400, description = "Unknown status or a transition that isn't allowed", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not allowed to update the quest", body = ErrorResponse),
+        (status = 404, description = "No such quest", body = ErrorResponse),
+        (status = 409, description = "The lock was changed by a concurrent request", body = ErrorResponse),
+        (status = 412, description = "The lock changed since the ETag in If-Match", body = ErrorResponse),
+        (status = 422, description = "The body doesn't have the expected shape", body = ErrorResponse),
+    ),
+    security(("bearer" = ["quests:attempt"]))
+)]
+pub async fn update_quest_status_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<AttemptQuests>,
+    headers: HeaderMap,
+    Path(quest_id): Path<String>,
+    JsonBody(payload): JsonBody<UpdateQuestStatusRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let (quest, lock) = state
+        .quest_service
+        .update_quest_status(
+            &user.principal,
+            quest_id,
+            payload.status,
+            lock_precondition(&headers),
+        )
+        .await?;
+    Ok(([(ETAG, lock_etag(&lock))], RestApiResponse::success(quest)))
+}
+
+pub fn quest_commands_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(update_quest_status_handler))
+}
@@ -0,0 +1,41 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    api::extractors::{AuthenticatedUser, ReadLocks},
+    api::schemas::responses::{ApiResponse, ErrorResponse, RestApiResponse},
+    application::{dtos::quest::QuestDTO, exceptions::AppError},
+    setup::app_state::AppState,
+};
+
+#[utoipa::path(
+    get,
+    path = "/quest/{quest_id}",
+    tag = "quests",
+    params(("quest_id" = String, Path, description = "Quest id")),
+    responses(
+        (status = 200, description = "The quest, redacted for viewers", body = ApiResponse<QuestDTO>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Token lacks the locks:read scope", body = ErrorResponse),
+        (status = 404, description = "No such quest", body = ErrorResponse),
+    ),
+    security(("bearer" = ["locks:read"]))
+)]
+pub async fn get_quest_by_id_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser<ReadLocks>,
+    Path(quest_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let quest = state
+        .quest_query_service
+        .get_quest_by_id(&user.principal, quest_id)
+        .await?;
+    Ok(RestApiResponse::success(quest))
+}
+
+pub fn quest_queries_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(get_quest_by_id_handler))
+}
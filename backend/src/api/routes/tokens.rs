@@ -0,0 +1,113 @@
+use axum::{
+    extract::{Path, State},
+    http::{StatusCode, header::CACHE_CONTROL},
+    response::IntoResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    api::extractors::{AuthenticatedUser, JsonBody},
+    api::schemas::{
+        requests::CreateTokenRequest,
+        responses::{ApiResponse, ErrorResponse, RestApiResponse},
+    },
+    application::{
+        dtos::token::{CreatedPersonalAccessTokenDTO, PersonalAccessTokenDTO},
+        exceptions::AppError,
+    },
+    setup::app_state::AppState,
+};
+
+// Tokens are managed from a signed in session only, a leaked token must not be able to
+// mint more of them.
+
+#[utoipa::path(
+    post,
+    path = "/me/tokens",
+    tag = "tokens",
+    request_body = CreateTokenRequest,
+    responses(
+        (status = 201, description = "The token, the only response that carries its secret", body = ApiResponse<CreatedPersonalAccessTokenDTO>),
+        (status = 400, description = "Invalid name, scopes or lifetime", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot be used", body = ErrorResponse),
+        (status = 422, description = "The body doesn't have the expected shape", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_token_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    JsonBody(payload): JsonBody<CreateTokenRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    user.require_session()?;
+    let token = state
+        .token_service
+        .create_token(
+            user.user_id(),
+            payload.name,
+            payload.scopes,
+            payload.expires_in_days,
+        )
+        .await?;
+
+    // Keeps the secret out of caches, and out of the stored idempotent responses.
+    Ok((
+        [(CACHE_CONTROL, "no-store")],
+        RestApiResponse::created(token),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/me/tokens",
+    tag = "tokens",
+    responses(
+        (status = 200, description = "The caller's tokens, including revoked and expired ones", body = ApiResponse<Vec<PersonalAccessTokenDTO>>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot be used", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_tokens_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    user.require_session()?;
+    let tokens = state.token_service.list_tokens(user.user_id()).await?;
+
+    Ok(RestApiResponse::success(tokens))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/me/tokens/{token_id}",
+    tag = "tokens",
+    params(("token_id" = String, Path, description = "Token id")),
+    responses(
+        (status = 204, description = "The token was revoked"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot be used", body = ErrorResponse),
+        (status = 404, description = "No such active token", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn revoke_token_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(token_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    user.require_session()?;
+    state
+        .token_service
+        .revoke_token(user.user_id(), token_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn tokens_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(list_tokens_handler, create_token_handler))
+        .routes(routes!(revoke_token_handler))
+}
@@ -0,0 +1,161 @@
+use axum::{
+    extract::{Path, State},
+    http::{StatusCode, header::CACHE_CONTROL},
+    response::IntoResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    api::extractors::{AuthenticatedUser, JsonBody},
+    api::schemas::{
+        requests::CreateWebhookRequest,
+        responses::{ApiResponse, ErrorResponse, RestApiResponse},
+    },
+    application::{
+        dtos::webhook::{CreatedWebhookDTO, WebhookDTO, WebhookDeliveryDTO},
+        exceptions::AppError,
+    },
+    setup::app_state::AppState,
+};
+
+// Like tokens, webhooks are managed from a signed in session only. A webhook receives the
+// events of every lock of its owner.
+
+#[utoipa::path(
+    post,
+    path = "/me/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "The webhook, the only response that carries its signing secret", body = ApiResponse<CreatedWebhookDTO>),
+        (status = 400, description = "Invalid URL or event type", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot be used", body = ErrorResponse),
+        (status = 422, description = "The body doesn't have the expected shape", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_webhook_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    JsonBody(payload): JsonBody<CreateWebhookRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    user.require_session()?;
+    let webhook = state
+        .webhook_service
+        .create_webhook(user.user_id(), payload.url, payload.event_types)
+        .await?;
+
+    // Keeps the secret out of caches, and out of the stored idempotent responses.
+    Ok((
+        [(CACHE_CONTROL, "no-store")],
+        RestApiResponse::created(webhook),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/me/webhooks",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "The caller's webhooks", body = ApiResponse<Vec<WebhookDTO>>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot be used", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_webhooks_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    user.require_session()?;
+    let webhooks = state.webhook_service.list_webhooks(user.user_id()).await?;
+
+    Ok(RestApiResponse::success(webhooks))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/me/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(("webhook_id" = String, Path, description = "Webhook id")),
+    responses(
+        (status = 204, description = "The webhook and its pending deliveries were deleted"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot be used", body = ErrorResponse),
+        (status = 404, description = "No such webhook", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_webhook_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(webhook_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    user.require_session()?;
+    state
+        .webhook_service
+        .delete_webhook(user.user_id(), webhook_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/me/webhooks/dead-letters",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Deliveries that failed every attempt, newest first", body = ApiResponse<Vec<WebhookDeliveryDTO>>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot be used", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_dead_letters_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<impl IntoResponse, AppError> {
+    user.require_session()?;
+    let deliveries = state
+        .webhook_service
+        .list_dead_letters(user.user_id())
+        .await?;
+
+    Ok(RestApiResponse::success(deliveries))
+}
+
+#[utoipa::path(
+    post,
+    path = "/me/webhooks/dead-letters/{delivery_id}/replay",
+    tag = "webhooks",
+    params(("delivery_id" = String, Path, description = "Delivery id")),
+    responses(
+        (status = 202, description = "The delivery was queued again", body = ApiResponse<WebhookDeliveryDTO>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Personal access tokens cannot be used", body = ErrorResponse),
+        (status = 404, description = "No such dead letter", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn replay_dead_letter_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(delivery_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    user.require_session()?;
+    let delivery = state
+        .webhook_service
+        .replay_dead_letter(user.user_id(), delivery_id)
+        .await?;
+
+    Ok(RestApiResponse::accepted(delivery))
+}
+
+pub fn webhooks_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(list_webhooks_handler, create_webhook_handler))
+        .routes(routes!(delete_webhook_handler))
+        .routes(routes!(list_dead_letters_handler))
+        .routes(routes!(replay_dead_letter_handler))
+}
@@ -1,18 +1,251 @@
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::HashMap, str::FromStr};
 
+use base64::prelude::*;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError, ValidationErrors, ValidationErrorsKind};
 
-#[derive(PartialEq, Debug, Deserialize, Serialize)]
+use crate::application::dtos::lock::BatchMode;
+use crate::domain::{
+    membership::entity::MembershipRole,
+    quest::enums::{QuestStatus, QuestType},
+};
+
+const MAX_DATA_VALUE_LENGTH: usize = 500;
+
+#[derive(PartialEq, Debug, Deserialize, Serialize, ToSchema, Validate)]
+// Each struct level check reports its own violation, clippy takes the repeated option for a mistake.
+#[allow(clippy::duplicated_attributes)]
+#[validate(schema(function = "validate_threshold", skip_on_field_errors = false))]
+#[validate(schema(function = "validate_quest_count", skip_on_field_errors = false))]
 pub struct CreateLockRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Label must be between 1 and 100 characters"
+    ))]
+    #[schema(min_length = 1, max_length = 100)]
     pub label: Option<String>,
+    #[validate(range(min = 1, message = "A lock needs at least one share"))]
+    #[schema(minimum = 1)]
     pub total_shares: u8,
+    /// How many shares unlock the lock, at most `total_shares`.
+    #[validate(range(min = 1, message = "Threshold must be at least 1"))]
+    #[schema(minimum = 1)]
     pub threshold: u8,
+    /// One quest per share held by the server, so at most `total_shares`.
+    #[validate(nested)]
     pub quests: Vec<CreateQuestRequest>,
 }
 
-#[derive(PartialEq, Debug, Deserialize, Serialize)]
+fn validate_threshold(request: &CreateLockRequest) -> Result<(), ValidationError> {
+    if request.threshold > request.total_shares {
+        return Err(field_error(
+            "threshold",
+            "range",
+            "Threshold can't be larger than total_shares",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_quest_count(request: &CreateLockRequest) -> Result<(), ValidationError> {
+    if request.quests.len() > request.total_shares as usize {
+        return Err(field_error(
+            "quests",
+            "length",
+            "A lock can't have more quests than shares",
+        ));
+    }
+    Ok(())
+}
+
+/// Create several locks in one call, see `POST /lock/batch`.
+#[derive(PartialEq, Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct CreateLocksRequest {
+    /// `atomic` by default.
+    #[serde(default)]
+    pub mode: BatchMode,
+    /// Each lock is validated like the body of `POST /lock/`.
+    #[validate(length(min = 1, max = 100, message = "A batch holds 1 to 100 locks"))]
+    #[schema(min_items = 1, max_items = 100)]
+    pub locks: Vec<CreateLockRequest>,
+}
+
+/// Fetch several locks in one call, see `POST /lock-query/batch`.
+#[derive(PartialEq, Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct LockBatchQueryRequest {
+    #[validate(length(min = 1, max = 100, message = "A batch holds 1 to 100 lock ids"))]
+    #[schema(min_items = 1, max_items = 100)]
+    pub ids: Vec<String>,
+}
+
+/// A struct level error reported against one of the struct's fields, see
+/// `api::extractors::ValidatedJson`.
+fn field_error(field: &'static str, code: &'static str, message: &'static str) -> ValidationError {
+    let mut error = ValidationError::new(code).with_message(Cow::Borrowed(message));
+    error.add_param(Cow::Borrowed("field"), &field);
+    error
+}
+
+#[derive(PartialEq, Debug, Deserialize, Serialize, ToSchema)]
 pub struct CreateQuestRequest {
+    #[schema(format = Byte)]
     pub share: String, // base64 encoded
+    #[schema(value_type = QuestType)]
     pub quest_type: String,
+    /// The keys depend on the quest type, e.g. a GEO quest needs `location_name`, `latitude` and
+    /// `longitude`.
     pub data: HashMap<String, String>,
 }
+
+/// Written by hand because the `data` keys depend on `quest_type`, which the derive can't express.
+impl Validate for CreateQuestRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let share = BASE64_STANDARD
+            .decode(&self.share)
+            .ok()
+            .and_then(|share| String::from_utf8(share).ok());
+        if share.is_none_or(|share| share.is_empty()) {
+            errors.add(
+                "share",
+                ValidationError::new("share")
+                    .with_message(Cow::Borrowed("Share must be non-empty base64 encoded text")),
+            );
+        }
+
+        match QuestType::from_str(&self.quest_type) {
+            Ok(quest_type) => {
+                let data_errors = validate_quest_data(&quest_type, &self.data);
+                if !data_errors.is_empty() {
+                    errors.errors_mut().insert(
+                        Cow::Borrowed("data"),
+                        ValidationErrorsKind::Struct(Box::new(data_errors)),
+                    );
+                }
+            }
+            Err(_) => errors.add(
+                "quest_type",
+                ValidationError::new("quest_type").with_message(Cow::Borrowed(
+                    "Quest type must be one of GEO, TIME, FRIEND or PAYWALL",
+                )),
+            ),
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Every problem with a quest's data, keyed by the data key.
+fn validate_quest_data(quest_type: &QuestType, data: &HashMap<String, String>) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    let mut add = |key: &str, code: &'static str, message: String| {
+        errors.errors_mut().insert(
+            Cow::Owned(key.to_string()),
+            ValidationErrorsKind::Field(vec![
+                ValidationError::new(code).with_message(Cow::Owned(message)),
+            ]),
+        );
+    };
+
+    for key in quest_type.required_data_keys() {
+        if data.get(*key).is_none_or(|value| value.trim().is_empty()) {
+            add(key, "required", format!("{quest_type} quests need a {key}"));
+        }
+    }
+
+    for (key, value) in data {
+        let known = quest_type.required_data_keys().contains(&key.as_str())
+            || quest_type.optional_data_keys().contains(&key.as_str());
+        if !known {
+            add(key, "unknown", format!("{quest_type} quests have no {key}"));
+        } else if value.len() > MAX_DATA_VALUE_LENGTH {
+            add(
+                key,
+                "length",
+                format!("{key} can't be longer than {MAX_DATA_VALUE_LENGTH} characters"),
+            );
+        } else if let Some((min, max)) = numeric_range(key)
+            && !value.trim().is_empty()
+            && !value
+                .trim()
+                .parse::<f64>()
+                .is_ok_and(|number| (min..=max).contains(&number))
+        {
+            add(
+                key,
+                "range",
+                format!("{key} must be a number between {min} and {max}"),
+            );
+        }
+    }
+
+    errors
+}
+
+/// The bounds of data values that have to be numbers.
+fn numeric_range(key: &str) -> Option<(f64, f64)> {
+    match key {
+        "latitude" => Some((-90.0, 90.0)),
+        "longitude" => Some((-180.0, 180.0)),
+        "proximity_range" => Some((1.0, 100_000.0)),
+        "amount" => Some((0.01, 1_000_000.0)),
+        _ => None,
+    }
+}
+
+#[derive(PartialEq, Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateQuestStatusRequest {
+    #[schema(value_type = QuestStatus)]
+    pub status: String,
+}
+
+#[derive(PartialEq, Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateTokenRequest {
+    pub name: String,
+    #[schema(example = json!(["locks:read"]))]
+    pub scopes: Vec<String>,
+    pub expires_in_days: u32,
+}
+
+#[derive(PartialEq, Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    #[schema(example = "https://example.com/hooks/quest-lock")]
+    pub url: String,
+    /// Event types to deliver, every event type when empty.
+    #[serde(default)]
+    #[schema(example = json!(["lock.unlockable", "quest.status_changed"]))]
+    pub event_types: Vec<String>,
+}
+
+#[derive(PartialEq, Debug, Deserialize, Serialize, ToSchema)]
+pub struct InviteMemberRequest {
+    pub user_id: String,
+    /// Only VIEWER can be granted.
+    #[schema(value_type = MembershipRole)]
+    pub role: String,
+}
+
+#[derive(PartialEq, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ForceQuestStatusRequest {
+    #[schema(value_type = QuestStatus)]
+    pub status: String,
+    pub reason: String,
+}
+
+#[derive(PartialEq, Debug, Deserialize, Serialize, ToSchema)]
+pub struct CancelLockDeletionRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(PartialEq, Debug, Deserialize, Serialize, ToSchema)]
+pub struct EraseAccountRequest {
+    #[schema(example = "erase my account")]
+    pub confirmation: String,
+}
@@ -1,15 +1,111 @@
-use axum::response::{IntoResponse, Response};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::application::exceptions::{AppError, ErrorCode};
 
 /// A standardized API response format.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct ApiResponse<T>
 where
     T: Serialize,
 {
+    #[schema(example = 200)]
     pub status: u16,
     pub message: String,
     pub data: Option<T>,
+    /// Set when the request failed.
+    pub error: Option<ApiError>,
+}
+
+/// What went wrong with a failed request, in a form clients can branch on.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ApiError {
+    pub code: ErrorCode,
+    /// The fields that were rejected, empty when the error isn't about particular fields.
+    pub details: Vec<ErrorDetail>,
+}
+
+/// A rejected field of the request.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct ErrorDetail {
+    /// JSON pointer to the field in the request body, e.g. `/quests/0/quest_type`.
+    #[schema(example = "/label")]
+    pub field: String,
+    pub message: String,
+}
+
+/// The outcome of one item of a batch request. Items are answered in the order they were sent.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct BatchItem<T>
+where
+    T: Serialize,
+{
+    /// The lock the item is about, absent when a lock could not be created.
+    pub id: Option<String>,
+    /// The status the item would have been answered with as a request of its own.
+    #[schema(example = 200)]
+    pub status: u16,
+    pub message: String,
+    pub data: Option<T>,
+    /// Set when the item failed.
+    pub error: Option<ApiError>,
+}
+
+impl<T> BatchItem<T>
+where
+    T: Serialize,
+{
+    pub fn success(id: impl Into<String>, data: T) -> Self {
+        Self {
+            id: Some(id.into()),
+            status: 200,
+            message: "success".to_string(),
+            data: Some(data),
+            error: None,
+        }
+    }
+
+    pub fn failure(
+        id: Option<String>,
+        status: u16,
+        code: ErrorCode,
+        message: impl Into<String>,
+        details: Vec<ErrorDetail>,
+    ) -> Self {
+        Self {
+            id,
+            status,
+            message: message.into(),
+            data: None,
+            error: Some(ApiError { code, details }),
+        }
+    }
+
+    /// An item that failed the way a request of its own would have.
+    pub fn error(id: Option<String>, error: AppError) -> Self {
+        Self::failure(
+            id,
+            error.status().as_u16(),
+            error.code(),
+            error.to_string(),
+            Vec::new(),
+        )
+    }
+}
+
+/// The body of a failed request, an [`ApiResponse`] without data. Only used to document the API.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ErrorResponse {
+    #[schema(example = 404)]
+    pub status: u16,
+    pub message: String,
+    #[schema(value_type = Option<Object>)]
+    pub data: Option<()>,
+    pub error: ApiError,
 }
 
 /// A standardized API response format for successful and failed responses.
@@ -28,6 +124,7 @@ where
             status: 200,
             message: "success".to_string(),
             data: Some(data),
+            error: None,
         }
     }
 
@@ -37,17 +134,30 @@ where
             status: 200,
             message: message.into(),
             data: Some(data),
+            error: None,
         }
     }
 
     /// Create a failure response with no data.
-    pub fn failure(status: u16, message: impl Into<String>) -> Self {
+    pub fn failure(status: u16, code: ErrorCode, message: impl Into<String>) -> Self {
         Self {
             status,
             message: message.into(),
             data: None,
+            error: Some(ApiError {
+                code,
+                details: Vec::new(),
+            }),
         }
     }
+
+    /// Attach the fields that caused a failure.
+    pub fn with_details(mut self, details: Vec<ErrorDetail>) -> Self {
+        if let Some(error) = self.error.as_mut() {
+            error.details = details;
+        }
+        self
+    }
 }
 
 /// A wrapper struct for the API response.
@@ -71,14 +181,33 @@ impl<T: Serialize> RestApiResponse<T> {
         Self(ApiResponse::success_with_message(message, data))
     }
 
-    /// Return a failed response with a status code and message.
-    pub fn failure(status: u16, message: impl Into<String>) -> Self {
-        Self(ApiResponse::failure(status, message))
+    /// Return a 201 response for a newly created resource.
+    pub fn created(data: T) -> Self {
+        Self(ApiResponse {
+            status: StatusCode::CREATED.as_u16(),
+            ..ApiResponse::success_with_message("created", data)
+        })
+    }
+
+    /// Return a 202 response for work that will complete later.
+    pub fn accepted(data: T) -> Self {
+        Self(ApiResponse {
+            status: StatusCode::ACCEPTED.as_u16(),
+            ..ApiResponse::success_with_message("accepted", data)
+        })
+    }
+
+    /// Return a failed response with a status code, error code and message.
+    pub fn failure(status: u16, code: ErrorCode, message: impl Into<String>) -> Self {
+        Self(ApiResponse::failure(status, code, message))
     }
 }
 
+/// The HTTP status is taken from the envelope, so the two never disagree.
 impl<T: Serialize> IntoResponse for RestApiResponse<T> {
     fn into_response(self) -> Response {
-        axum::Json(self.0).into_response()
+        let status =
+            StatusCode::from_u16(self.0.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (status, axum::Json(self.0)).into_response()
     }
 }
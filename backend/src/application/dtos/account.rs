@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use crate::application::dtos::{
+    membership::LockMembershipDTO, token::PersonalAccessTokenDTO, webhook::WebhookDTO,
+};
+use crate::domain::{
+    account::entity::AccountErasure,
+    event::entity::DomainEvent,
+    lock::entity::Lock,
+    quest::{
+        entity::Quest,
+        enums::{QuestStatus, QuestType},
+    },
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Everything stored about a user, as handed to them by `GET /me/export`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AccountExportDTO {
+    pub user_id: String,
+    pub exported_at: DateTime<Utc>,
+    /// The user's own locks, including soft deleted ones that have not been purged yet.
+    pub locks: Vec<ExportedLockDTO>,
+    /// Memberships of other users' locks, pending or accepted.
+    pub memberships: Vec<LockMembershipDTO>,
+    pub personal_access_tokens: Vec<PersonalAccessTokenDTO>,
+    /// Webhooks without their signing secrets.
+    pub webhooks: Vec<WebhookDTO>,
+    /// Events recorded for the user's locks, oldest first.
+    #[schema(value_type = Vec<Object>)]
+    pub history: Vec<DomainEvent>,
+    pub erasure: Option<AccountErasureDTO>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportedLockDTO {
+    pub id: String,
+    pub label: Option<String>,
+    pub total_shares: u8,
+    pub threshold: u8,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub quests: Vec<ExportedQuestDTO>,
+}
+
+impl From<Lock> for ExportedLockDTO {
+    fn from(lock: Lock) -> Self {
+        Self {
+            id: lock.id.to_string(),
+            label: lock.label,
+            total_shares: lock.total_shares,
+            threshold: lock.threshold,
+            deleted_at: lock.deleted_at,
+            quests: lock
+                .quests
+                .into_iter()
+                .map(ExportedQuestDTO::from)
+                .collect(),
+        }
+    }
+}
+
+/// A quest in an export. The share is only included once the quest has released it, an
+/// export must not become a way around the quest.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportedQuestDTO {
+    pub id: String,
+    pub share: Option<String>,
+    #[schema(value_type = QuestType)]
+    pub quest_type: String,
+    #[schema(value_type = QuestStatus)]
+    pub status: String,
+    pub data: HashMap<String, String>,
+}
+
+impl From<Quest> for ExportedQuestDTO {
+    fn from(quest: Quest) -> Self {
+        let share = match quest.status {
+            QuestStatus::COMPLETED => Some(quest.share),
+            QuestStatus::PENDING | QuestStatus::IN_PROGRESS | QuestStatus::ABANDONED => None,
+        };
+        Self {
+            id: quest.id.to_string(),
+            share,
+            quest_type: quest.quest_type.to_string(),
+            status: quest.status.to_string(),
+            data: quest.data,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AccountErasureDTO {
+    pub requested_at: DateTime<Utc>,
+    /// When the data is erased, unless the request is cancelled before then.
+    pub scheduled_for: DateTime<Utc>,
+}
+
+impl From<AccountErasure> for AccountErasureDTO {
+    fn from(erasure: AccountErasure) -> Self {
+        Self {
+            requested_at: erasure.requested_at,
+            scheduled_for: erasure.scheduled_for,
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::domain::{
+    lock::entity::Lock,
+    quest::{
+        entity::Quest,
+        enums::{QuestStatus, QuestType},
+    },
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A lock as support staff see it, without shares or quest data.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AdminLockDTO {
+    pub id: String,
+    pub user_id: String,
+    pub label: Option<String>,
+    pub total_shares: u8,
+    pub threshold: u8,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub quests: Vec<AdminQuestDTO>,
+}
+
+impl From<Lock> for AdminLockDTO {
+    fn from(lock: Lock) -> Self {
+        Self {
+            id: lock.id.to_string(),
+            user_id: lock.user_id,
+            label: lock.label,
+            total_shares: lock.total_shares,
+            threshold: lock.threshold,
+            deleted_at: lock.deleted_at,
+            quests: lock.quests.into_iter().map(AdminQuestDTO::from).collect(),
+        }
+    }
+}
+
+/// A quest's state, without its share or data.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AdminQuestDTO {
+    pub id: String,
+    pub lock_id: String,
+    #[schema(value_type = QuestType)]
+    pub quest_type: String,
+    #[schema(value_type = QuestStatus)]
+    pub status: String,
+}
+
+impl From<Quest> for AdminQuestDTO {
+    fn from(quest: Quest) -> Self {
+        Self {
+            id: quest.id.to_string(),
+            lock_id: quest.lock_id.to_string(),
+            quest_type: quest.quest_type.to_string(),
+            status: quest.status.to_string(),
+        }
+    }
+}
@@ -1,12 +1,23 @@
-use crate::{application::dtos::quest::QuestDTO, domain::lock::entity::Lock};
+use std::collections::HashMap;
+
+use crate::{
+    application::dtos::quest::QuestDTO,
+    domain::{lock::entity::Lock, membership::entity::MembershipRole},
+};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LockDTO {
     pub id: String,
     pub label: Option<String>,
     pub total_shares: u8,
     pub threshold: u8,
+    /// Moves on with every change to the lock or its quests, sent as the lock's ETag.
+    pub version: i64,
+    /// The caller's role, VIEWER for a lock shared with them.
+    #[schema(value_type = MembershipRole)]
+    pub role: String,
     pub quests: Vec<QuestDTO>,
 }
 
@@ -17,7 +28,47 @@ impl From<Lock> for LockDTO {
             label: lock.label,
             total_shares: lock.total_shares,
             threshold: lock.threshold,
+            version: lock.version,
+            role: MembershipRole::OWNER.to_string(),
             quests: lock.quests.into_iter().map(QuestDTO::from).collect(),
         }
     }
 }
+
+impl LockDTO {
+    /// A lock shared with the caller, which shows progress but no shares or quest data.
+    pub fn redacted(lock: Lock) -> Self {
+        Self {
+            id: lock.id.to_string(),
+            label: lock.label,
+            total_shares: lock.total_shares,
+            threshold: lock.threshold,
+            version: lock.version,
+            role: MembershipRole::VIEWER.to_string(),
+            quests: lock.quests.into_iter().map(QuestDTO::redacted).collect(),
+        }
+    }
+}
+
+/// The decoded share, quest type and data of a quest to create.
+pub type NewQuest = (String, String, HashMap<String, String>);
+
+/// A lock to create, along with its quests.
+#[derive(Debug, Clone)]
+pub struct NewLock {
+    pub label: Option<String>,
+    pub total_shares: u8,
+    pub threshold: u8,
+    pub quests: Vec<NewQuest>,
+}
+
+/// How a batch of locks is created.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchMode {
+    /// Every lock is created or none is, the first failure fails the whole batch.
+    #[default]
+    Atomic,
+    /// Each lock is created on its own, failures are reported per lock.
+    Partial,
+}
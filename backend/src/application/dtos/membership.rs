@@ -0,0 +1,31 @@
+use crate::domain::membership::entity::{LockMembership, MembershipRole};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LockMembershipDTO {
+    pub id: String,
+    pub lock_id: String,
+    pub user_id: String,
+    #[schema(value_type = MembershipRole)]
+    pub role: String,
+    pub invited_by: String,
+    pub created_at: DateTime<Utc>,
+    /// Unset while the invitation is pending.
+    pub accepted_at: Option<DateTime<Utc>>,
+}
+
+impl From<LockMembership> for LockMembershipDTO {
+    fn from(membership: LockMembership) -> Self {
+        Self {
+            id: membership.id.to_string(),
+            lock_id: membership.lock_id.to_string(),
+            user_id: membership.user_id,
+            role: membership.role.to_string(),
+            invited_by: membership.invited_by,
+            created_at: membership.created_at,
+            accepted_at: membership.accepted_at,
+        }
+    }
+}
@@ -1,2 +1,7 @@
+pub mod account;
+pub mod admin;
 pub mod lock;
+pub mod membership;
 pub mod quest;
+pub mod token;
+pub mod webhook;
@@ -1,14 +1,20 @@
 use std::collections::HashMap;
 
-use crate::domain::quest::entity::Quest;
+use crate::domain::quest::{
+    entity::Quest,
+    enums::{QuestStatus, QuestType},
+};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct QuestDTO {
     pub id: String,
     pub lock_id: String,
     pub share: Option<String>,
+    #[schema(value_type = QuestType)]
     pub quest_type: String,
+    #[schema(value_type = QuestStatus)]
     pub status: String,
     pub data: HashMap<String, String>,
 }
@@ -25,3 +31,18 @@ impl From<Quest> for QuestDTO {
         }
     }
 }
+
+impl QuestDTO {
+    /// The quest's progress only, for viewers of a shared lock. GEO coordinates and other
+    /// quest data are as sensitive as the share.
+    pub fn redacted(quest: Quest) -> Self {
+        Self {
+            id: quest.id.to_string(),
+            lock_id: quest.lock_id.to_string(),
+            share: None,
+            quest_type: quest.quest_type.to_string(),
+            status: quest.status.to_string(),
+            data: HashMap::new(),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::domain::auth::entity::PersonalAccessToken;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PersonalAccessTokenDTO {
+    pub id: String,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<PersonalAccessToken> for PersonalAccessTokenDTO {
+    fn from(token: PersonalAccessToken) -> Self {
+        Self {
+            id: token.id.to_string(),
+            name: token.name,
+            scopes: token.scopes,
+            expires_at: token.expires_at,
+            created_at: token.created_at,
+            last_used_at: token.last_used_at,
+            revoked_at: token.revoked_at,
+        }
+    }
+}
+
+/// Returned once when a token is created, the only time the secret is available.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreatedPersonalAccessTokenDTO {
+    #[serde(flatten)]
+    pub token: PersonalAccessTokenDTO,
+    pub secret: String,
+}
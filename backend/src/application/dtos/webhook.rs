@@ -0,0 +1,64 @@
+use crate::domain::webhook::entity::{DeliveryStatus, WebhookDelivery, WebhookSubscription};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDTO {
+    pub id: String,
+    pub url: String,
+    /// Event types delivered, every type when empty.
+    pub event_types: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookSubscription> for WebhookDTO {
+    fn from(subscription: WebhookSubscription) -> Self {
+        Self {
+            id: subscription.id.to_string(),
+            url: subscription.url,
+            event_types: subscription.event_types,
+            created_at: subscription.created_at,
+        }
+    }
+}
+
+/// Returned once when a webhook is created, the only time the signing secret is available.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreatedWebhookDTO {
+    #[serde(flatten)]
+    pub webhook: WebhookDTO,
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDeliveryDTO {
+    pub id: String,
+    pub webhook_id: String,
+    pub event_id: String,
+    pub event_type: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    /// When the next attempt is due, while the delivery is pending.
+    pub available_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryDTO {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id.to_string(),
+            webhook_id: delivery.subscription_id.to_string(),
+            event_id: delivery.event.id.to_string(),
+            event_type: delivery.event.event_type().to_string(),
+            status: delivery.status,
+            attempts: delivery.attempts,
+            last_error: delivery.last_error,
+            available_at: delivery.available_at,
+            created_at: delivery.created_at,
+            processed_at: delivery.processed_at,
+        }
+    }
+}
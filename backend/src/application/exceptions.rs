@@ -1,6 +1,80 @@
+use serde::{Deserialize, Serialize};
 use sqlx::Error as SqlxError;
 use thiserror::Error;
 use tracing::error;
+use utoipa::ToSchema;
+
+/// A stable, machine-readable reason for a failed request, sent as `error.code` in the response
+/// envelope. Clients branch on these, so a code is never renamed or reused for another meaning.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    // The request itself
+    /// The body is valid JSON but doesn't have the expected shape, see `error.details`.
+    InvalidBody,
+    /// The body isn't valid JSON.
+    MalformedBody,
+    /// The body wasn't sent as `application/json`.
+    UnsupportedMediaType,
+    /// The body is larger than the server accepts.
+    PayloadTooLarge,
+    /// The request failed validation, see `error.details`.
+    ValidationFailed,
+    /// An id in the path isn't a valid UUID.
+    InvalidId,
+    InvalidShare,
+    InvalidQuestStatus,
+    InvalidQuestType,
+    InvalidStatusTransition,
+    InvalidRole,
+    InvalidInvitee,
+    AlreadyMember,
+    InvalidTokenName,
+    InvalidScope,
+    InvalidExpiry,
+    ReasonRequired,
+    ConfirmationRequired,
+    ErasureAlreadyScheduled,
+    /// The `Idempotency-Key` header isn't 1 to 255 visible ASCII characters.
+    InvalidIdempotencyKey,
+    /// The `Idempotency-Key` was already used for a different request.
+    IdempotencyKeyReused,
+    /// The first request with this `Idempotency-Key` is still being handled.
+    IdempotencyKeyInProgress,
+    InvalidWebhookUrl,
+    InvalidEventType,
+    /// The lock changed since the version the request was based on, see `If-Match`.
+    LockModified,
+
+    // Missing resources
+    LockNotFound,
+    /// The deleted lock is past its retention window and can no longer be restored.
+    LockRetentionExpired,
+    QuestNotFound,
+    MembershipNotFound,
+    InvitationNotFound,
+    TokenNotFound,
+    ErasureNotFound,
+    WebhookNotFound,
+    DeadLetterNotFound,
+    UserNotFound,
+
+    // Authentication and authorization
+    /// No bearer token was sent.
+    Unauthenticated,
+    /// The Authorization header isn't a well formed bearer credential.
+    MalformedAuthorization,
+    InvalidToken,
+    InsufficientScope,
+    /// The route can't be used with a personal access token.
+    SessionRequired,
+    AdminRequired,
+    Forbidden,
+
+    // The server
+    RequestTimeout,
+    InternalError,
+}
 
 /// AppError is an enum that represents various types of errors that can occur in the application.
 /// It implements the `std::error::Error` trait and the `axum::response::IntoResponse` trait.
@@ -8,18 +82,27 @@ use tracing::error;
 pub enum AppError {
     #[error("Database error: {0}")]
     DatabaseError(#[from] SqlxError),
-    #[error("Not found: {0}")]
-    NotFound(String),
+    #[error("Not found: {1}")]
+    NotFound(ErrorCode, String),
     #[error("Internal server error")]
     InternalError,
-    #[error("Validation error: {0}")]
-    ValidationError(String),
+    #[error("Validation error: {1}")]
+    ValidationError(ErrorCode, String),
     #[error("Invalid Quest Share provided")]
     InvalidQuestShare,
 
     /// Used for authentication-related errors
     #[error("Unauthorised: {0}")]
     Unauthorised(String),
+    /// The caller is known but may not perform the action, see `application::policy`.
+    #[error("Forbidden: {1}")]
+    Forbidden(ErrorCode, String),
+    /// A write raced another one on the same resource.
+    #[error("Conflict: {1}")]
+    Conflict(ErrorCode, String),
+    /// The request's `If-Match` no longer holds.
+    #[error("Precondition failed: {1}")]
+    PreconditionFailed(ErrorCode, String),
     #[error("Wrong credentials")]
     WrongCredentials,
     #[error("Missing credentials")]
@@ -31,3 +114,25 @@ pub enum AppError {
     #[error("User not found")]
     UserNotFound,
 }
+
+impl AppError {
+    /// The code sent to clients for this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::NotFound(code, _)
+            | AppError::ValidationError(code, _)
+            | AppError::Forbidden(code, _)
+            | AppError::Conflict(code, _)
+            | AppError::PreconditionFailed(code, _) => *code,
+            AppError::DatabaseError(_) | AppError::InternalError | AppError::TokenCreation => {
+                ErrorCode::InternalError
+            }
+            AppError::InvalidQuestShare => ErrorCode::InvalidShare,
+            AppError::Unauthorised(_) | AppError::WrongCredentials | AppError::InvalidToken => {
+                ErrorCode::InvalidToken
+            }
+            AppError::MissingCredentials => ErrorCode::Unauthenticated,
+            AppError::UserNotFound => ErrorCode::UserNotFound,
+        }
+    }
+}
@@ -1,3 +1,5 @@
 pub mod dtos;
 pub mod exceptions;
+pub mod policy;
+pub mod precondition;
 pub mod services;
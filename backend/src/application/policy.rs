@@ -0,0 +1,154 @@
+//! Authorization policy for locks and the quests under them.
+//!
+//! Every service that touches a lock asks `authorize_lock` first. A caller who may not do
+//! anything at all with a lock gets `NotFound`, exactly as if it didn't exist, so lock ids
+//! can't be probed. A caller who can see the lock but not perform this action gets
+//! `Forbidden`.
+//!
+//! New roles are a new `Relationship` variant and a row in `permits`.
+use crate::{
+    application::exceptions::{AppError, ErrorCode},
+    domain::{
+        auth::entity::Principal,
+        lock::entity::Lock,
+        membership::{
+            entity::MembershipRole,
+            repository::LockMembershipRepository as LockMembershipRepositoryInterface,
+        },
+    },
+};
+
+/// Role granted by the identity provider to operators of the service.
+pub const ADMIN_ROLE: &str = "admin";
+
+/// What the caller wants to do with a lock. Quests are covered by their lock's policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockAction {
+    /// Read the lock and its quests, see `Relationship::sees_secrets` for what is redacted.
+    View,
+    /// Plan quests or record progress on them.
+    Edit,
+    Delete,
+    Restore,
+    /// Invite people to the lock and manage their memberships.
+    Share,
+}
+
+/// How the caller relates to a lock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relationship {
+    Owner,
+    /// Invited through an accepted membership to follow the lock's progress.
+    Viewer,
+    /// Anyone else, operators included. They act on other people's locks through the audited
+    /// admin API, never through these services.
+    Stranger,
+}
+
+impl Relationship {
+    /// Whether the caller may see shares and quest data, rather than only progress.
+    pub fn sees_secrets(self) -> bool {
+        self == Relationship::Owner
+    }
+}
+
+fn relationship(
+    principal: &Principal,
+    lock: &Lock,
+    membership: Option<MembershipRole>,
+) -> Relationship {
+    if lock.user_id == principal.subject {
+        return Relationship::Owner;
+    }
+    match membership {
+        Some(MembershipRole::OWNER) => Relationship::Owner,
+        Some(MembershipRole::VIEWER) => Relationship::Viewer,
+        None => Relationship::Stranger,
+    }
+}
+
+fn permits(relationship: Relationship, action: LockAction) -> bool {
+    matches!(
+        (relationship, action),
+        (Relationship::Owner, _) | (Relationship::Viewer, LockAction::View)
+    )
+}
+
+/// The caller's accepted membership of `lock`, for `authorize_lock`. Owners have none, so
+/// the lookup is skipped for them.
+pub async fn membership_role(
+    repo: &dyn LockMembershipRepositoryInterface,
+    principal: &Principal,
+    lock: &Lock,
+) -> Result<Option<MembershipRole>, AppError> {
+    if lock.user_id == principal.subject {
+        return Ok(None);
+    }
+    repo.get_role(lock.id, principal.subject.clone())
+        .await
+        .map_err(AppError::DatabaseError)
+}
+
+pub fn authorize_lock(
+    principal: &Principal,
+    lock: &Lock,
+    membership: Option<MembershipRole>,
+    action: LockAction,
+) -> Result<Relationship, AppError> {
+    let relationship = relationship(principal, lock, membership);
+    if permits(relationship, action) {
+        return Ok(relationship);
+    }
+
+    let visible = [
+        LockAction::View,
+        LockAction::Edit,
+        LockAction::Delete,
+        LockAction::Restore,
+        LockAction::Share,
+    ]
+    .into_iter()
+    .any(|action| permits(relationship, action));
+
+    if visible {
+        Err(AppError::Forbidden(
+            ErrorCode::Forbidden,
+            format!(
+                "You are not allowed to {} this lock",
+                format!("{action:?}").to_lowercase()
+            ),
+        ))
+    } else {
+        Err(AppError::NotFound(
+            ErrorCode::LockNotFound,
+            "Lock not found".to_string(),
+        ))
+    }
+}
+
+/// Whether the caller may use the admin API at all.
+pub fn authorize_admin(principal: &Principal) -> Result<(), AppError> {
+    if principal.has_role(ADMIN_ROLE) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            ErrorCode::AdminRequired,
+            "The admin role is required for this request".to_string(),
+        ))
+    }
+}
+
+/// `authorize_lock` for a quest of `lock`, a hidden lock hides its quests too.
+pub fn authorize_quest(
+    principal: &Principal,
+    lock: &Lock,
+    membership: Option<MembershipRole>,
+    action: LockAction,
+) -> Result<Relationship, AppError> {
+    authorize_lock(principal, lock, membership, action).map_err(|err| match err {
+        AppError::NotFound(..) => {
+            AppError::NotFound(ErrorCode::QuestNotFound, "Quest not found".to_string())
+        }
+        err => err,
+    })
+}
@@ -0,0 +1,45 @@
+//! Compare-and-set for lock commands.
+//!
+//! Every change to a lock or its quests moves the lock to its next `version`. Lock queries send
+//! it as the ETag, and a command sent with `If-Match` only goes ahead while the lock is still at
+//! that version. The repositories check the version again in the write itself, so of two
+//! requests racing on the same lock only the first one wins.
+use crate::{
+    application::exceptions::{AppError, ErrorCode},
+    domain::lock::entity::Lock,
+};
+
+/// The `If-Match` condition of a command on a lock, or on one of its quests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LockPrecondition {
+    /// No `If-Match`, or `If-Match: *`. The command applies to whatever version is current.
+    #[default]
+    Any,
+    /// The lock must still be at one of these versions.
+    Versions(Vec<i64>),
+}
+
+impl LockPrecondition {
+    pub fn check(&self, lock: &Lock) -> Result<(), AppError> {
+        match self {
+            LockPrecondition::Versions(versions) if !versions.contains(&lock.version) => {
+                Err(self.failed())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The error for a write that found the lock at another version than it was read at.
+    pub fn failed(&self) -> AppError {
+        match self {
+            LockPrecondition::Any => AppError::Conflict(
+                ErrorCode::LockModified,
+                "The lock was changed by another request, try again".to_string(),
+            ),
+            LockPrecondition::Versions(_) => AppError::PreconditionFailed(
+                ErrorCode::LockModified,
+                "The lock has changed since it was read".to_string(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::application::{
+    dtos::account::{AccountErasureDTO, AccountExportDTO},
+    exceptions::AppError,
+};
+use crate::domain::auth::entity::Principal;
+
+use async_trait::async_trait;
+
+/// Phrase the user has to send back to confirm they want their account erased.
+pub const ERASURE_CONFIRMATION: &str = "erase my account";
+
+/// A user's access to, and erasure of, everything stored about them.
+#[async_trait]
+pub trait AccountServiceTrait: Send + Sync {
+    async fn export(&self, principal: &Principal) -> Result<AccountExportDTO, AppError>;
+
+    /// Schedule the erasure of all of the user's data once the grace period is over.
+    /// `confirmation` must be [`ERASURE_CONFIRMATION`].
+    async fn request_erasure(
+        &self,
+        principal: &Principal,
+        confirmation: String,
+    ) -> Result<AccountErasureDTO, AppError>;
+
+    async fn get_erasure(&self, principal: &Principal) -> Result<AccountErasureDTO, AppError>;
+
+    async fn cancel_erasure(&self, principal: &Principal) -> Result<(), AppError>;
+
+    /// Erase the accounts whose grace period is over. Returns how many were erased.
+    async fn erase_due_accounts(&self) -> Result<u64, AppError>;
+}
@@ -0,0 +1,42 @@
+use crate::application::{
+    dtos::admin::{AdminLockDTO, AdminQuestDTO},
+    exceptions::AppError,
+};
+use crate::domain::auth::entity::Principal;
+
+use async_trait::async_trait;
+
+/// Support operations for operators. Every call is written to the audit log under the
+/// operator's name before it takes effect.
+#[async_trait]
+pub trait AdminServiceTrait: Send + Sync {
+    /// The user's locks, including soft deleted ones that have not been purged yet.
+    async fn get_locks_by_user(
+        &self,
+        operator: &Principal,
+        user_id: String,
+    ) -> Result<Vec<AdminLockDTO>, AppError>;
+
+    async fn get_quest(
+        &self,
+        operator: &Principal,
+        quest_id: String,
+    ) -> Result<AdminQuestDTO, AppError>;
+
+    /// Set a quest's status, bypassing the usual transition rules.
+    async fn force_quest_status(
+        &self,
+        operator: &Principal,
+        quest_id: String,
+        status: String,
+        reason: String,
+    ) -> Result<AdminQuestDTO, AppError>;
+
+    /// Restore a soft deleted lock, even if it is past the window in which its owner could.
+    async fn cancel_lock_deletion(
+        &self,
+        operator: &Principal,
+        lock_id: String,
+        reason: Option<String>,
+    ) -> Result<AdminLockDTO, AppError>;
+}
@@ -1,7 +1,9 @@
 use crate::application::exceptions::AppError;
+use crate::domain::auth::entity::Principal;
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait AuthServiceTrait: Send + Sync {
-    async fn verify(&self, token: &str) -> Result<String, AppError>;
+    /// Verify a bearer token, checking its signature, expiry, issuer and audience.
+    async fn verify(&self, token: &str) -> Result<Principal, AppError>;
 }
@@ -0,0 +1,17 @@
+use crate::{application::exceptions::AppError, domain::event::entity::DomainEvent};
+
+use async_trait::async_trait;
+
+/// A side effect run by the outbox dispatcher for each committed domain event.
+///
+/// Delivery is at least once: an event is retried on the handlers that failed it until all of
+/// them succeed. A handler can still see an event twice, e.g. when the dispatcher dies before
+/// recording the outcome, so handlers must tolerate that.
+#[async_trait]
+pub trait EventHandlerTrait: Send + Sync {
+    /// Name used when logging handler failures, and to remember which handlers are done with
+    /// an event. Renaming a handler makes it handle pending retries again.
+    fn name(&self) -> &'static str;
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError>;
+}
@@ -0,0 +1,33 @@
+use crate::application::exceptions::AppError;
+use crate::domain::{auth::entity::Principal, event::entity::DomainEvent};
+
+use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+use uuid::Uuid;
+
+/// An item of a user's live event stream.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamedEvent {
+    Event(DomainEvent),
+    /// Events may have been missed, the client should reload what it shows.
+    Reset,
+}
+
+/// Pushes committed lock and quest changes to the users following them, see `GET /events`.
+///
+/// Events are only shared within this process, a client connected to another instance doesn't
+/// see them.
+#[async_trait]
+pub trait EventStreamServiceTrait: Send + Sync {
+    /// Publish the events of a change that was just saved.
+    fn publish(&self, events: &[DomainEvent]);
+
+    /// The events of the locks the user owns or has accepted a membership of, as they happen.
+    /// With `last_event_id`, the buffered events since that one are sent first, or a
+    /// [`StreamedEvent::Reset`] if it is no longer buffered.
+    async fn subscribe(
+        &self,
+        principal: &Principal,
+        last_event_id: Option<Uuid>,
+    ) -> Result<BoxStream<'static, StreamedEvent>, AppError>;
+}
@@ -0,0 +1,42 @@
+use crate::application::exceptions::AppError;
+use crate::domain::idempotency::entity::StoredResponse;
+
+use async_trait::async_trait;
+
+/// What to do with a command sent with an `Idempotency-Key`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyOutcome {
+    /// The key is new and now reserved, run the command and `complete` or `release` the key.
+    Proceed,
+    /// The command already ran, send its response again.
+    Replay(StoredResponse),
+    /// The first request with this key hasn't finished yet.
+    InProgress,
+    /// The key was used for a different request.
+    Mismatch,
+}
+
+#[async_trait]
+pub trait IdempotencyServiceTrait: Send + Sync {
+    /// `fingerprint` identifies the request, a key can only be reused for the same one.
+    async fn begin(
+        &self,
+        user_id: String,
+        key: String,
+        fingerprint: String,
+    ) -> Result<IdempotencyOutcome, AppError>;
+
+    /// Keep the response of a command started with `begin`.
+    async fn complete(
+        &self,
+        user_id: String,
+        key: String,
+        response: StoredResponse,
+    ) -> Result<(), AppError>;
+
+    /// Give the key up so the command can be retried with it, used when it failed.
+    async fn release(&self, user_id: String, key: String) -> Result<(), AppError>;
+
+    /// Remove the expired keys. Returns how many were removed.
+    async fn purge_expired(&self) -> Result<u64, AppError>;
+}
@@ -1,10 +1,46 @@
 use crate::application::{dtos::lock::LockDTO, exceptions::AppError};
+use crate::domain::{
+    auth::entity::Principal, event::entity::DomainEvent, lock::repository::ReadPreference,
+};
 
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait LockQueryServiceTrait: Send + Sync {
-    async fn get_lock_by_id(&self, user_id: String, lock_id: String) -> Result<LockDTO, AppError>;
+    async fn get_lock_by_id(
+        &self,
+        principal: &Principal,
+        lock_id: String,
+        read: ReadPreference,
+    ) -> Result<LockDTO, AppError>;
 
-    async fn get_locks(&self, user_id: String) -> Result<Vec<LockDTO>, AppError>;
+    /// Each of the locks, in the order asked for, or why the caller can't have it.
+    async fn get_locks_by_ids(
+        &self,
+        principal: &Principal,
+        lock_ids: Vec<String>,
+        read: ReadPreference,
+    ) -> Vec<Result<LockDTO, AppError>>;
+
+    /// What happened to the lock, oldest first. Viewers of a shared lock see its history too.
+    async fn get_lock_history(
+        &self,
+        principal: &Principal,
+        lock_id: String,
+        read: ReadPreference,
+    ) -> Result<Vec<DomainEvent>, AppError>;
+
+    /// Locks the caller owns and locks shared with them.
+    async fn get_locks(
+        &self,
+        principal: &Principal,
+        read: ReadPreference,
+    ) -> Result<Vec<LockDTO>, AppError>;
+
+    /// Only the locks other users have shared with the caller.
+    async fn get_shared_locks(
+        &self,
+        principal: &Principal,
+        read: ReadPreference,
+    ) -> Result<Vec<LockDTO>, AppError>;
 }
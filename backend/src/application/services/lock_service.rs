@@ -1,6 +1,11 @@
 use std::collections::HashMap;
 
-use crate::application::{dtos::lock::LockDTO, exceptions::AppError};
+use crate::application::{
+    dtos::lock::{BatchMode, LockDTO, NewLock},
+    exceptions::AppError,
+    precondition::LockPrecondition,
+};
+use crate::domain::auth::entity::Principal;
 
 use async_trait::async_trait;
 
@@ -8,7 +13,7 @@ use async_trait::async_trait;
 pub trait LockServiceTrait: Send + Sync {
     async fn create_lock(
         &self,
-        user_id: String,
+        principal: &Principal,
         label: Option<String>,
         total_shares: u8,
         threshold: u8,
@@ -16,6 +21,7 @@ pub trait LockServiceTrait: Send + Sync {
 
     async fn plan_quest(
         &self,
+        principal: &Principal,
         lock_id: String,
         share: String,
         quest_type: String,
@@ -24,10 +30,36 @@ pub trait LockServiceTrait: Send + Sync {
 
     async fn create_lock_with_quests(
         &self,
-        user_id: String,
+        principal: &Principal,
         label: Option<String>,
         total_shares: u8,
         threshold: u8,
         quest_data: Vec<(String, String, HashMap<String, String>)>,
     ) -> Result<LockDTO, AppError>;
+
+    /// Create several locks for the caller, in the order given. In `BatchMode::Atomic` the first
+    /// failure is returned and nothing is created, otherwise each lock gets its own result.
+    async fn create_locks(
+        &self,
+        principal: &Principal,
+        locks: Vec<NewLock>,
+        mode: BatchMode,
+    ) -> Result<Vec<Result<LockDTO, AppError>>, AppError>;
+
+    async fn delete_lock(
+        &self,
+        principal: &Principal,
+        lock_id: String,
+        precondition: LockPrecondition,
+    ) -> Result<(), AppError>;
+
+    async fn restore_lock(
+        &self,
+        principal: &Principal,
+        lock_id: String,
+        precondition: LockPrecondition,
+    ) -> Result<LockDTO, AppError>;
+
+    /// Permanently remove locks whose retention window has passed, returning how many were purged.
+    async fn purge_deleted_locks(&self) -> Result<u64, AppError>;
 }
@@ -0,0 +1,43 @@
+use crate::application::{dtos::membership::LockMembershipDTO, exceptions::AppError};
+use crate::domain::auth::entity::Principal;
+
+use async_trait::async_trait;
+
+/// Sharing a lock with other users. The owner invites someone by their user id, and the
+/// invitation grants nothing until that user accepts it.
+#[async_trait]
+pub trait MembershipServiceTrait: Send + Sync {
+    async fn invite_member(
+        &self,
+        principal: &Principal,
+        lock_id: String,
+        user_id: String,
+        role: String,
+    ) -> Result<LockMembershipDTO, AppError>;
+
+    /// Everyone invited to the lock, pending or accepted.
+    async fn get_members(
+        &self,
+        principal: &Principal,
+        lock_id: String,
+    ) -> Result<Vec<LockMembershipDTO>, AppError>;
+
+    /// Invitations waiting for the caller to accept them.
+    async fn get_invitations(
+        &self,
+        principal: &Principal,
+    ) -> Result<Vec<LockMembershipDTO>, AppError>;
+
+    async fn accept_invitation(
+        &self,
+        principal: &Principal,
+        membership_id: String,
+    ) -> Result<LockMembershipDTO, AppError>;
+
+    /// Members can leave or decline, the owner can remove anyone.
+    async fn remove_membership(
+        &self,
+        principal: &Principal,
+        membership_id: String,
+    ) -> Result<(), AppError>;
+}
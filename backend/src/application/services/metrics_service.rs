@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use crate::{application::exceptions::AppError, domain::quest::enums::QuestType};
+
+/// What a quest status update ended in, the `outcome` label of the attempt counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttemptOutcome {
+    /// The quest moved on, e.g. started or abandoned. Owners can't complete a quest.
+    Updated,
+    /// The quest can't move to the requested status.
+    Rejected,
+    /// The lock changed since the version the attempt was based on.
+    Conflict,
+    /// The caller may not update the quest, or it doesn't exist.
+    Denied,
+    Failed,
+}
+
+impl AttemptOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttemptOutcome::Updated => "updated",
+            AttemptOutcome::Rejected => "rejected",
+            AttemptOutcome::Conflict => "conflict",
+            AttemptOutcome::Denied => "denied",
+            AttemptOutcome::Failed => "failed",
+        }
+    }
+
+    /// The outcome of an update that failed with `error`.
+    pub fn from_error(error: &AppError) -> Self {
+        match error {
+            AppError::ValidationError(..) | AppError::InvalidQuestShare => AttemptOutcome::Rejected,
+            AppError::Conflict(..) | AppError::PreconditionFailed(..) => AttemptOutcome::Conflict,
+            AppError::NotFound(..)
+            | AppError::Forbidden(..)
+            | AppError::Unauthorised(_)
+            | AppError::WrongCredentials
+            | AppError::MissingCredentials
+            | AppError::InvalidToken
+            | AppError::UserNotFound => AttemptOutcome::Denied,
+            AppError::DatabaseError(_) | AppError::InternalError | AppError::TokenCreation => {
+                AttemptOutcome::Failed
+            }
+        }
+    }
+}
+
+/// Collects the process' metrics and renders them for `GET /metrics`.
+pub trait MetricsServiceTrait: Send + Sync {
+    /// Record a handled HTTP request. `route` is the matched route template, never the raw
+    /// path, so ids don't end up as labels.
+    fn observe_request(&self, method: &str, route: &str, status: u16, latency: Duration);
+
+    fn lock_created(&self);
+
+    /// Record an owner's quest status update.
+    fn quest_attempted(&self, outcome: AttemptOutcome);
+
+    /// Record a completed quest. Only operators complete quests, by forcing their status.
+    fn quest_completed(&self, quest_type: &QuestType);
+
+    /// Record a run of a background job. Its lag is the time since its last successful run.
+    fn job_ran(&self, job: &'static str, succeeded: bool);
+
+    /// Every metric in the Prometheus text exposition format.
+    fn render(&self) -> String;
+}
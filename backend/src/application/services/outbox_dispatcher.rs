@@ -0,0 +1,10 @@
+use crate::application::exceptions::AppError;
+
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait OutboxDispatcherTrait: Send + Sync {
+    /// Claim a batch of due outbox events and run the registered handlers on them.
+    /// Returns the number of events claimed.
+    async fn dispatch_batch(&self) -> Result<usize, AppError>;
+}
@@ -0,0 +1,13 @@
+use crate::application::{dtos::quest::QuestDTO, exceptions::AppError};
+use crate::domain::auth::entity::Principal;
+
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait QuestQueryServiceTrait: Send + Sync {
+    async fn get_quest_by_id(
+        &self,
+        principal: &Principal,
+        quest_id: String,
+    ) -> Result<QuestDTO, AppError>;
+}
@@ -0,0 +1,20 @@
+use crate::application::{
+    dtos::{lock::LockDTO, quest::QuestDTO},
+    exceptions::AppError,
+    precondition::LockPrecondition,
+};
+use crate::domain::auth::entity::Principal;
+
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait QuestServiceTrait: Send + Sync {
+    /// The updated quest, along with its lock at the version the update moved it to.
+    async fn update_quest_status(
+        &self,
+        principal: &Principal,
+        quest_id: String,
+        status: String,
+        precondition: LockPrecondition,
+    ) -> Result<(QuestDTO, LockDTO), AppError>;
+}
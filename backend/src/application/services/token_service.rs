@@ -0,0 +1,21 @@
+use crate::application::{
+    dtos::token::{CreatedPersonalAccessTokenDTO, PersonalAccessTokenDTO},
+    exceptions::AppError,
+};
+
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait TokenServiceTrait: Send + Sync {
+    async fn create_token(
+        &self,
+        user_id: String,
+        name: String,
+        scopes: Vec<String>,
+        expires_in_days: u32,
+    ) -> Result<CreatedPersonalAccessTokenDTO, AppError>;
+
+    async fn list_tokens(&self, user_id: String) -> Result<Vec<PersonalAccessTokenDTO>, AppError>;
+
+    async fn revoke_token(&self, user_id: String, token_id: String) -> Result<(), AppError>;
+}
@@ -0,0 +1,44 @@
+use crate::application::{
+    dtos::webhook::{CreatedWebhookDTO, WebhookDTO, WebhookDeliveryDTO},
+    exceptions::AppError,
+};
+
+use async_trait::async_trait;
+
+/// Header naming the event type of a delivery.
+pub const WEBHOOK_EVENT_HEADER: &str = "x-quest-lock-event";
+/// Header carrying the delivery id, the same on every attempt of a delivery.
+pub const WEBHOOK_DELIVERY_HEADER: &str = "x-quest-lock-delivery";
+/// Header carrying the unix time the delivery was signed at.
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "x-quest-lock-timestamp";
+/// Header carrying `sha256=<hex HMAC-SHA256 of "{timestamp}.{body}">`.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-quest-lock-signature";
+
+/// Outbound webhooks: each user's subscriptions, and sending them the events of their locks.
+#[async_trait]
+pub trait WebhookServiceTrait: Send + Sync {
+    async fn create_webhook(
+        &self,
+        user_id: String,
+        url: String,
+        event_types: Vec<String>,
+    ) -> Result<CreatedWebhookDTO, AppError>;
+
+    async fn list_webhooks(&self, user_id: String) -> Result<Vec<WebhookDTO>, AppError>;
+
+    async fn delete_webhook(&self, user_id: String, webhook_id: String) -> Result<(), AppError>;
+
+    /// Deliveries that failed every attempt.
+    async fn list_dead_letters(&self, user_id: String)
+    -> Result<Vec<WebhookDeliveryDTO>, AppError>;
+
+    /// Queue a dead letter to be sent again.
+    async fn replay_dead_letter(
+        &self,
+        user_id: String,
+        delivery_id: String,
+    ) -> Result<WebhookDeliveryDTO, AppError>;
+
+    /// Send the deliveries that are due. Returns how many were attempted.
+    async fn deliver_batch(&self) -> Result<usize, AppError>;
+}
@@ -0,0 +1,87 @@
+//! Mint an HS256 access token for local development and CI, for use with `AUTH_MODE=hs256`.
+//!
+//! Usage: mint_dev_token <subject> [--scope <scope>]... [--role <role>]... [--email <email>]
+//!        [--ttl-secs <seconds>]
+//!
+//! The token is signed with `AUTH_HS256_SECRET` and carries `AUTH_ISSUER` and `AUTH_AUDIENCE`
+//! when they are set, so it passes the same checks as the server applies.
+use std::{env, process::exit, time::Duration};
+
+use jwtk::{
+    HeaderAndClaims,
+    hmac::{HmacAlgorithm, HmacKey},
+};
+use quest_lock_backend::infrastructure::services::auth_service::MIN_HS256_SECRET_LEN;
+
+fn usage() -> ! {
+    eprintln!(
+        "Usage: mint_dev_token <subject> [--scope <scope>]... [--role <role>]... \
+         [--email <email>] [--ttl-secs <seconds>]"
+    );
+    exit(2);
+}
+
+fn main() {
+    dotenv::dotenv().ok();
+
+    let mut args = env::args().skip(1);
+    let subject = match args.next() {
+        Some(subject) if !subject.starts_with("--") => subject,
+        _ => usage(),
+    };
+
+    let mut scopes = Vec::new();
+    let mut roles = Vec::new();
+    let mut email = None;
+    let mut ttl_secs = 3600;
+    while let Some(flag) = args.next() {
+        let value = args.next().unwrap_or_else(|| usage());
+        match flag.as_str() {
+            "--scope" => scopes.push(value),
+            "--role" => roles.push(value),
+            "--email" => email = Some(value),
+            "--ttl-secs" => ttl_secs = value.parse().unwrap_or_else(|_| usage()),
+            _ => usage(),
+        }
+    }
+
+    let secret = env::var("AUTH_HS256_SECRET").unwrap_or_default();
+    if secret.len() < MIN_HS256_SECRET_LEN {
+        eprintln!("AUTH_HS256_SECRET must be set to at least {MIN_HS256_SECRET_LEN} bytes");
+        exit(1);
+    }
+    let key = HmacKey::from_bytes(secret.as_bytes(), HmacAlgorithm::HS256);
+
+    let mut token = HeaderAndClaims::new_dynamic();
+    token
+        .set_sub(subject)
+        .set_iat_now()
+        .set_exp_from_now(Duration::from_secs(ttl_secs));
+    if let Ok(issuer) = env::var("AUTH_ISSUER")
+        && !issuer.is_empty()
+    {
+        token.set_iss(issuer);
+    }
+    if let Ok(audience) = env::var("AUTH_AUDIENCE")
+        && !audience.is_empty()
+    {
+        token.add_aud(audience);
+    }
+    if !scopes.is_empty() {
+        token.insert("scope", scopes.join(" "));
+    }
+    if !roles.is_empty() {
+        token.insert("roles", roles);
+    }
+    if let Some(email) = email {
+        token.insert("email", email);
+    }
+
+    match jwtk::sign(&mut token, &key) {
+        Ok(token) => println!("{token}"),
+        Err(e) => {
+            eprintln!("Failed to sign token: {e}");
+            exit(1);
+        }
+    }
+}
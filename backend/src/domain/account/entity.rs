@@ -0,0 +1,31 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A user's request to have all of their data erased. Nothing is removed until the grace
+/// period is over, and until then the user can change their mind.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct AccountErasure {
+    pub user_id: String,
+    pub requested_at: DateTime<Utc>,
+    pub scheduled_for: DateTime<Utc>,
+}
+
+impl AccountErasure {
+    pub fn schedule(user_id: String, grace_period: Duration) -> Self {
+        let requested_at = Utc::now();
+        Self {
+            user_id,
+            requested_at,
+            scheduled_for: requested_at + grace_period,
+        }
+    }
+}
+
+/// How much was removed when an account was erased.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ErasureSummary {
+    pub locks: u64,
+    pub memberships: u64,
+    pub personal_access_tokens: u64,
+    pub events: u64,
+}
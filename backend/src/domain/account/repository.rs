@@ -0,0 +1,38 @@
+use super::entity::{AccountErasure, ErasureSummary};
+use crate::domain::audit::entity::AuditRecord;
+use crate::domain::event::entity::DomainEvent;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+#[async_trait]
+/// Trait representing repository-level operations for account erasure.
+/// A user has at most one scheduled erasure.
+pub trait AccountRepository: Send + Sync {
+    /// Events recorded for the user's locks, oldest first.
+    async fn get_history(&self, user_id: String) -> Result<Vec<DomainEvent>, sqlx::Error>;
+
+    async fn get_erasure(&self, user_id: String) -> Result<Option<AccountErasure>, sqlx::Error>;
+
+    /// Returns false if an erasure is already scheduled for the user.
+    async fn save_erasure(&self, erasure: &AccountErasure) -> Result<bool, sqlx::Error>;
+
+    async fn cancel_erasure(&self, user_id: String) -> Result<bool, sqlx::Error>;
+
+    /// Erasures whose grace period ended before `now`, oldest first.
+    async fn get_due_erasures(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<AccountErasure>, sqlx::Error>;
+
+    /// Remove everything belonging to the user: their locks with quests, shares, members and
+    /// events, their memberships of other locks and their personal access tokens. Audit
+    /// records about them lose the user id. `audit` is written in the same transaction.
+    /// Returns `None` if the erasure was cancelled in the meantime.
+    async fn erase(
+        &self,
+        erasure: &AccountErasure,
+        audit: &AuditRecord,
+    ) -> Result<Option<ErasureSummary>, sqlx::Error>;
+}
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::quest::enums::QuestStatus;
+
+/// Operator named on records of actions the service takes by itself.
+pub const SYSTEM_OPERATOR: &str = "system";
+
+/// Stands in for the target id of records about an erased account.
+pub const ERASED_TARGET_ID: &str = "erased-account";
+
+/// A support action taken through the admin API, kept so every operator intervention can be
+/// traced back to the person who made it.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct AuditRecord {
+    pub id: Uuid,
+    /// Subject of the operator who took the action.
+    pub operator: String,
+    /// Id of the user, lock or quest the action concerned.
+    pub target_id: String,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub action: AuditAction,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "action")]
+pub enum AuditAction {
+    #[serde(rename = "locks.looked_up")]
+    LocksLookedUp,
+    #[serde(rename = "quest.inspected")]
+    QuestInspected,
+    #[serde(rename = "quest.status_forced")]
+    QuestStatusForced { from: QuestStatus, to: QuestStatus },
+    #[serde(rename = "lock.deletion_cancelled")]
+    LockDeletionCancelled,
+    /// A user's data was erased at their request. The record keeps no trace of who they were.
+    #[serde(rename = "account.erased")]
+    AccountErased,
+}
+
+impl AuditRecord {
+    pub fn create(
+        operator: String,
+        target_id: String,
+        reason: Option<String>,
+        action: AuditAction,
+    ) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            operator,
+            target_id,
+            reason,
+            created_at: Utc::now(),
+            action,
+        }
+    }
+}
+
+impl AuditAction {
+    /// Stable name of the action, as stored in the audit log `action` column.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AuditAction::LocksLookedUp => "locks.looked_up",
+            AuditAction::QuestInspected => "quest.inspected",
+            AuditAction::QuestStatusForced { .. } => "quest.status_forced",
+            AuditAction::LockDeletionCancelled => "lock.deletion_cancelled",
+            AuditAction::AccountErased => "account.erased",
+        }
+    }
+}
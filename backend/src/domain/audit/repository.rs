@@ -0,0 +1,10 @@
+use super::entity::AuditRecord;
+
+use async_trait::async_trait;
+
+#[async_trait]
+/// Trait representing repository-level operations for the admin audit log.
+/// The log is append only, records are never changed or removed.
+pub trait AuditRepository: Send + Sync {
+    async fn save(&self, record: &AuditRecord) -> Result<bool, sqlx::Error>;
+}
@@ -0,0 +1,169 @@
+use base64::prelude::*;
+use chrono::{DateTime, Duration, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Scopes that can be granted to a personal access token.
+pub const TOKEN_SCOPES: [&str; 3] = ["locks:read", "locks:write", "quests:attempt"];
+
+/// Marks a bearer token as a personal access token rather than a JWT.
+pub const PERSONAL_ACCESS_TOKEN_PREFIX: &str = "qlp_";
+
+/// How the caller proved who they are.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub enum AuthMethod {
+    /// An access token from the identity provider.
+    Jwt,
+    PersonalAccessToken {
+        token_id: Uuid,
+    },
+}
+
+/// The authenticated caller, built from a verified access token.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Principal {
+    /// Stable id of the user, the token's `sub` claim. Locks are owned by this id.
+    pub subject: String,
+    pub issuer: Option<String>,
+    pub scopes: Vec<String>,
+    pub roles: Vec<String>,
+    pub email: Option<String>,
+    /// When the user last actively authenticated, as opposed to when the token was refreshed.
+    pub auth_time: Option<DateTime<Utc>>,
+    pub method: AuthMethod,
+}
+
+impl Principal {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+
+    /// Whether the caller may use a route requiring `scope`. Personal access tokens are
+    /// limited to the scopes they were created with, as are JWTs that carry any of this
+    /// API's scopes. Other scopes, such as the OpenID ones the frontend's tokens carry, are
+    /// ignored: a JWT without any of ours is a first party session with the user's full access.
+    pub fn allows(&self, scope: &str) -> bool {
+        let scoped = self
+            .scopes
+            .iter()
+            .any(|s| TOKEN_SCOPES.contains(&s.as_str()));
+        match self.method {
+            AuthMethod::Jwt if !scoped => true,
+            _ => self.has_scope(scope),
+        }
+    }
+}
+
+/// A long lived token a user creates for scripts and integrations. Only a hash of the
+/// secret is kept, the secret itself is shown once when the token is created.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PersonalAccessToken {
+    pub id: Uuid,
+    pub user_id: String,
+    pub name: String,
+    /// Hex encoded SHA-256 of the secret.
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl PersonalAccessToken {
+    /// Create a token, returning it along with its secret.
+    pub fn generate(
+        user_id: String,
+        name: String,
+        scopes: Vec<String>,
+        lifetime: Duration,
+    ) -> (Self, String) {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let secret = format!(
+            "{PERSONAL_ACCESS_TOKEN_PREFIX}{}",
+            BASE64_URL_SAFE_NO_PAD.encode(bytes)
+        );
+
+        let now = Utc::now();
+        let token = Self {
+            id: Uuid::now_v7(),
+            user_id,
+            name,
+            token_hash: Self::hash_secret(&secret),
+            scopes,
+            expires_at: now + lifetime,
+            created_at: now,
+            last_used_at: None,
+            revoked_at: None,
+        };
+        (token, secret)
+    }
+
+    pub fn hash_secret(secret: &str) -> String {
+        hex::encode(Sha256::digest(secret.as_bytes()))
+    }
+
+    /// Whether the token can still be used to authenticate.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn principal(method: AuthMethod, scopes: &[&str]) -> Principal {
+        Principal {
+            subject: "user".to_string(),
+            issuer: None,
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            roles: vec![],
+            email: None,
+            auth_time: None,
+            method,
+        }
+    }
+
+    #[test]
+    fn personal_access_token_is_limited_to_its_scopes() {
+        let method = AuthMethod::PersonalAccessToken {
+            token_id: Uuid::now_v7(),
+        };
+        let token = principal(method.clone(), &["locks:read"]);
+        assert!(token.allows("locks:read"));
+        assert!(!token.allows("locks:write"));
+        assert!(!principal(method, &[]).allows("locks:read"));
+    }
+
+    #[test]
+    fn jwt_without_scopes_has_full_access() {
+        let jwt = principal(AuthMethod::Jwt, &[]);
+        for scope in TOKEN_SCOPES {
+            assert!(jwt.allows(scope));
+        }
+    }
+
+    #[test]
+    fn jwt_with_only_openid_scopes_has_full_access() {
+        let jwt = principal(AuthMethod::Jwt, &["openid", "profile", "email"]);
+        for scope in TOKEN_SCOPES {
+            assert!(jwt.allows(scope));
+        }
+    }
+
+    #[test]
+    fn jwt_with_api_scopes_is_limited_to_them() {
+        let jwt = principal(AuthMethod::Jwt, &["openid", "locks:read"]);
+        assert!(jwt.allows("locks:read"));
+        assert!(!jwt.allows("locks:write"));
+        assert!(!jwt.allows("quests:attempt"));
+    }
+}
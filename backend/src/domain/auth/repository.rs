@@ -0,0 +1,29 @@
+use super::entity::PersonalAccessToken;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[async_trait]
+/// Trait representing repository-level operations for personal access tokens.
+/// Revoked and expired tokens are kept so users can see them in their token list.
+pub trait PersonalAccessTokenRepository: Send + Sync {
+    async fn get_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PersonalAccessToken>, sqlx::Error>;
+
+    async fn get_by_user_id(
+        &self,
+        user_id: String,
+    ) -> Result<Vec<PersonalAccessToken>, sqlx::Error>;
+
+    async fn save(&self, token: &PersonalAccessToken) -> Result<bool, sqlx::Error>;
+
+    /// Revoke one of the user's tokens. Returns false if the user has no such active token.
+    async fn revoke(&self, id: Uuid, user_id: String) -> Result<bool, sqlx::Error>;
+
+    /// Record that the token was used. Implementations may skip the write if the token was
+    /// used very recently.
+    async fn touch(&self, id: Uuid, used_at: DateTime<Utc>) -> Result<bool, sqlx::Error>;
+}
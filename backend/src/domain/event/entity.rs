@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::quest::enums::{QuestStatus, QuestType};
+
+/// Something that happened to a lock or one of its quests.
+///
+/// Events are recorded on the entity they concern and written to the outbox in the same
+/// transaction that persists the change, so side effects only ever see committed state.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct DomainEvent {
+    pub id: Uuid,
+    pub lock_id: Uuid,
+    /// Owner of the lock the event concerns.
+    pub user_id: String,
+    pub occurred_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: DomainEventKind,
+}
+
+/// Every event type, as returned by [`DomainEvent::event_type`].
+pub const EVENT_TYPES: [&str; 6] = [
+    "lock.created",
+    "lock.deleted",
+    "lock.restored",
+    "lock.unlockable",
+    "quest.planned",
+    "quest.status_changed",
+];
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum DomainEventKind {
+    #[serde(rename = "lock.created")]
+    LockCreated,
+    #[serde(rename = "lock.deleted")]
+    LockDeleted,
+    #[serde(rename = "lock.restored")]
+    LockRestored,
+    /// Enough quests are complete to meet the lock's threshold.
+    #[serde(rename = "lock.unlockable")]
+    LockUnlockable,
+    #[serde(rename = "quest.planned")]
+    QuestPlanned {
+        quest_id: Uuid,
+        quest_type: QuestType,
+    },
+    #[serde(rename = "quest.status_changed")]
+    QuestStatusChanged {
+        quest_id: Uuid,
+        from: QuestStatus,
+        to: QuestStatus,
+    },
+}
+
+impl DomainEvent {
+    pub fn create(lock_id: Uuid, user_id: String, kind: DomainEventKind) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            lock_id,
+            user_id,
+            occurred_at: Utc::now(),
+            kind,
+        }
+    }
+
+    /// Stable name of the event, as stored in the outbox `event_type` column.
+    pub fn event_type(&self) -> &'static str {
+        self.kind.name()
+    }
+}
+
+impl DomainEventKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DomainEventKind::LockCreated => "lock.created",
+            DomainEventKind::LockDeleted => "lock.deleted",
+            DomainEventKind::LockRestored => "lock.restored",
+            DomainEventKind::LockUnlockable => "lock.unlockable",
+            DomainEventKind::QuestPlanned { .. } => "quest.planned",
+            DomainEventKind::QuestStatusChanged { .. } => "quest.status_changed",
+        }
+    }
+}
+
+/// An outbox row claimed by the dispatcher.
+#[derive(Debug, Clone)]
+pub struct OutboxMessage {
+    pub id: Uuid,
+    pub event: DomainEvent,
+    /// Delivery attempts so far, including the current one.
+    pub attempts: i32,
+    /// Names of the handlers that succeeded on an earlier attempt, skipped on this one.
+    pub handled_by: Vec<String>,
+}
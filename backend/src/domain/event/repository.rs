@@ -0,0 +1,31 @@
+use super::entity::OutboxMessage;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+#[async_trait]
+/// Trait representing the dispatcher's view of the outbox.
+/// Events are written to the outbox by the Lock and Quest repositories as part of `save`,
+/// this trait only covers claiming them and recording the outcome.
+pub trait OutboxRepository: Send + Sync {
+    /// Claim up to `limit` pending events that are due, locking the rows so that concurrent
+    /// dispatchers skip them. Claimed events are hidden from other dispatchers for `lease`.
+    async fn claim_batch(
+        &self,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<OutboxMessage>, sqlx::Error>;
+
+    async fn mark_done(&self, id: Uuid) -> Result<bool, sqlx::Error>;
+
+    /// Record a failed delivery. The event is retried at `retry_at`, or given up on when
+    /// `retry_at` is `None`. `handled_by` are the handlers done with it, which a retry skips.
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        handled_by: &[String],
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<bool, sqlx::Error>;
+}
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A command sent with an `Idempotency-Key`. The key is reserved before the command runs and
+/// the response is kept once it succeeded, so a retry gets the same answer instead of running
+/// the command again.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct IdempotencyRecord {
+    pub user_id: String,
+    pub key: String,
+    /// Hash of the request the key was first used for, a retry must match it.
+    pub fingerprint: String,
+    /// `None` while the first request is still being handled.
+    pub response: Option<StoredResponse>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl IdempotencyRecord {
+    pub fn reserve(user_id: String, key: String, fingerprint: String, ttl: Duration) -> Self {
+        let created_at = Utc::now();
+        Self {
+            user_id,
+            key,
+            fingerprint,
+            response: None,
+            created_at,
+            expires_at: created_at + ttl,
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// The response to replay for a key.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    /// Other headers the replay has to repeat, as name and value.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
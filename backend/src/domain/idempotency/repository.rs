@@ -0,0 +1,30 @@
+use super::entity::{IdempotencyRecord, StoredResponse};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+#[async_trait]
+/// Trait representing repository-level operations for idempotency keys.
+/// Keys are scoped to the user that sent them.
+pub trait IdempotencyRepository: Send + Sync {
+    /// Returns false if the user already holds the key.
+    async fn reserve(&self, record: &IdempotencyRecord) -> Result<bool, sqlx::Error>;
+
+    async fn get(
+        &self,
+        user_id: String,
+        key: String,
+    ) -> Result<Option<IdempotencyRecord>, sqlx::Error>;
+
+    async fn save_response(
+        &self,
+        user_id: String,
+        key: String,
+        response: &StoredResponse,
+    ) -> Result<bool, sqlx::Error>;
+
+    async fn delete(&self, user_id: String, key: String) -> Result<bool, sqlx::Error>;
+
+    /// Remove the keys that expired before `now`. Returns how many were removed.
+    async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, sqlx::Error>;
+}
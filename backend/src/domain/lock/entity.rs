@@ -1,7 +1,9 @@
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::domain::quest::entity::Quest;
+use crate::domain::event::entity::{DomainEvent, DomainEventKind};
+use crate::domain::quest::{entity::Quest, enums::QuestStatus};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Lock {
@@ -11,6 +13,13 @@ pub struct Lock {
     pub total_shares: u8,
     pub threshold: u8,
     pub quests: Vec<Quest>,
+    /// Set when the lock has been soft deleted, tombstoned locks are hidden from every query.
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Moves on with every change to the lock or its quests, see `LockPrecondition`.
+    pub version: i64,
+    /// Events recorded against the lock, written to the outbox when it is persisted.
+    #[serde(skip)]
+    pub events: Vec<DomainEvent>,
 }
 
 impl Lock {
@@ -28,6 +37,32 @@ impl Lock {
             total_shares,
             threshold,
             quests,
+            deleted_at: None,
+            version: 1,
+            events: vec![],
         };
     }
+
+    pub fn record(&mut self, kind: DomainEventKind) {
+        self.events
+            .push(DomainEvent::create(self.id, self.user_id.clone(), kind));
+    }
+
+    /// Whether enough quests are complete to meet the threshold.
+    pub fn is_unlockable(&self) -> bool {
+        let completed = self
+            .quests
+            .iter()
+            .filter(|quest| quest.status == QuestStatus::COMPLETED)
+            .count();
+        completed >= self.threshold as usize
+    }
+
+    /// Whether a soft deleted lock is still inside the retention window and can be restored.
+    pub fn is_restorable(&self, retention: Duration, now: DateTime<Utc>) -> bool {
+        match self.deleted_at {
+            Some(deleted_at) => deleted_at + retention > now,
+            None => false,
+        }
+    }
 }
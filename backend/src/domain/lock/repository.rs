@@ -1,17 +1,62 @@
 use super::entity::Lock;
+use crate::domain::event::entity::DomainEvent;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+/// Which database a read should go to when a read replica is configured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadPreference {
+    /// Read from the primary, for read-after-write paths that must see their own changes.
+    Primary,
+    /// Read from the replica, which may lag behind the primary.
+    Replica,
+}
+
 #[async_trait]
 /// Trait representing repository-level operations for Lock entities.
 /// Provides methods for saving, retrieving, updating, and deleting Locks in the database.
+/// Deleting only tombstones a lock, soft deleted locks are excluded from every other query
+/// until they are restored or purged.
 pub trait LockRepository: Send + Sync {
-    async fn get_by_id(&self, id: Uuid) -> Result<Option<Lock>, sqlx::Error>;
+    async fn get_by_id(&self, id: Uuid, read: ReadPreference) -> Result<Option<Lock>, sqlx::Error>;
 
-    async fn get_by_user_id(&self, user_id: String) -> Result<Vec<Lock>, sqlx::Error>;
+    /// Locks the user owns and locks shared with them through an accepted membership.
+    async fn get_by_user_id(
+        &self,
+        user_id: String,
+        read: ReadPreference,
+    ) -> Result<Vec<Lock>, sqlx::Error>;
 
+    /// Events recorded for the lock, oldest first.
+    async fn get_history(
+        &self,
+        lock_id: Uuid,
+        read: ReadPreference,
+    ) -> Result<Vec<DomainEvent>, sqlx::Error>;
+
+    /// Save the lock with its quests and events. Returns false, changing nothing, when the
+    /// lock has been deleted.
     async fn save(&self, lock: &Lock) -> Result<bool, sqlx::Error>;
 
+    /// Save every lock in one transaction, or none of them. Returns false, changing nothing,
+    /// when one of the locks could not be saved.
+    async fn save_all(&self, locks: &[Lock]) -> Result<bool, sqlx::Error>;
+
+    /// Deleting and restoring only apply while the lock is still at `lock.version`, they
+    /// return false, changing nothing, once it has moved on.
     async fn delete(&self, lock: &Lock) -> Result<bool, sqlx::Error>;
+
+    /// Fetch a soft deleted lock, for restoring it.
+    async fn get_deleted_by_id(&self, id: Uuid) -> Result<Option<Lock>, sqlx::Error>;
+
+    /// Fetch the user's soft deleted locks that have not been purged yet.
+    async fn get_deleted_by_user_id(&self, user_id: String) -> Result<Vec<Lock>, sqlx::Error>;
+
+    async fn restore(&self, lock: &Lock) -> Result<bool, sqlx::Error>;
+
+    /// Permanently remove locks tombstoned before `deleted_before`, along with their quests
+    /// and shares. Returns the number of locks removed.
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> Result<u64, sqlx::Error>;
 }
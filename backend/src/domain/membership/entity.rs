@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumString;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// What a member may do with a lock they have been invited to, see `application::policy`.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, EnumString, PartialEq, Eq, ToSchema)]
+pub enum MembershipRole {
+    /// Full control, held by the lock's creator. Invitations can't grant it.
+    #[strum(serialize = "OWNER", serialize = "owner")]
+    OWNER,
+    /// An accountability partner, who follows progress but never sees shares or quest data.
+    #[strum(serialize = "VIEWER", serialize = "viewer")]
+    VIEWER,
+}
+
+impl std::fmt::Display for MembershipRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MembershipRole::OWNER => write!(f, "OWNER"),
+            MembershipRole::VIEWER => write!(f, "VIEWER"),
+        }
+    }
+}
+
+/// Another user's access to a lock. The lock's creator owns it outright and has no
+/// membership. A membership grants nothing until the invited user accepts it.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct LockMembership {
+    pub id: Uuid,
+    pub lock_id: Uuid,
+    /// The invited user.
+    pub user_id: String,
+    pub role: MembershipRole,
+    pub invited_by: String,
+    pub created_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+}
+
+impl LockMembership {
+    pub fn invite(
+        lock_id: Uuid,
+        user_id: String,
+        role: MembershipRole,
+        invited_by: String,
+    ) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            lock_id,
+            user_id,
+            role,
+            invited_by,
+            created_at: Utc::now(),
+            accepted_at: None,
+        }
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.accepted_at.is_some()
+    }
+}
@@ -0,0 +1,43 @@
+use super::entity::{LockMembership, MembershipRole};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[async_trait]
+/// Trait representing repository-level operations for lock memberships.
+/// A user has at most one membership, pending or accepted, per lock.
+pub trait LockMembershipRepository: Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<LockMembership>, sqlx::Error>;
+
+    async fn get_by_lock_id(&self, lock_id: Uuid) -> Result<Vec<LockMembership>, sqlx::Error>;
+
+    /// Invitations the user has not accepted yet.
+    async fn get_pending_by_user_id(
+        &self,
+        user_id: String,
+    ) -> Result<Vec<LockMembership>, sqlx::Error>;
+
+    /// All of the user's memberships, pending or accepted, of any lock.
+    async fn get_by_user_id(&self, user_id: String) -> Result<Vec<LockMembership>, sqlx::Error>;
+
+    /// The role of the user's accepted membership of the lock, if they have one.
+    async fn get_role(
+        &self,
+        lock_id: Uuid,
+        user_id: String,
+    ) -> Result<Option<MembershipRole>, sqlx::Error>;
+
+    /// Returns false if the user already has a membership of the lock.
+    async fn save(&self, membership: &LockMembership) -> Result<bool, sqlx::Error>;
+
+    /// Returns false if the membership is not a pending invitation for the user.
+    async fn accept(
+        &self,
+        id: Uuid,
+        user_id: String,
+        accepted_at: DateTime<Utc>,
+    ) -> Result<bool, sqlx::Error>;
+
+    async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error>;
+}
@@ -1,2 +1,9 @@
+pub mod account;
+pub mod audit;
+pub mod auth;
+pub mod event;
+pub mod idempotency;
 pub mod lock;
+pub mod membership;
 pub mod quest;
+pub mod webhook;
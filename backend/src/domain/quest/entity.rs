@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::enums::{QuestStatus, QuestType};
+use crate::domain::event::entity::{DomainEvent, DomainEventKind};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Quest {
@@ -13,6 +14,9 @@ pub struct Quest {
     pub quest_type: QuestType,
     pub status: QuestStatus,
     pub data: HashMap<String, String>,
+    /// Events recorded against the quest, written to the outbox when it is persisted.
+    #[serde(skip)]
+    pub events: Vec<DomainEvent>,
 }
 
 impl Quest {
@@ -30,6 +34,13 @@ impl Quest {
             quest_type,
             status: status.unwrap_or(QuestStatus::PENDING),
             data,
+            events: vec![],
         };
     }
+
+    /// Quests don't know their owner, so it is passed in from the parent lock.
+    pub fn record(&mut self, user_id: String, kind: DomainEventKind) {
+        self.events
+            .push(DomainEvent::create(self.lock_id, user_id, kind));
+    }
 }
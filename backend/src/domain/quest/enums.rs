@@ -1,26 +1,50 @@
 use serde::{Deserialize, Serialize};
 use sqlx::Type;
 use strum_macros::EnumString;
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Deserialize, Serialize, EnumString, Type, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, EnumString, Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "quest_status", rename_all = "lowercase")]
 pub enum QuestStatus {
     #[strum(serialize = "PENDING", serialize = "pending")]
     PENDING,
+    #[strum(serialize = "IN_PROGRESS", serialize = "in_progress")]
+    #[allow(non_camel_case_types)]
+    IN_PROGRESS,
+    #[strum(serialize = "ABANDONED", serialize = "abandoned")]
+    ABANDONED,
     #[strum(serialize = "COMPLETED", serialize = "completed")]
     COMPLETED,
 }
 
+impl QuestStatus {
+    /// Whether the lock's owner may move a quest in this status to `next`.
+    ///
+    /// None of these complete a quest, that is left to the quest's verification and to
+    /// operators, otherwise an owner could collect the shares without doing any quest.
+    pub fn can_transition_to(&self, next: &QuestStatus) -> bool {
+        matches!(
+            (self, next),
+            (QuestStatus::PENDING, QuestStatus::IN_PROGRESS)
+                | (QuestStatus::PENDING, QuestStatus::ABANDONED)
+                | (QuestStatus::IN_PROGRESS, QuestStatus::ABANDONED)
+                | (QuestStatus::ABANDONED, QuestStatus::PENDING)
+        )
+    }
+}
+
 impl std::fmt::Display for QuestStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             QuestStatus::PENDING => write!(f, "PENDING"),
+            QuestStatus::IN_PROGRESS => write!(f, "IN_PROGRESS"),
+            QuestStatus::ABANDONED => write!(f, "ABANDONED"),
             QuestStatus::COMPLETED => write!(f, "COMPLETED"),
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, EnumString, Type, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, EnumString, Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "quest_type", rename_all = "lowercase")]
 pub enum QuestType {
     #[strum(serialize = "GEO", serialize = "geo")]
@@ -33,6 +57,28 @@ pub enum QuestType {
     PAYWALL,
 }
 
+impl QuestType {
+    /// The `data` keys a quest of this type can't be completed without.
+    pub fn required_data_keys(&self) -> &'static [&'static str] {
+        match self {
+            QuestType::GEO => &["location_name", "latitude", "longitude"],
+            QuestType::TIME => &["release_date"],
+            QuestType::FRIEND => &["friend_email"],
+            QuestType::PAYWALL => &["amount"],
+        }
+    }
+
+    /// The `data` keys a quest of this type may carry besides the required ones.
+    pub fn optional_data_keys(&self) -> &'static [&'static str] {
+        match self {
+            QuestType::GEO => &["proximity_range"],
+            QuestType::TIME => &["description"],
+            QuestType::FRIEND => &["friend_name", "message"],
+            QuestType::PAYWALL => &["charity", "purpose"],
+        }
+    }
+}
+
 impl std::fmt::Display for QuestType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
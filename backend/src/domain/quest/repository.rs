@@ -9,7 +9,9 @@ use uuid::Uuid;
 pub trait QuestRepository: Send + Sync {
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Quest>, sqlx::Error>;
 
-    async fn save(&self, quest: &Quest) -> Result<bool, sqlx::Error>;
+    /// Save the quest and move its lock to the next version, only while the lock is still at
+    /// `lock_version`. Returns false, saving nothing, once the lock has moved on.
+    async fn save(&self, quest: &Quest, lock_version: i64) -> Result<bool, sqlx::Error>;
 
     async fn delete(&self, quest: &Quest) -> Result<bool, sqlx::Error>;
 }
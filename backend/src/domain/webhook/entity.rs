@@ -0,0 +1,123 @@
+use base64::{Engine, prelude::BASE64_URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use strum_macros::EnumString;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::event::entity::DomainEvent;
+
+/// Marks a webhook signing secret.
+pub const WEBHOOK_SECRET_PREFIX: &str = "whsec_";
+
+/// A user's request to have the events of their locks POSTed to `url`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub user_id: String,
+    pub url: String,
+    /// Event types to deliver, every type when empty.
+    pub event_types: Vec<String>,
+    /// Key the deliveries are signed with, shared with the receiver.
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    /// A new subscription with a random signing secret.
+    pub fn generate(user_id: String, url: String, event_types: Vec<String>) -> Self {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+
+        Self {
+            id: Uuid::now_v7(),
+            user_id,
+            url,
+            event_types,
+            secret: format!(
+                "{WEBHOOK_SECRET_PREFIX}{}",
+                BASE64_URL_SAFE_NO_PAD.encode(bytes)
+            ),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Hex HMAC-SHA256 of `{timestamp}.{body}` under the secret. The timestamp is signed too,
+    /// so a receiver can reject old deliveries being sent again.
+    pub fn sign(&self, timestamp: i64, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    pub fn wants(&self, event: &DomainEvent) -> bool {
+        self.event_types.is_empty()
+            || self
+                .event_types
+                .iter()
+                .any(|event_type| event_type == event.event_type())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, EnumString, PartialEq, Eq, ToSchema)]
+pub enum DeliveryStatus {
+    /// Waiting for its next attempt.
+    #[strum(serialize = "PENDING", serialize = "pending")]
+    PENDING,
+    #[strum(serialize = "DELIVERED", serialize = "delivered")]
+    DELIVERED,
+    /// Every attempt failed, it is only sent again when the user replays it.
+    #[strum(serialize = "DEAD", serialize = "dead")]
+    DEAD,
+}
+
+impl std::fmt::Display for DeliveryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeliveryStatus::PENDING => write!(f, "PENDING"),
+            DeliveryStatus::DELIVERED => write!(f, "DELIVERED"),
+            DeliveryStatus::DEAD => write!(f, "DEAD"),
+        }
+    }
+}
+
+/// One event to send to one subscription.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub user_id: String,
+    pub event: DomainEvent,
+    pub status: DeliveryStatus,
+    /// Attempts so far, including the current one.
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    /// When the next attempt is due.
+    pub available_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+impl WebhookDelivery {
+    pub fn create(subscription: &WebhookSubscription, event: &DomainEvent) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::now_v7(),
+            subscription_id: subscription.id,
+            user_id: subscription.user_id.clone(),
+            event: event.clone(),
+            status: DeliveryStatus::PENDING,
+            attempts: 0,
+            last_error: None,
+            available_at: now,
+            created_at: now,
+            processed_at: None,
+        }
+    }
+}
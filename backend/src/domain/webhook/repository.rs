@@ -0,0 +1,59 @@
+use super::entity::{WebhookDelivery, WebhookSubscription};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+#[async_trait]
+/// Trait representing repository-level operations for webhook subscriptions and their
+/// deliveries. Deleting a subscription deletes its deliveries.
+pub trait WebhookRepository: Send + Sync {
+    async fn save_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<bool, sqlx::Error>;
+
+    async fn get_subscription(&self, id: Uuid) -> Result<Option<WebhookSubscription>, sqlx::Error>;
+
+    async fn get_subscriptions_by_user_id(
+        &self,
+        user_id: String,
+    ) -> Result<Vec<WebhookSubscription>, sqlx::Error>;
+
+    /// Returns false if the user has no such subscription.
+    async fn delete_subscription(&self, id: Uuid, user_id: String) -> Result<bool, sqlx::Error>;
+
+    /// Queue deliveries. A subscription gets each event once, repeats are ignored since the
+    /// outbox may hand over an event more than once.
+    async fn enqueue_deliveries(&self, deliveries: &[WebhookDelivery]) -> Result<u64, sqlx::Error>;
+
+    /// Claim up to `limit` pending deliveries that are due, hidden from other workers for
+    /// `lease`. Works like `OutboxRepository::claim_batch`.
+    async fn claim_deliveries(
+        &self,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<WebhookDelivery>, sqlx::Error>;
+
+    async fn mark_delivered(&self, id: Uuid) -> Result<bool, sqlx::Error>;
+
+    /// Record a failed attempt. The delivery is retried at `retry_at`, or becomes a dead
+    /// letter when `retry_at` is `None`.
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<bool, sqlx::Error>;
+
+    /// The user's dead letters, newest first.
+    async fn get_dead_letters(&self, user_id: String) -> Result<Vec<WebhookDelivery>, sqlx::Error>;
+
+    /// Queue a dead letter of the user again, starting over from the first attempt.
+    /// Returns `None` if the user has no such dead letter.
+    async fn replay_dead_letter(
+        &self,
+        id: Uuid,
+        user_id: String,
+    ) -> Result<Option<WebhookDelivery>, sqlx::Error>;
+}
@@ -0,0 +1,246 @@
+use async_trait::async_trait;
+use base64::prelude::*;
+use tonic::{Request, Response, Status};
+use validator::Validate;
+
+use crate::{
+    api::{
+        extractors::{WriteLocks, violations},
+        schemas::{
+            requests::{CreateLockRequest, CreateQuestRequest},
+            responses::ErrorDetail,
+        },
+    },
+    application::{
+        dtos::lock::{BatchMode, NewLock, NewQuest},
+        precondition::LockPrecondition,
+    },
+    setup::app_state::AppState,
+};
+
+use super::{
+    authenticate, batch_size, lock_result,
+    proto::{self, lock_service_server::LockService},
+    validation_error,
+};
+
+pub struct LockCommands {
+    state: AppState,
+}
+
+impl LockCommands {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+}
+
+/// The quest as the REST API takes it, so it is validated the same way.
+fn quest_request(quest: &proto::NewQuest) -> CreateQuestRequest {
+    CreateQuestRequest {
+        share: BASE64_STANDARD.encode(&quest.share),
+        quest_type: quest.quest_type.clone(),
+        data: quest.data.clone(),
+    }
+}
+
+fn new_quest(quest: proto::NewQuest) -> NewQuest {
+    // Only called once the share passed validation, which makes sure it is UTF-8.
+    let share = String::from_utf8_lossy(&quest.share).into_owned();
+    (share, quest.quest_type, quest.data)
+}
+
+/// The lock to create, or why it failed validation. `pointer` locates the lock in the request.
+fn new_lock(lock: proto::CreateLockRequest, pointer: &str) -> Result<NewLock, Vec<ErrorDetail>> {
+    let too_large = |field: &str, value: u32| {
+        (value > u8::MAX.into()).then(|| ErrorDetail {
+            field: format!("{pointer}/{field}"),
+            message: format!("{field} can't be larger than {}", u8::MAX),
+        })
+    };
+    let details: Vec<ErrorDetail> = [
+        too_large("total_shares", lock.total_shares),
+        too_large("threshold", lock.threshold),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if !details.is_empty() {
+        return Err(details);
+    }
+
+    let request = CreateLockRequest {
+        label: lock.label,
+        total_shares: lock.total_shares as u8,
+        threshold: lock.threshold as u8,
+        quests: lock.quests.iter().map(quest_request).collect(),
+    };
+    if let Err(errors) = request.validate() {
+        return Err(violations(&errors, pointer));
+    }
+
+    Ok(NewLock {
+        label: request.label,
+        total_shares: request.total_shares,
+        threshold: request.threshold,
+        quests: lock.quests.into_iter().map(new_quest).collect(),
+    })
+}
+
+/// The `If-Match` of a command, from its `if_version`.
+fn precondition(if_version: Option<i64>) -> LockPrecondition {
+    match if_version {
+        Some(version) => LockPrecondition::Versions(vec![version]),
+        None => LockPrecondition::Any,
+    }
+}
+
+#[async_trait]
+impl LockService for LockCommands {
+    async fn create_lock(
+        &self,
+        request: Request<proto::CreateLockRequest>,
+    ) -> Result<Response<proto::Lock>, Status> {
+        let principal = authenticate::<WriteLocks>(&self.state, request.metadata()).await?;
+        let lock = new_lock(request.into_inner(), "").map_err(validation_error)?;
+
+        let lock = self
+            .state
+            .lock_service
+            .create_lock_with_quests(
+                &principal,
+                lock.label,
+                lock.total_shares,
+                lock.threshold,
+                lock.quests,
+            )
+            .await?;
+
+        Ok(Response::new(lock.into()))
+    }
+
+    async fn create_locks(
+        &self,
+        request: Request<proto::CreateLocksRequest>,
+    ) -> Result<Response<proto::CreateLocksResponse>, Status> {
+        let principal = authenticate::<WriteLocks>(&self.state, request.metadata()).await?;
+        let request = request.into_inner();
+        let mode = match request.mode() {
+            proto::BatchMode::Atomic => BatchMode::Atomic,
+            proto::BatchMode::Partial => BatchMode::Partial,
+        };
+        batch_size(
+            request.locks.len(),
+            "/locks",
+            "A batch holds 1 to 100 locks",
+        )?;
+
+        // The items that failed validation, None where a lock is to be created.
+        let mut results = Vec::with_capacity(request.locks.len());
+        let mut new_locks = Vec::new();
+        let mut invalid = Vec::new();
+        for (index, lock) in request.locks.into_iter().enumerate() {
+            match new_lock(lock, &format!("/locks/{index}")) {
+                Ok(lock) => {
+                    new_locks.push(lock);
+                    results.push(None);
+                }
+                Err(details) => {
+                    invalid.extend(details.iter().cloned());
+                    results.push(Some(proto::LockResult {
+                        id: String::new(),
+                        result: Some(proto::lock_result::Result::Error(validation_error(details))),
+                    }));
+                }
+            }
+        }
+
+        if mode == BatchMode::Atomic && !invalid.is_empty() {
+            return Err(validation_error(invalid).into());
+        }
+
+        let mut created = self
+            .state
+            .lock_service
+            .create_locks(&principal, new_locks, mode)
+            .await?
+            .into_iter();
+        let results = results
+            .into_iter()
+            .filter_map(|result| {
+                result.or_else(|| {
+                    created
+                        .next()
+                        .map(|result| lock_result(String::new(), result))
+                })
+            })
+            .collect();
+
+        Ok(Response::new(proto::CreateLocksResponse { results }))
+    }
+
+    async fn plan_quest(
+        &self,
+        request: Request<proto::PlanQuestRequest>,
+    ) -> Result<Response<proto::Lock>, Status> {
+        let principal = authenticate::<WriteLocks>(&self.state, request.metadata()).await?;
+        let request = request.into_inner();
+        let Some(quest) = request.quest else {
+            return Err(validation_error(vec![ErrorDetail {
+                field: "/quest".to_string(),
+                message: "A quest is required".to_string(),
+            }])
+            .into());
+        };
+        if let Err(errors) = quest_request(&quest).validate() {
+            return Err(validation_error(violations(&errors, "/quest")).into());
+        }
+
+        let (share, quest_type, data) = new_quest(quest);
+        let lock = self
+            .state
+            .lock_service
+            .plan_quest(&principal, request.lock_id, share, quest_type, data)
+            .await?;
+
+        Ok(Response::new(lock.into()))
+    }
+
+    async fn delete_lock(
+        &self,
+        request: Request<proto::DeleteLockRequest>,
+    ) -> Result<Response<proto::DeleteLockResponse>, Status> {
+        let principal = authenticate::<WriteLocks>(&self.state, request.metadata()).await?;
+        let request = request.into_inner();
+
+        self.state
+            .lock_service
+            .delete_lock(
+                &principal,
+                request.lock_id,
+                precondition(request.if_version),
+            )
+            .await?;
+
+        Ok(Response::new(proto::DeleteLockResponse {}))
+    }
+
+    async fn restore_lock(
+        &self,
+        request: Request<proto::RestoreLockRequest>,
+    ) -> Result<Response<proto::Lock>, Status> {
+        let principal = authenticate::<WriteLocks>(&self.state, request.metadata()).await?;
+        let request = request.into_inner();
+
+        let lock = self
+            .state
+            .lock_service
+            .restore_lock(
+                &principal,
+                request.lock_id,
+                precondition(request.if_version),
+            )
+            .await?;
+
+        Ok(Response::new(lock.into()))
+    }
+}
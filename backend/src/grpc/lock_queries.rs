@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use tonic::{Request, Response, Status};
+
+use crate::{api::extractors::ReadLocks, setup::app_state::AppState};
+
+use super::{
+    authenticate, batch_size, lock_result,
+    proto::{self, lock_query_service_server::LockQueryService},
+    read_preference,
+};
+
+pub struct LockQueries {
+    state: AppState,
+}
+
+impl LockQueries {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl LockQueryService for LockQueries {
+    async fn get_lock(
+        &self,
+        request: Request<proto::GetLockRequest>,
+    ) -> Result<Response<proto::Lock>, Status> {
+        let principal = authenticate::<ReadLocks>(&self.state, request.metadata()).await?;
+        let read = read_preference(request.metadata());
+
+        let lock = self
+            .state
+            .lock_query_service
+            .get_lock_by_id(&principal, request.into_inner().lock_id, read)
+            .await?;
+
+        Ok(Response::new(lock.into()))
+    }
+
+    async fn get_locks(
+        &self,
+        request: Request<proto::GetLocksRequest>,
+    ) -> Result<Response<proto::GetLocksResponse>, Status> {
+        let principal = authenticate::<ReadLocks>(&self.state, request.metadata()).await?;
+        let read = read_preference(request.metadata());
+        let lock_ids = request.into_inner().lock_ids;
+        batch_size(
+            lock_ids.len(),
+            "/lock_ids",
+            "A batch holds 1 to 100 lock ids",
+        )?;
+
+        let locks = self
+            .state
+            .lock_query_service
+            .get_locks_by_ids(&principal, lock_ids.clone(), read)
+            .await;
+        let results = lock_ids
+            .into_iter()
+            .zip(locks)
+            .map(|(id, result)| lock_result(id, result))
+            .collect();
+
+        Ok(Response::new(proto::GetLocksResponse { results }))
+    }
+
+    async fn list_locks(
+        &self,
+        request: Request<proto::ListLocksRequest>,
+    ) -> Result<Response<proto::ListLocksResponse>, Status> {
+        let principal = authenticate::<ReadLocks>(&self.state, request.metadata()).await?;
+        let read = read_preference(request.metadata());
+
+        let locks = if request.into_inner().shared_only {
+            self.state
+                .lock_query_service
+                .get_shared_locks(&principal, read)
+                .await
+        } else {
+            self.state
+                .lock_query_service
+                .get_locks(&principal, read)
+                .await
+        }?;
+
+        Ok(Response::new(proto::ListLocksResponse {
+            locks: locks.into_iter().map(proto::Lock::from).collect(),
+        }))
+    }
+
+    async fn get_lock_history(
+        &self,
+        request: Request<proto::GetLockHistoryRequest>,
+    ) -> Result<Response<proto::GetLockHistoryResponse>, Status> {
+        let principal = authenticate::<ReadLocks>(&self.state, request.metadata()).await?;
+        let read = read_preference(request.metadata());
+
+        let events = self
+            .state
+            .lock_query_service
+            .get_lock_history(&principal, request.into_inner().lock_id, read)
+            .await?;
+
+        Ok(Response::new(proto::GetLockHistoryResponse {
+            events: events.into_iter().map(Into::into).collect(),
+        }))
+    }
+}
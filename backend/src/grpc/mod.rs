@@ -0,0 +1,285 @@
+//! The lock commands and queries over gRPC, see `proto/quest_lock/v1/locks.proto`. Only built
+//! with the `grpc` feature, and served on `GRPC_PORT` next to the REST API.
+//!
+//! Calls are authenticated like REST requests, with the same bearer tokens and scopes, and go
+//! through the same services, so ownership checks, redaction and validation don't differ
+//! between the two.
+pub mod lock_commands;
+pub mod lock_queries;
+
+use std::{future::Future, net::SocketAddr};
+
+use prost::Message;
+use tonic::{
+    Code, Status,
+    metadata::{MetadataMap, MetadataValue},
+    transport::Server,
+};
+
+use crate::{
+    api::{
+        extractors::RequiredScope, routes::lock_queries::READ_CONSISTENCY_HEADER,
+        schemas::responses::ErrorDetail,
+    },
+    application::{
+        dtos::{lock::LockDTO, quest::QuestDTO},
+        exceptions::{AppError, ErrorCode},
+    },
+    domain::{
+        auth::entity::Principal,
+        event::entity::{DomainEvent, DomainEventKind},
+        lock::repository::ReadPreference,
+    },
+    setup::app_state::AppState,
+};
+
+use self::{
+    lock_commands::LockCommands,
+    lock_queries::LockQueries,
+    proto::{
+        lock_query_service_server::LockQueryServiceServer, lock_service_server::LockServiceServer,
+    },
+};
+
+pub mod proto {
+    tonic::include_proto!("quest_lock.v1");
+}
+
+/// The metadata key a failed call's error code is sent in.
+pub const ERROR_CODE_METADATA: &str = "error-code";
+
+/// The gRPC code for an error, the counterpart of its REST status.
+fn status_code(err: &AppError) -> Code {
+    match err {
+        AppError::ValidationError(..) | AppError::InvalidQuestShare => Code::InvalidArgument,
+        AppError::NotFound(..) | AppError::UserNotFound => Code::NotFound,
+        AppError::DatabaseError(_) | AppError::InternalError | AppError::TokenCreation => {
+            Code::Internal
+        }
+        AppError::Unauthorised(_)
+        | AppError::WrongCredentials
+        | AppError::MissingCredentials
+        | AppError::InvalidToken => Code::Unauthenticated,
+        AppError::Forbidden(..) => Code::PermissionDenied,
+        AppError::Conflict(..) => Code::Aborted,
+        AppError::PreconditionFailed(..) => Code::FailedPrecondition,
+    }
+}
+
+/// The error code as sent to REST clients, e.g. `LOCK_NOT_FOUND`.
+fn code_name(code: ErrorCode) -> String {
+    serde_json::to_value(code)
+        .ok()
+        .and_then(|code| code.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// The error of a batch item, or of a whole call when sent in the status details.
+fn error(
+    code: Code,
+    error_code: ErrorCode,
+    message: String,
+    violations: Vec<ErrorDetail>,
+) -> proto::Error {
+    proto::Error {
+        status: code as i32,
+        code: code_name(error_code),
+        message,
+        violations: violations
+            .into_iter()
+            .map(|detail| proto::FieldViolation {
+                field: detail.field,
+                message: detail.message,
+            })
+            .collect(),
+    }
+}
+
+/// A request that failed validation, each violation naming the field as a JSON pointer.
+fn validation_error(violations: Vec<ErrorDetail>) -> proto::Error {
+    error(
+        Code::InvalidArgument,
+        ErrorCode::ValidationFailed,
+        "The request failed validation".to_string(),
+        violations,
+    )
+}
+
+impl From<AppError> for proto::Error {
+    fn from(err: AppError) -> Self {
+        error(status_code(&err), err.code(), err.to_string(), vec![])
+    }
+}
+
+impl From<proto::Error> for Status {
+    fn from(err: proto::Error) -> Self {
+        let code = Code::from(err.status);
+        let mut status =
+            Status::with_details(code, err.message.clone(), err.encode_to_vec().into());
+        if let Ok(value) = MetadataValue::try_from(err.code.as_str()) {
+            status.metadata_mut().insert(ERROR_CODE_METADATA, value);
+        }
+        status
+    }
+}
+
+impl From<AppError> for Status {
+    fn from(err: AppError) -> Self {
+        proto::Error::from(err).into()
+    }
+}
+
+fn unauthenticated(error_code: ErrorCode, message: &str) -> Status {
+    error(
+        Code::Unauthenticated,
+        error_code,
+        message.to_string(),
+        vec![],
+    )
+    .into()
+}
+
+/// The caller, authenticated from the `authorization: Bearer` metadata like
+/// `api::extractors::AuthenticatedUser`, and holding the scope `S` names.
+async fn authenticate<S: RequiredScope>(
+    state: &AppState,
+    metadata: &MetadataMap,
+) -> Result<Principal, Status> {
+    let header = metadata
+        .get("authorization")
+        .ok_or_else(|| unauthenticated(ErrorCode::Unauthenticated, "Missing bearer token"))?;
+    let token = header
+        .to_str()
+        .ok()
+        .and_then(|header| header.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map(|(_, token)| token.trim())
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| {
+            unauthenticated(
+                ErrorCode::MalformedAuthorization,
+                "Malformed authorization metadata",
+            )
+        })?;
+
+    let principal = state.auth_service.verify(token).await?;
+    if let Some(scope) = S::SCOPE
+        && !principal.allows(scope)
+    {
+        return Err(error(
+            Code::PermissionDenied,
+            ErrorCode::InsufficientScope,
+            format!("Token is missing the '{scope}' scope"),
+            vec![],
+        )
+        .into());
+    }
+
+    Ok(principal)
+}
+
+/// Like `api::routes::lock_queries::read_preference`, from the call's metadata.
+fn read_preference(metadata: &MetadataMap) -> ReadPreference {
+    match metadata
+        .get(READ_CONSISTENCY_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        Some(value) if value.eq_ignore_ascii_case("strong") => ReadPreference::Primary,
+        _ => ReadPreference::Replica,
+    }
+}
+
+/// A violation when a batch holds more or fewer items than it may.
+fn batch_size(len: usize, field: &str, message: &str) -> Result<(), Status> {
+    if (1..=100).contains(&len) {
+        return Ok(());
+    }
+    Err(validation_error(vec![ErrorDetail {
+        field: field.to_string(),
+        message: message.to_string(),
+    }])
+    .into())
+}
+
+/// The result of a batch item that was answered by a service. `id` is the lock the item was
+/// about, if it is known when the item failed.
+fn lock_result(id: String, result: Result<LockDTO, AppError>) -> proto::LockResult {
+    match result {
+        Ok(lock) => proto::LockResult {
+            id: lock.id.clone(),
+            result: Some(proto::lock_result::Result::Lock(lock.into())),
+        },
+        Err(err) => proto::LockResult {
+            id,
+            result: Some(proto::lock_result::Result::Error(err.into())),
+        },
+    }
+}
+
+impl From<LockDTO> for proto::Lock {
+    fn from(lock: LockDTO) -> Self {
+        Self {
+            id: lock.id,
+            label: lock.label,
+            total_shares: lock.total_shares.into(),
+            threshold: lock.threshold.into(),
+            version: lock.version,
+            role: lock.role,
+            quests: lock.quests.into_iter().map(proto::Quest::from).collect(),
+        }
+    }
+}
+
+impl From<QuestDTO> for proto::Quest {
+    fn from(quest: QuestDTO) -> Self {
+        Self {
+            id: quest.id,
+            lock_id: quest.lock_id,
+            share: quest.share.map(String::into_bytes),
+            quest_type: quest.quest_type,
+            status: quest.status,
+            data: quest.data,
+        }
+    }
+}
+
+impl From<DomainEvent> for proto::LockEvent {
+    fn from(event: DomainEvent) -> Self {
+        let mut lock_event = Self {
+            id: event.id.to_string(),
+            r#type: event.event_type().to_string(),
+            lock_id: event.lock_id.to_string(),
+            occurred_at: event.occurred_at.to_rfc3339(),
+            ..Default::default()
+        };
+        match event.kind {
+            DomainEventKind::QuestPlanned {
+                quest_id,
+                quest_type,
+            } => {
+                lock_event.quest_id = Some(quest_id.to_string());
+                lock_event.quest_type = Some(quest_type.to_string());
+            }
+            DomainEventKind::QuestStatusChanged { quest_id, from, to } => {
+                lock_event.quest_id = Some(quest_id.to_string());
+                lock_event.from = Some(from.to_string());
+                lock_event.to = Some(to.to_string());
+            }
+            _ => {}
+        }
+        lock_event
+    }
+}
+
+/// Serve the gRPC services on `addr` until `shutdown` resolves.
+pub async fn serve(
+    state: AppState,
+    addr: SocketAddr,
+    shutdown: impl Future<Output = ()>,
+) -> Result<(), tonic::transport::Error> {
+    Server::builder()
+        .add_service(LockServiceServer::new(LockCommands::new(state.clone())))
+        .add_service(LockQueryServiceServer::new(LockQueries::new(state)))
+        .serve_with_shutdown(addr, shutdown)
+        .await
+}
@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    account::{
+        entity::{AccountErasure, ErasureSummary},
+        repository::AccountRepository as AccountRepositoryInterface,
+    },
+    audit::entity::{AuditRecord, ERASED_TARGET_ID},
+    event::entity::DomainEvent,
+};
+use crate::infrastructure::audit_repository::AuditRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
+
+#[derive(Debug, Clone)]
+pub struct AccountRepository {
+    pool: Pool<Postgres>,
+}
+
+impl AccountRepository {
+    pub fn create(pool: Pool<Postgres>) -> Arc<dyn AccountRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+}
+
+#[async_trait]
+impl AccountRepositoryInterface for AccountRepository {
+    async fn get_history(&self, user_id: String) -> Result<Vec<DomainEvent>, sqlx::Error> {
+        let payloads = sqlx::query_scalar!(
+            r#"SELECT payload as "payload: serde_json::Value"
+            FROM outbox
+            WHERE aggregate_id IN (SELECT id FROM locks WHERE user_id = $1)
+            ORDER BY created_at"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        payloads
+            .into_iter()
+            .map(|payload| {
+                serde_json::from_value(payload).map_err(|e| sqlx::Error::Decode(Box::new(e)))
+            })
+            .collect()
+    }
+
+    async fn get_erasure(&self, user_id: String) -> Result<Option<AccountErasure>, sqlx::Error> {
+        sqlx::query_as!(
+            AccountErasure,
+            r#"SELECT
+                user_id,
+                requested_at as "requested_at: DateTime<Utc>",
+                scheduled_for as "scheduled_for: DateTime<Utc>"
+            FROM account_erasures
+            WHERE user_id = $1"#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn save_erasure(&self, erasure: &AccountErasure) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query!(
+            r#"
+            INSERT INTO account_erasures (
+                user_id, requested_at, scheduled_for
+            ) VALUES (
+                $1, $2, $3
+            )
+            ON CONFLICT (user_id) DO NOTHING
+            "#,
+            erasure.user_id,
+            erasure.requested_at as _,
+            erasure.scheduled_for as _
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn cancel_erasure(&self, user_id: String) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query!(
+            r#"DELETE FROM account_erasures WHERE user_id = $1"#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn get_due_erasures(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<AccountErasure>, sqlx::Error> {
+        sqlx::query_as!(
+            AccountErasure,
+            r#"SELECT
+                user_id,
+                requested_at as "requested_at: DateTime<Utc>",
+                scheduled_for as "scheduled_for: DateTime<Utc>"
+            FROM account_erasures
+            WHERE scheduled_for <= $1
+            ORDER BY scheduled_for
+            LIMIT $2"#,
+            now as _,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn erase(
+        &self,
+        erasure: &AccountErasure,
+        audit: &AuditRecord,
+    ) -> Result<Option<ErasureSummary>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let user_id = &erasure.user_id;
+
+        // Only erase if the request is still there, it may have been cancelled meanwhile.
+        let scheduled = sqlx::query!(
+            r#"DELETE FROM account_erasures WHERE user_id = $1"#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        if scheduled.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        let events = sqlx::query!(
+            r#"DELETE FROM outbox
+            WHERE aggregate_id IN (SELECT id FROM locks WHERE user_id = $1)"#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Quests, shares and the memberships of other users go through ON DELETE CASCADE.
+        let locks = sqlx::query!(r#"DELETE FROM locks WHERE user_id = $1"#, user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let memberships = sqlx::query!(
+            r#"DELETE FROM lock_memberships WHERE user_id = $1"#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let tokens = sqlx::query!(
+            r#"DELETE FROM personal_access_tokens WHERE user_id = $1"#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Stored responses hold lock data, shares included.
+        sqlx::query!(
+            r#"DELETE FROM idempotency_keys WHERE user_id = $1"#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Deliveries go with their subscriptions, they hold the same events.
+        sqlx::query!(
+            r#"DELETE FROM webhook_subscriptions WHERE user_id = $1"#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"UPDATE admin_audit_log SET target_id = $2 WHERE target_id = $1"#,
+            user_id,
+            ERASED_TARGET_ID
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        AuditRepository::insert(&mut tx, audit).await?;
+        tx.commit().await?;
+
+        Ok(Some(ErasureSummary {
+            locks: locks.rows_affected(),
+            memberships: memberships.rows_affected(),
+            personal_access_tokens: tokens.rows_affected(),
+            events: events.rows_affected(),
+        }))
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use crate::domain::audit::{
+    entity::AuditRecord, repository::AuditRepository as AuditRepositoryInterface,
+};
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres, Transaction};
+
+#[derive(Debug, Clone)]
+pub struct AuditRepository {
+    pool: Pool<Postgres>,
+}
+
+impl AuditRepository {
+    pub fn create(pool: Pool<Postgres>) -> Arc<dyn AuditRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+
+    /// Write an audit record as part of the caller's transaction.
+    pub async fn insert(
+        tx: &mut Transaction<'_, Postgres>,
+        record: &AuditRecord,
+    ) -> Result<bool, sqlx::Error> {
+        let details =
+            serde_json::to_value(&record.action).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+
+        let res = sqlx::query!(
+            r#"
+            INSERT INTO admin_audit_log (
+                id, operator, action, target_id, reason, details, created_at
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6, $7
+            )
+            "#,
+            record.id,
+            record.operator,
+            record.action.name(),
+            record.target_id,
+            record.reason,
+            details,
+            record.created_at as _
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl AuditRepositoryInterface for AuditRepository {
+    async fn save(&self, record: &AuditRecord) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let saved = Self::insert(&mut tx, record).await?;
+        tx.commit().await?;
+
+        Ok(saved)
+    }
+}
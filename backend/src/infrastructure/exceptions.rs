@@ -5,4 +5,10 @@ use tracing::error;
 pub enum InfrastructureError {
     #[error("Database Row To Domain Conversion error: {0}")]
     DatabaseRowToDomainConversionError(String),
+    #[error("Auth configuration error: {0}")]
+    AuthConfigurationError(String),
+    #[error("HTTP client error: {0}")]
+    HttpClientError(String),
+    #[error("Metrics error: {0}")]
+    MetricsError(String),
 }
@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use crate::domain::idempotency::{
+    entity::{IdempotencyRecord, StoredResponse},
+    repository::IdempotencyRepository as IdempotencyRepositoryInterface,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
+
+#[derive(Debug)]
+struct IdempotencyKeyRow {
+    user_id: String,
+    idempotency_key: String,
+    fingerprint: String,
+    response_status: Option<i16>,
+    response_content_type: Option<String>,
+    response_headers: Option<serde_json::Value>,
+    response_body: Option<Vec<u8>>,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+impl TryFrom<IdempotencyKeyRow> for IdempotencyRecord {
+    type Error = sqlx::Error;
+
+    fn try_from(row: IdempotencyKeyRow) -> Result<Self, Self::Error> {
+        let response = match (row.response_status, row.response_body) {
+            (Some(status), Some(body)) => Some(StoredResponse {
+                status: status as u16,
+                content_type: row.response_content_type,
+                headers: match row.response_headers {
+                    Some(headers) => serde_json::from_value(headers)
+                        .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+                    None => vec![],
+                },
+                body,
+            }),
+            _ => None,
+        };
+        Ok(Self {
+            user_id: row.user_id,
+            key: row.idempotency_key,
+            fingerprint: row.fingerprint,
+            response,
+            created_at: row.created_at,
+            expires_at: row.expires_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IdempotencyKeyRepository {
+    pool: Pool<Postgres>,
+}
+
+impl IdempotencyKeyRepository {
+    pub fn create(pool: Pool<Postgres>) -> Arc<dyn IdempotencyRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+}
+
+#[async_trait]
+impl IdempotencyRepositoryInterface for IdempotencyKeyRepository {
+    async fn reserve(&self, record: &IdempotencyRecord) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query!(
+            r#"
+            INSERT INTO idempotency_keys (
+                user_id, idempotency_key, fingerprint, created_at, expires_at
+            ) VALUES (
+                $1, $2, $3, $4, $5
+            )
+            ON CONFLICT (user_id, idempotency_key) DO NOTHING
+            "#,
+            record.user_id,
+            record.key,
+            record.fingerprint,
+            record.created_at as _,
+            record.expires_at as _
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn get(
+        &self,
+        user_id: String,
+        key: String,
+    ) -> Result<Option<IdempotencyRecord>, sqlx::Error> {
+        let row = sqlx::query_as!(
+            IdempotencyKeyRow,
+            r#"SELECT
+                user_id,
+                idempotency_key,
+                fingerprint,
+                response_status,
+                response_content_type,
+                response_headers as "response_headers: serde_json::Value",
+                response_body,
+                created_at as "created_at: DateTime<Utc>",
+                expires_at as "expires_at: DateTime<Utc>"
+            FROM idempotency_keys
+            WHERE user_id = $1 AND idempotency_key = $2"#,
+            user_id,
+            key
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(IdempotencyRecord::try_from).transpose()
+    }
+
+    async fn save_response(
+        &self,
+        user_id: String,
+        key: String,
+        response: &StoredResponse,
+    ) -> Result<bool, sqlx::Error> {
+        let headers = serde_json::to_value(&response.headers)
+            .map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+        let res = sqlx::query!(
+            r#"UPDATE idempotency_keys
+            SET response_status = $3, response_content_type = $4, response_headers = $5,
+                response_body = $6
+            WHERE user_id = $1 AND idempotency_key = $2"#,
+            user_id,
+            key,
+            response.status as i16,
+            response.content_type,
+            headers,
+            response.body
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn delete(&self, user_id: String, key: String) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query!(
+            r#"DELETE FROM idempotency_keys WHERE user_id = $1 AND idempotency_key = $2"#,
+            user_id,
+            key
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let res = sqlx::query!(
+            r#"DELETE FROM idempotency_keys WHERE expires_at <= $1"#,
+            now as _
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+}
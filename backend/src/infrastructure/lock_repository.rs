@@ -2,12 +2,16 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::domain::{
-    lock::entity::Lock, lock::repository::LockRepository as LockRepositoryInterface,
+    event::entity::DomainEvent,
+    lock::entity::Lock,
+    lock::repository::{LockRepository as LockRepositoryInterface, ReadPreference},
 };
 use crate::infrastructure::exceptions::InfrastructureError;
-use crate::infrastructure::models::{LockModel, LockWithQuests, QuestModel};
+use crate::infrastructure::models::{LockModel, LockWithQuests, QuestModel, serialize_quest_data};
+use crate::infrastructure::outbox_repository::OutboxRepository;
 use async_trait::async_trait;
-use sqlx::{Pool, Postgres};
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres, Transaction};
 use uuid::Uuid;
 
 #[derive(sqlx::FromRow)]
@@ -17,6 +21,7 @@ struct FlatLockQuestRow {
     lock_label: Option<String>,
     lock_total_shares: i16,
     lock_threshold: i16,
+    lock_version: i64,
 
     quest_id: Option<Uuid>,
     quest_share: Option<String>,
@@ -28,94 +33,170 @@ struct FlatLockQuestRow {
 #[derive(Debug, Clone)]
 pub struct LockRepository {
     pool: Pool<Postgres>,
+    /// Replica for reads that tolerate lag, the primary pool when no replica is configured.
+    read_pool: Pool<Postgres>,
 }
 
 impl LockRepository {
-    pub fn create(pool: Pool<Postgres>) -> Arc<dyn LockRepositoryInterface> {
-        Arc::new(Self { pool })
+    pub fn create(
+        pool: Pool<Postgres>,
+        read_pool: Option<Pool<Postgres>>,
+    ) -> Arc<dyn LockRepositoryInterface> {
+        let read_pool = read_pool.unwrap_or_else(|| pool.clone());
+        Arc::new(Self { pool, read_pool })
     }
 
-    fn serialize_quest_data(
-        data: &HashMap<String, String>,
-    ) -> Result<serde_json::Value, sqlx::Error> {
-        serde_json::to_value(data).map_err(|e| sqlx::Error::Encode(Box::new(e)))
+    fn reader(&self, read: ReadPreference) -> &Pool<Postgres> {
+        match read {
+            ReadPreference::Primary => &self.pool,
+            ReadPreference::Replica => &self.read_pool,
+        }
+    }
+
+    /// Write the lock, its quests and their events within `tx`.
+    async fn save_in(tx: &mut Transaction<'_, Postgres>, lock: &Lock) -> Result<bool, sqlx::Error> {
+        let lock_res = sqlx::query!(
+            r#"
+            INSERT INTO locks (
+                id, user_id, label, total_shares, threshold
+            ) VALUES (
+                $1, $2, $3, $4, $5
+            )
+            ON CONFLICT (id) DO UPDATE SET
+                label = EXCLUDED.label,
+                updated_at = NOW(),
+                version = locks.version + 1
+            WHERE locks.deleted_at IS NULL
+            "#,
+            lock.id,
+            lock.user_id,
+            lock.label,
+            lock.total_shares as i16,
+            lock.threshold as i16
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        for quest in &lock.quests {
+            let data_json = serialize_quest_data(&quest.data)?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO quests (
+                    id, lock_id, share, quest_type, status, data
+                ) VALUES (
+                    $1, $2, $3, $4, $5, $6
+                )
+                ON CONFLICT (id) DO UPDATE SET
+                    status = EXCLUDED.status,
+                    updated_at = NOW()
+                "#,
+                quest.id,
+                quest.lock_id,
+                quest.share,
+                quest.quest_type.to_string(),
+                quest.status.to_string(),
+                data_json
+            )
+            .execute(&mut **tx)
+            .await?;
+
+            OutboxRepository::insert_events(tx, &quest.events).await?;
+        }
+        OutboxRepository::insert_events(tx, &lock.events).await?;
+
+        Ok(lock_res.rows_affected() > 0)
+    }
+
+    async fn with_quests(
+        &self,
+        pool: &Pool<Postgres>,
+        lock_model: LockModel,
+    ) -> Result<Lock, sqlx::Error> {
+        let quest_rows = sqlx::query!(
+            r#"SELECT
+                id,
+                lock_id,
+                share,
+                quest_type,
+                status,
+                data as "data: serde_json::Value"
+            FROM quests
+            WHERE lock_id = $1
+            ORDER BY id"#,
+            lock_model.id()
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut quest_models = Vec::new();
+        for quest_row in quest_rows {
+            let data: HashMap<String, String> = serde_json::from_value(quest_row.data)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+
+            let quest_model = QuestModel::create(
+                quest_row.id,
+                quest_row.lock_id,
+                quest_row.share,
+                quest_row.quest_type,
+                quest_row.status,
+                sqlx::types::Json(data),
+            );
+            quest_models.push(quest_model);
+        }
+
+        let lock_with_quests = LockWithQuests {
+            lock: lock_model,
+            quests: quest_models,
+        };
+
+        Lock::try_from(lock_with_quests).map_err(|conv_err| sqlx::Error::Decode(Box::new(conv_err)))
     }
 }
 
 #[async_trait]
 impl LockRepositoryInterface for LockRepository {
-    async fn get_by_id(&self, id: Uuid) -> Result<Option<Lock>, sqlx::Error> {
+    async fn get_by_id(&self, id: Uuid, read: ReadPreference) -> Result<Option<Lock>, sqlx::Error> {
         let lock_row = sqlx::query!(
             r#"SELECT 
                 id,
                 user_id,
                 label,
                 total_shares,
-                threshold
+                threshold,
+                deleted_at as "deleted_at: DateTime<Utc>",
+                version
             FROM locks
-            WHERE id = $1"#,
+            WHERE id = $1 AND deleted_at IS NULL"#,
             id
         )
-        .fetch_optional(&self.pool)
+        .fetch_optional(self.reader(read))
         .await?;
 
         match lock_row {
             Some(lock_row) => {
-                let quest_rows = sqlx::query!(
-                    r#"SELECT
-                        id,
-                        lock_id,
-                        share,
-                        quest_type,
-                        status,
-                        data as "data: serde_json::Value"
-                    FROM quests
-                    WHERE lock_id = $1
-                    ORDER BY id"#,
-                    id
-                )
-                .fetch_all(&self.pool)
-                .await?;
-
-                let mut quest_models = Vec::new();
-                for quest_row in quest_rows {
-                    let data: HashMap<String, String> = serde_json::from_value(quest_row.data)
-                        .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
-
-                    let quest_model = QuestModel::create(
-                        quest_row.id,
-                        quest_row.lock_id,
-                        quest_row.share,
-                        quest_row.quest_type,
-                        quest_row.status,
-                        sqlx::types::Json(data),
-                    );
-                    quest_models.push(quest_model);
-                }
-
                 let lock_model = LockModel::create(
                     lock_row.id,
                     lock_row.user_id,
                     lock_row.label,
                     lock_row.total_shares,
                     lock_row.threshold,
+                    lock_row.deleted_at,
+                    lock_row.version,
                 );
-
-                let lock_with_quests = LockWithQuests {
-                    lock: lock_model,
-                    quests: quest_models,
-                };
-
-                match Lock::try_from(lock_with_quests) {
-                    Ok(lock) => Ok(Some(lock)),
-                    Err(conv_err) => Err(sqlx::Error::Decode(Box::new(conv_err))),
-                }
+                self.with_quests(self.reader(read), lock_model)
+                    .await
+                    .map(Some)
             }
             None => Ok(None),
         }
     }
 
-    async fn get_by_user_id(&self, user_id: String) -> Result<Vec<Lock>, sqlx::Error> {
+    async fn get_by_user_id(
+        &self,
+        user_id: String,
+        read: ReadPreference,
+    ) -> Result<Vec<Lock>, sqlx::Error> {
         let rows = sqlx::query_as!(
             FlatLockQuestRow,
             r#"
@@ -125,6 +206,7 @@ impl LockRepositoryInterface for LockRepository {
                 l.label as "lock_label",
                 l.total_shares as "lock_total_shares!",
                 l.threshold as "lock_threshold!",
+                l.version as "lock_version!",
                 q.id as "quest_id",
                 q.share as "quest_share",
                 q.quest_type,
@@ -135,13 +217,20 @@ impl LockRepositoryInterface for LockRepository {
             LEFT JOIN 
                 quests q ON l.id = q.lock_id
             WHERE 
-                l.user_id = $1
+                (
+                    l.user_id = $1
+                    OR l.id IN (
+                        SELECT lock_id FROM lock_memberships
+                        WHERE user_id = $1 AND accepted_at IS NOT NULL
+                    )
+                )
+                AND l.deleted_at IS NULL
             ORDER BY 
-                l.id  -- Ordering is important for predictable grouping
+                l.id, q.id  -- Ordering is important for predictable grouping
             "#,
             user_id
         )
-        .fetch_all(&self.pool)
+        .fetch_all(self.reader(read))
         .await?;
 
         let mut locks_with_quests_map: HashMap<Uuid, LockWithQuests> = HashMap::new();
@@ -157,6 +246,8 @@ impl LockRepositoryInterface for LockRepository {
                             row.lock_label.clone(),
                             row.lock_total_shares,
                             row.lock_threshold,
+                            None,
+                            row.lock_version,
                         ),
                         quests: Vec::new(),
                     });
@@ -190,63 +281,172 @@ impl LockRepositoryInterface for LockRepository {
 
         locks_result.map_err(|e| sqlx::Error::Decode(Box::new(e)))
     }
+    async fn get_history(
+        &self,
+        lock_id: Uuid,
+        read: ReadPreference,
+    ) -> Result<Vec<DomainEvent>, sqlx::Error> {
+        let payloads = sqlx::query_scalar!(
+            r#"SELECT payload as "payload: serde_json::Value"
+            FROM outbox
+            WHERE aggregate_id = $1
+            ORDER BY created_at, id"#,
+            lock_id
+        )
+        .fetch_all(self.reader(read))
+        .await?;
+
+        payloads
+            .into_iter()
+            .map(|payload| {
+                serde_json::from_value(payload).map_err(|e| sqlx::Error::Decode(Box::new(e)))
+            })
+            .collect()
+    }
+
     async fn save(&self, lock: &Lock) -> Result<bool, sqlx::Error> {
         let mut tx = self.pool.begin().await?;
+        if !Self::save_in(&mut tx, lock).await? {
+            // Dropping the transaction rolls back the quests and events written with it.
+            return Ok(false);
+        }
+        tx.commit().await?;
 
-        let lock_res = sqlx::query!(
-            r#"
-            INSERT INTO locks (
-                id, user_id, label, total_shares, threshold
-            ) VALUES (
-                $1, $2, $3, $4, $5
-            )
-            ON CONFLICT (id) DO UPDATE SET
-                label = EXCLUDED.label,
-                updated_at = NOW()
-            "#,
+        Ok(true)
+    }
+
+    async fn save_all(&self, locks: &[Lock]) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        for lock in locks {
+            if !Self::save_in(&mut tx, lock).await? {
+                // Dropping the transaction rolls back the locks saved so far.
+                return Ok(false);
+            }
+        }
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn delete(&self, lock: &Lock) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let res = sqlx::query!(
+            r#"UPDATE locks SET deleted_at = NOW(), updated_at = NOW(), version = version + 1
+            WHERE id = $1 AND deleted_at IS NULL AND version = $2"#,
             lock.id,
-            lock.user_id,
-            lock.label,
-            lock.total_shares as i16,
-            lock.threshold as i16
+            lock.version
         )
         .execute(&mut *tx)
         .await?;
+        if res.rows_affected() == 0 {
+            return Ok(false);
+        }
 
-        for quest in &lock.quests {
-            let data_json = Self::serialize_quest_data(&quest.data)?;
+        OutboxRepository::insert_events(&mut tx, &lock.events).await?;
+        tx.commit().await?;
 
-            sqlx::query!(
-                r#"
-                INSERT INTO quests (
-                    id, lock_id, share, quest_type, status, data
-                ) VALUES (
-                    $1, $2, $3, $4, $5, $6
-                )
-                ON CONFLICT (id) DO UPDATE SET
-                    status = EXCLUDED.status,
-                    updated_at = NOW()
-                "#,
-                quest.id,
-                quest.lock_id,
-                quest.share,
-                quest.quest_type.to_string(),
-                quest.status.to_string(),
-                data_json
-            )
-            .execute(&mut *tx)
-            .await?;
+        Ok(true)
+    }
+
+    async fn get_deleted_by_id(&self, id: Uuid) -> Result<Option<Lock>, sqlx::Error> {
+        let lock_row = sqlx::query!(
+            r#"SELECT
+                id,
+                user_id,
+                label,
+                total_shares,
+                threshold,
+                deleted_at as "deleted_at: DateTime<Utc>",
+                version
+            FROM locks
+            WHERE id = $1 AND deleted_at IS NOT NULL"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match lock_row {
+            Some(lock_row) => {
+                let lock_model = LockModel::create(
+                    lock_row.id,
+                    lock_row.user_id,
+                    lock_row.label,
+                    lock_row.total_shares,
+                    lock_row.threshold,
+                    lock_row.deleted_at,
+                    lock_row.version,
+                );
+                self.with_quests(&self.pool, lock_model).await.map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn get_deleted_by_user_id(&self, user_id: String) -> Result<Vec<Lock>, sqlx::Error> {
+        let lock_rows = sqlx::query!(
+            r#"SELECT
+                id,
+                user_id,
+                label,
+                total_shares,
+                threshold,
+                deleted_at as "deleted_at: DateTime<Utc>",
+                version
+            FROM locks
+            WHERE user_id = $1 AND deleted_at IS NOT NULL
+            ORDER BY deleted_at"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut locks = Vec::new();
+        for lock_row in lock_rows {
+            let lock_model = LockModel::create(
+                lock_row.id,
+                lock_row.user_id,
+                lock_row.label,
+                lock_row.total_shares,
+                lock_row.threshold,
+                lock_row.deleted_at,
+                lock_row.version,
+            );
+            locks.push(self.with_quests(&self.pool, lock_model).await?);
         }
+        Ok(locks)
+    }
+
+    async fn restore(&self, lock: &Lock) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let res = sqlx::query!(
+            r#"UPDATE locks SET deleted_at = NULL, updated_at = NOW(), version = version + 1
+            WHERE id = $1 AND deleted_at IS NOT NULL AND version = $2"#,
+            lock.id,
+            lock.version
+        )
+        .execute(&mut *tx)
+        .await?;
+        if res.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        OutboxRepository::insert_events(&mut tx, &lock.events).await?;
         tx.commit().await?;
 
-        Ok(lock_res.rows_affected() > 0)
+        Ok(true)
     }
 
-    async fn delete(&self, lock: &Lock) -> Result<bool, sqlx::Error> {
-        let res = sqlx::query!(r#"DELETE FROM locks WHERE id = $1"#, lock.id)
-            .execute(&self.pool)
-            .await?;
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        // Quests, and with them the shares, go through ON DELETE CASCADE.
+        let res = sqlx::query!(
+            r#"DELETE FROM locks WHERE deleted_at IS NOT NULL AND deleted_at < $1"#,
+            deleted_before as _
+        )
+        .execute(&self.pool)
+        .await?;
 
-        Ok(res.rows_affected() > 0)
+        Ok(res.rows_affected())
     }
 }
@@ -1,4 +1,23 @@
+#[cfg(feature = "postgres")]
+pub mod account_repository;
+#[cfg(feature = "postgres")]
+pub mod audit_repository;
 pub mod exceptions;
+#[cfg(feature = "postgres")]
+pub mod idempotency_key_repository;
+#[cfg(feature = "postgres")]
+pub mod lock_membership_repository;
+#[cfg(feature = "postgres")]
 pub mod lock_repository;
 pub mod models;
+#[cfg(feature = "postgres")]
+pub mod outbox_repository;
+#[cfg(feature = "postgres")]
+pub mod personal_access_token_repository;
+#[cfg(feature = "postgres")]
+pub mod quest_repository;
 pub mod services;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "postgres")]
+pub mod webhook_repository;
@@ -4,10 +4,18 @@ use crate::domain::{
     quest::enums::{QuestStatus, QuestType},
 };
 use crate::infrastructure::exceptions::InfrastructureError;
+use chrono::{DateTime, Utc};
 use sqlx::{FromRow, types::Json};
 use std::{collections::HashMap, str::FromStr};
 use uuid::Uuid;
 
+/// Serialise a quest's data map for storage in the `quests.data` jsonb column.
+pub fn serialize_quest_data(
+    data: &HashMap<String, String>,
+) -> Result<serde_json::Value, sqlx::Error> {
+    serde_json::to_value(data).map_err(|e| sqlx::Error::Encode(Box::new(e)))
+}
+
 #[derive(FromRow, Debug)]
 pub struct LockModel {
     id: Uuid,
@@ -15,6 +23,8 @@ pub struct LockModel {
     label: Option<String>,
     total_shares: i16,
     threshold: i16,
+    deleted_at: Option<DateTime<Utc>>,
+    version: i64,
 }
 
 impl LockModel {
@@ -24,6 +34,8 @@ impl LockModel {
         label: Option<String>,
         total_shares: i16,
         threshold: i16,
+        deleted_at: Option<DateTime<Utc>>,
+        version: i64,
     ) -> Self {
         Self {
             id,
@@ -31,8 +43,14 @@ impl LockModel {
             label,
             total_shares,
             threshold,
+            deleted_at,
+            version,
         }
     }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
 }
 
 impl From<Lock> for LockModel {
@@ -43,6 +61,8 @@ impl From<Lock> for LockModel {
             label: lock.label,
             total_shares: lock.total_shares as i16,
             threshold: lock.threshold as i16,
+            deleted_at: lock.deleted_at,
+            version: lock.version,
         }
     }
 }
@@ -111,6 +131,7 @@ impl TryFrom<QuestModel> for Quest {
                 ))
             })?,
             data: row.data.0,
+            events: vec![],
         })
     }
 }
@@ -135,6 +156,9 @@ impl TryFrom<LockWithQuests> for Lock {
             total_shares: data.lock.total_shares as u8,
             threshold: data.lock.threshold as u8,
             quests: quests?,
+            deleted_at: data.lock.deleted_at,
+            version: data.lock.version,
+            events: vec![],
         })
     }
 }
@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use crate::domain::event::{
+    entity::{DomainEvent, OutboxMessage},
+    repository::OutboxRepository as OutboxRepositoryInterface,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{Pool, Postgres, Transaction};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct OutboxRepository {
+    pool: Pool<Postgres>,
+}
+
+impl OutboxRepository {
+    pub fn create(pool: Pool<Postgres>) -> Arc<dyn OutboxRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+
+    /// Write events to the outbox as part of the caller's transaction.
+    pub async fn insert_events(
+        tx: &mut Transaction<'_, Postgres>,
+        events: &[DomainEvent],
+    ) -> Result<(), sqlx::Error> {
+        for event in events {
+            let payload =
+                serde_json::to_value(event).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO outbox (
+                    id, event_type, aggregate_id, payload
+                ) VALUES (
+                    $1, $2, $3, $4
+                )
+                ON CONFLICT (id) DO NOTHING
+                "#,
+                event.id,
+                event.event_type(),
+                event.lock_id,
+                payload
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OutboxRepositoryInterface for OutboxRepository {
+    async fn claim_batch(
+        &self,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<OutboxMessage>, sqlx::Error> {
+        let lease_until = Utc::now() + lease;
+        let rows = sqlx::query!(
+            r#"
+            UPDATE outbox SET
+                attempts = attempts + 1,
+                available_at = $2
+            WHERE id IN (
+                SELECT id FROM outbox
+                WHERE status = 'pending' AND available_at <= NOW()
+                ORDER BY created_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, payload as "payload: serde_json::Value", attempts, handled_by
+            "#,
+            limit,
+            lease_until as _
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut messages = Vec::new();
+        for row in rows {
+            let event: DomainEvent = serde_json::from_value(row.payload)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+            messages.push(OutboxMessage {
+                id: row.id,
+                event,
+                attempts: row.attempts,
+                handled_by: row.handled_by,
+            });
+        }
+        // Event ids are v7 uuids, so this restores the order they were recorded in.
+        messages.sort_by_key(|message| message.id);
+
+        Ok(messages)
+    }
+
+    async fn mark_done(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query!(
+            r#"UPDATE outbox SET status = 'done', last_error = NULL, processed_at = NOW()
+            WHERE id = $1"#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        handled_by: &[String],
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<bool, sqlx::Error> {
+        let res = match retry_at {
+            Some(retry_at) => {
+                sqlx::query!(
+                    r#"UPDATE outbox SET last_error = $2, handled_by = $3, available_at = $4
+                    WHERE id = $1"#,
+                    id,
+                    error,
+                    handled_by,
+                    retry_at as _
+                )
+                .execute(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query!(
+                    r#"UPDATE outbox SET status = 'failed', last_error = $2, handled_by = $3,
+                        processed_at = NOW()
+                    WHERE id = $1"#,
+                    id,
+                    error,
+                    handled_by
+                )
+                .execute(&self.pool)
+                .await?
+            }
+        };
+
+        Ok(res.rows_affected() > 0)
+    }
+}
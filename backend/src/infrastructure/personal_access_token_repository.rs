@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use crate::domain::auth::{
+    entity::PersonalAccessToken,
+    repository::PersonalAccessTokenRepository as PersonalAccessTokenRepositoryInterface,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Debug)]
+struct PersonalAccessTokenRow {
+    id: Uuid,
+    user_id: String,
+    name: String,
+    token_hash: String,
+    scopes: Vec<String>,
+    expires_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    last_used_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<PersonalAccessTokenRow> for PersonalAccessToken {
+    fn from(row: PersonalAccessTokenRow) -> Self {
+        Self {
+            id: row.id,
+            user_id: row.user_id,
+            name: row.name,
+            token_hash: row.token_hash,
+            scopes: row.scopes,
+            expires_at: row.expires_at,
+            created_at: row.created_at,
+            last_used_at: row.last_used_at,
+            revoked_at: row.revoked_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PersonalAccessTokenRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PersonalAccessTokenRepository {
+    pub fn create(pool: Pool<Postgres>) -> Arc<dyn PersonalAccessTokenRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+}
+
+#[async_trait]
+impl PersonalAccessTokenRepositoryInterface for PersonalAccessTokenRepository {
+    async fn get_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PersonalAccessToken>, sqlx::Error> {
+        let row = sqlx::query_as!(
+            PersonalAccessTokenRow,
+            r#"SELECT
+                id,
+                user_id,
+                name,
+                token_hash,
+                scopes,
+                expires_at as "expires_at: DateTime<Utc>",
+                created_at as "created_at: DateTime<Utc>",
+                last_used_at as "last_used_at: DateTime<Utc>",
+                revoked_at as "revoked_at: DateTime<Utc>"
+            FROM personal_access_tokens
+            WHERE token_hash = $1"#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(PersonalAccessToken::from))
+    }
+
+    async fn get_by_user_id(
+        &self,
+        user_id: String,
+    ) -> Result<Vec<PersonalAccessToken>, sqlx::Error> {
+        let rows = sqlx::query_as!(
+            PersonalAccessTokenRow,
+            r#"SELECT
+                id,
+                user_id,
+                name,
+                token_hash,
+                scopes,
+                expires_at as "expires_at: DateTime<Utc>",
+                created_at as "created_at: DateTime<Utc>",
+                last_used_at as "last_used_at: DateTime<Utc>",
+                revoked_at as "revoked_at: DateTime<Utc>"
+            FROM personal_access_tokens
+            WHERE user_id = $1
+            ORDER BY created_at DESC"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(PersonalAccessToken::from).collect())
+    }
+
+    async fn save(&self, token: &PersonalAccessToken) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query!(
+            r#"
+            INSERT INTO personal_access_tokens (
+                id, user_id, name, token_hash, scopes, expires_at, created_at
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6, $7
+            )
+            "#,
+            token.id,
+            token.user_id,
+            token.name,
+            token.token_hash,
+            &token.scopes,
+            token.expires_at as _,
+            token.created_at as _
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn revoke(&self, id: Uuid, user_id: String) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query!(
+            r#"UPDATE personal_access_tokens SET revoked_at = NOW()
+            WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL"#,
+            id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn touch(&self, id: Uuid, used_at: DateTime<Utc>) -> Result<bool, sqlx::Error> {
+        // Scripts can call in a tight loop, a minute's precision is plenty.
+        let res = sqlx::query!(
+            r#"UPDATE personal_access_tokens SET last_used_at = $2
+            WHERE id = $1
+                AND (last_used_at IS NULL OR last_used_at < $2::timestamptz - INTERVAL '1 minute')"#,
+            id,
+            used_at as _
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::quest::{
+    entity::Quest, repository::QuestRepository as QuestRepositoryInterface,
+};
+use crate::infrastructure::models::{QuestModel, serialize_quest_data};
+use crate::infrastructure::outbox_repository::OutboxRepository;
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct QuestRepository {
+    pool: Pool<Postgres>,
+}
+
+impl QuestRepository {
+    pub fn create(pool: Pool<Postgres>) -> Arc<dyn QuestRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+}
+
+#[async_trait]
+impl QuestRepositoryInterface for QuestRepository {
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Quest>, sqlx::Error> {
+        let quest_row = sqlx::query!(
+            r#"SELECT
+                q.id,
+                q.lock_id,
+                q.share,
+                q.quest_type,
+                q.status,
+                q.data as "data: serde_json::Value"
+            FROM quests q
+            JOIN locks l ON l.id = q.lock_id
+            WHERE q.id = $1 AND l.deleted_at IS NULL"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match quest_row {
+            Some(quest_row) => {
+                let data: HashMap<String, String> = serde_json::from_value(quest_row.data)
+                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+
+                let quest_model = QuestModel::create(
+                    quest_row.id,
+                    quest_row.lock_id,
+                    quest_row.share,
+                    quest_row.quest_type,
+                    quest_row.status,
+                    sqlx::types::Json(data),
+                );
+
+                match Quest::try_from(quest_model) {
+                    Ok(quest) => Ok(Some(quest)),
+                    Err(conv_err) => Err(sqlx::Error::Decode(Box::new(conv_err))),
+                }
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn save(&self, quest: &Quest, lock_version: i64) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let data_json = serialize_quest_data(&quest.data)?;
+
+        let quest_res = sqlx::query!(
+            r#"
+            INSERT INTO quests (
+                id, lock_id, share, quest_type, status, data
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6
+            )
+            ON CONFLICT (id) DO UPDATE SET
+                status = EXCLUDED.status,
+                updated_at = NOW()
+            "#,
+            quest.id,
+            quest.lock_id,
+            quest.share,
+            quest.quest_type.to_string(),
+            quest.status.to_string(),
+            data_json
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // A quest change is a change to its lock, move the parent to its next version.
+        let lock_res = sqlx::query!(
+            r#"UPDATE locks SET updated_at = NOW(), version = version + 1
+            WHERE id = $1 AND version = $2"#,
+            quest.lock_id,
+            lock_version
+        )
+        .execute(&mut *tx)
+        .await?;
+        if lock_res.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        OutboxRepository::insert_events(&mut tx, &quest.events).await?;
+        tx.commit().await?;
+
+        Ok(quest_res.rows_affected() > 0)
+    }
+
+    async fn delete(&self, quest: &Quest) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query!(r#"DELETE FROM quests WHERE id = $1"#, quest.id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,219 @@
+// TODO move to application layer at some point
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use tracing::info;
+
+use crate::{
+    application::{
+        dtos::account::{AccountErasureDTO, AccountExportDTO, ExportedLockDTO},
+        exceptions::{AppError, ErrorCode},
+        services::account_service::{AccountServiceTrait, ERASURE_CONFIRMATION},
+    },
+    domain::{
+        account::{
+            entity::AccountErasure, repository::AccountRepository as AccountRepositoryInterface,
+        },
+        audit::entity::{AuditAction, AuditRecord, ERASED_TARGET_ID, SYSTEM_OPERATOR},
+        auth::{
+            entity::Principal,
+            repository::PersonalAccessTokenRepository as PersonalAccessTokenRepositoryInterface,
+        },
+        lock::repository::{LockRepository as LockRepositoryInterface, ReadPreference},
+        membership::repository::LockMembershipRepository as LockMembershipRepositoryInterface,
+        webhook::repository::WebhookRepository as WebhookRepositoryInterface,
+    },
+};
+
+/// Most accounts erased per run of the erasure job.
+const ERASURE_BATCH_SIZE: i64 = 50;
+
+pub struct AccountService {
+    pub repo: Arc<dyn AccountRepositoryInterface>,
+    pub lock_repo: Arc<dyn LockRepositoryInterface>,
+    pub membership_repo: Arc<dyn LockMembershipRepositoryInterface>,
+    pub token_repo: Arc<dyn PersonalAccessTokenRepositoryInterface>,
+    pub webhook_repo: Arc<dyn WebhookRepositoryInterface>,
+    /// How long after the request an account is erased, the user can cancel until then.
+    pub grace_period: Duration,
+}
+
+impl AccountService {
+    pub fn create(
+        account_repo: Arc<dyn AccountRepositoryInterface>,
+        lock_repo: Arc<dyn LockRepositoryInterface>,
+        membership_repo: Arc<dyn LockMembershipRepositoryInterface>,
+        token_repo: Arc<dyn PersonalAccessTokenRepositoryInterface>,
+        webhook_repo: Arc<dyn WebhookRepositoryInterface>,
+        grace_period: Duration,
+    ) -> Arc<dyn AccountServiceTrait> {
+        Arc::new(Self {
+            repo: account_repo,
+            lock_repo,
+            membership_repo,
+            token_repo,
+            webhook_repo,
+            grace_period,
+        })
+    }
+}
+
+#[async_trait]
+impl AccountServiceTrait for AccountService {
+    async fn export(&self, principal: &Principal) -> Result<AccountExportDTO, AppError> {
+        let user_id = principal.subject.clone();
+        info!("Export account - user_id: {}", user_id);
+
+        // Locks shared with the user belong to someone else, they show up as memberships.
+        let mut locks: Vec<_> = self
+            .lock_repo
+            .get_by_user_id(user_id.clone(), ReadPreference::Primary)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .into_iter()
+            .filter(|lock| lock.user_id == user_id)
+            .collect();
+        let deleted = self
+            .lock_repo
+            .get_deleted_by_user_id(user_id.clone())
+            .await
+            .map_err(AppError::DatabaseError)?;
+        locks.extend(deleted);
+
+        let memberships = self
+            .membership_repo
+            .get_by_user_id(user_id.clone())
+            .await
+            .map_err(AppError::DatabaseError)?;
+        let tokens = self
+            .token_repo
+            .get_by_user_id(user_id.clone())
+            .await
+            .map_err(AppError::DatabaseError)?;
+        let webhooks = self
+            .webhook_repo
+            .get_subscriptions_by_user_id(user_id.clone())
+            .await
+            .map_err(AppError::DatabaseError)?;
+        let history = self
+            .repo
+            .get_history(user_id.clone())
+            .await
+            .map_err(AppError::DatabaseError)?;
+        let erasure = self
+            .repo
+            .get_erasure(user_id.clone())
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(AccountExportDTO {
+            user_id,
+            exported_at: Utc::now(),
+            locks: locks.into_iter().map(ExportedLockDTO::from).collect(),
+            memberships: memberships.into_iter().map(Into::into).collect(),
+            personal_access_tokens: tokens.into_iter().map(Into::into).collect(),
+            webhooks: webhooks.into_iter().map(Into::into).collect(),
+            history,
+            erasure: erasure.map(AccountErasureDTO::from),
+        })
+    }
+
+    async fn request_erasure(
+        &self,
+        principal: &Principal,
+        confirmation: String,
+    ) -> Result<AccountErasureDTO, AppError> {
+        if confirmation.trim() != ERASURE_CONFIRMATION {
+            return Err(AppError::ValidationError(
+                ErrorCode::ConfirmationRequired,
+                format!("Confirm the erasure by sending '{ERASURE_CONFIRMATION}'"),
+            ));
+        }
+
+        let erasure = AccountErasure::schedule(principal.subject.clone(), self.grace_period);
+        info!(
+            "Request account erasure - user_id: {}, scheduled_for: {}",
+            principal.subject, erasure.scheduled_for
+        );
+
+        let saved = self
+            .repo
+            .save_erasure(&erasure)
+            .await
+            .map_err(AppError::DatabaseError)?;
+        if !saved {
+            return Err(AppError::ValidationError(
+                ErrorCode::ErasureAlreadyScheduled,
+                "Account erasure is already scheduled".to_string(),
+            ));
+        }
+
+        Ok(AccountErasureDTO::from(erasure))
+    }
+
+    async fn get_erasure(&self, principal: &Principal) -> Result<AccountErasureDTO, AppError> {
+        self.repo
+            .get_erasure(principal.subject.clone())
+            .await
+            .map_err(AppError::DatabaseError)?
+            .map(AccountErasureDTO::from)
+            .ok_or_else(|| {
+                AppError::NotFound(
+                    ErrorCode::ErasureNotFound,
+                    "No account erasure is scheduled".to_string(),
+                )
+            })
+    }
+
+    async fn cancel_erasure(&self, principal: &Principal) -> Result<(), AppError> {
+        info!("Cancel account erasure - user_id: {}", principal.subject);
+
+        let cancelled = self
+            .repo
+            .cancel_erasure(principal.subject.clone())
+            .await
+            .map_err(AppError::DatabaseError)?;
+        if !cancelled {
+            return Err(AppError::NotFound(
+                ErrorCode::ErasureNotFound,
+                "No account erasure is scheduled".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn erase_due_accounts(&self) -> Result<u64, AppError> {
+        let due = self
+            .repo
+            .get_due_erasures(Utc::now(), ERASURE_BATCH_SIZE)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        let mut erased = 0;
+        for erasure in due {
+            // The record must not identify the user.
+            let audit = AuditRecord::create(
+                SYSTEM_OPERATOR.to_string(),
+                ERASED_TARGET_ID.to_string(),
+                None,
+                AuditAction::AccountErased,
+            );
+            let Some(summary) = self
+                .repo
+                .erase(&erasure, &audit)
+                .await
+                .map_err(AppError::DatabaseError)?
+            else {
+                continue;
+            };
+
+            info!(
+                "Erased account - locks: {}, memberships: {}, tokens: {}, events: {}",
+                summary.locks, summary.memberships, summary.personal_access_tokens, summary.events
+            );
+            erased += 1;
+        }
+        Ok(erased)
+    }
+}
@@ -0,0 +1,281 @@
+// TODO move to application layer at some point
+use std::{str::FromStr, sync::Arc};
+
+use async_trait::async_trait;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        dtos::admin::{AdminLockDTO, AdminQuestDTO},
+        exceptions::{AppError, ErrorCode},
+        policy::authorize_admin,
+        precondition::LockPrecondition,
+        services::{
+            admin_service::AdminServiceTrait, event_stream_service::EventStreamServiceTrait,
+            metrics_service::MetricsServiceTrait,
+        },
+    },
+    domain::{
+        audit::{
+            entity::{AuditAction, AuditRecord},
+            repository::AuditRepository as AuditRepositoryInterface,
+        },
+        auth::entity::Principal,
+        event::entity::DomainEventKind,
+        lock::repository::{LockRepository as LockRepositoryInterface, ReadPreference},
+        quest::{enums::QuestStatus, repository::QuestRepository as QuestRepositoryInterface},
+    },
+};
+
+pub struct AdminService {
+    pub lock_repo: Arc<dyn LockRepositoryInterface + Send + Sync>,
+    pub quest_repo: Arc<dyn QuestRepositoryInterface + Send + Sync>,
+    pub audit_repo: Arc<dyn AuditRepositoryInterface + Send + Sync>,
+    /// Where the events of saved changes are published for live clients.
+    pub events: Arc<dyn EventStreamServiceTrait>,
+    pub metrics: Arc<dyn MetricsServiceTrait>,
+}
+
+impl AdminService {
+    pub fn create(
+        lock_repo: Arc<dyn LockRepositoryInterface>,
+        quest_repo: Arc<dyn QuestRepositoryInterface>,
+        audit_repo: Arc<dyn AuditRepositoryInterface>,
+        events: Arc<dyn EventStreamServiceTrait>,
+        metrics: Arc<dyn MetricsServiceTrait>,
+    ) -> Arc<dyn AdminServiceTrait> {
+        Arc::new(Self {
+            lock_repo,
+            quest_repo,
+            audit_repo,
+            events,
+            metrics,
+        })
+    }
+
+    fn _parse_id(&self, id: &str) -> Result<Uuid, AppError> {
+        match Uuid::try_parse(id) {
+            Ok(id) => Ok(id),
+            Err(_) => Err(AppError::ValidationError(
+                ErrorCode::InvalidId,
+                id.to_string(),
+            )),
+        }
+    }
+
+    fn _parse_status(&self, status: &str) -> Result<QuestStatus, AppError> {
+        match QuestStatus::from_str(status) {
+            Ok(status) => Ok(status),
+            Err(err) => Err(AppError::ValidationError(
+                ErrorCode::InvalidQuestStatus,
+                err.to_string(),
+            )),
+        }
+    }
+
+    /// Write the audit record before the action runs, so nothing an operator does can go
+    /// unrecorded. An action that then fails leaves a record of the attempt.
+    async fn _audit(
+        &self,
+        operator: &Principal,
+        target_id: String,
+        reason: Option<String>,
+        action: AuditAction,
+    ) -> Result<(), AppError> {
+        authorize_admin(operator)?;
+
+        info!(
+            "Admin action - operator: {}, action: {}, target_id: {target_id}",
+            operator.subject,
+            action.name()
+        );
+        let record = AuditRecord::create(operator.subject.clone(), target_id, reason, action);
+        if let Err(err) = self.audit_repo.save(&record).await {
+            tracing::error!("Error writing audit record: {err}");
+            return Err(AppError::DatabaseError(err));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AdminServiceTrait for AdminService {
+    async fn get_locks_by_user(
+        &self,
+        operator: &Principal,
+        user_id: String,
+    ) -> Result<Vec<AdminLockDTO>, AppError> {
+        self._audit(operator, user_id.clone(), None, AuditAction::LocksLookedUp)
+            .await?;
+
+        let mut locks = self
+            .lock_repo
+            .get_by_user_id(user_id.clone(), ReadPreference::Primary)
+            .await
+            .map_err(AppError::DatabaseError)?;
+        let deleted = self
+            .lock_repo
+            .get_deleted_by_user_id(user_id)
+            .await
+            .map_err(AppError::DatabaseError)?;
+        locks.extend(deleted);
+
+        Ok(locks.into_iter().map(AdminLockDTO::from).collect())
+    }
+
+    async fn get_quest(
+        &self,
+        operator: &Principal,
+        quest_id: String,
+    ) -> Result<AdminQuestDTO, AppError> {
+        let parsed_quest_id = self._parse_id(&quest_id)?;
+        self._audit(operator, quest_id, None, AuditAction::QuestInspected)
+            .await?;
+
+        let quest = self
+            .quest_repo
+            .get_by_id(parsed_quest_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| {
+                AppError::NotFound(ErrorCode::QuestNotFound, "Quest not found".to_string())
+            })?;
+
+        Ok(AdminQuestDTO::from(quest))
+    }
+
+    async fn force_quest_status(
+        &self,
+        operator: &Principal,
+        quest_id: String,
+        status: String,
+        reason: String,
+    ) -> Result<AdminQuestDTO, AppError> {
+        let reason = reason.trim().to_string();
+        if reason.is_empty() {
+            return Err(AppError::ValidationError(
+                ErrorCode::ReasonRequired,
+                "A reason is required to force a quest status".to_string(),
+            ));
+        }
+        let status = self._parse_status(&status)?;
+        let parsed_quest_id = self._parse_id(&quest_id)?;
+
+        let mut quest = self
+            .quest_repo
+            .get_by_id(parsed_quest_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| {
+                AppError::NotFound(ErrorCode::QuestNotFound, "Quest not found".to_string())
+            })?;
+        let mut lock = self
+            .lock_repo
+            .get_by_id(quest.lock_id, ReadPreference::Primary)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| {
+                AppError::NotFound(ErrorCode::LockNotFound, "Lock not found".to_string())
+            })?;
+
+        if quest.status == status {
+            return Err(AppError::ValidationError(
+                ErrorCode::InvalidStatusTransition,
+                format!("Quest is already {status}"),
+            ));
+        }
+
+        self._audit(
+            operator,
+            quest_id,
+            Some(reason),
+            AuditAction::QuestStatusForced {
+                from: quest.status.clone(),
+                to: status.clone(),
+            },
+        )
+        .await?;
+
+        quest.record(
+            lock.user_id.clone(),
+            DomainEventKind::QuestStatusChanged {
+                quest_id: quest.id,
+                from: quest.status.clone(),
+                to: status.clone(),
+            },
+        );
+        quest.status = status;
+
+        let was_unlockable = lock.is_unlockable();
+        if let Some(lock_quest) = lock.quests.iter_mut().find(|q| q.id == quest.id) {
+            lock_quest.status = quest.status.clone();
+        }
+        if !was_unlockable && lock.is_unlockable() {
+            quest.record(lock.user_id.clone(), DomainEventKind::LockUnlockable);
+        }
+
+        let saved = self
+            .quest_repo
+            .save(&quest, lock.version)
+            .await
+            .map_err(|err| {
+                tracing::error!("Error forcing quest status: {err}");
+                AppError::DatabaseError(err)
+            })?;
+        if !saved {
+            return Err(LockPrecondition::Any.failed());
+        }
+        self.events.publish(&quest.events);
+        if quest.status == QuestStatus::COMPLETED {
+            self.metrics.quest_completed(&quest.quest_type);
+        }
+
+        Ok(AdminQuestDTO::from(quest))
+    }
+
+    async fn cancel_lock_deletion(
+        &self,
+        operator: &Principal,
+        lock_id: String,
+        reason: Option<String>,
+    ) -> Result<AdminLockDTO, AppError> {
+        let parsed_lock_id = self._parse_id(&lock_id)?;
+        let mut lock = self
+            .lock_repo
+            .get_deleted_by_id(parsed_lock_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| {
+                AppError::NotFound(
+                    ErrorCode::LockNotFound,
+                    "Deleted lock not found".to_string(),
+                )
+            })?;
+
+        let reason = reason
+            .map(|reason| reason.trim().to_string())
+            .filter(|reason| !reason.is_empty());
+        self._audit(
+            operator,
+            lock_id,
+            reason,
+            AuditAction::LockDeletionCancelled,
+        )
+        .await?;
+
+        lock.record(DomainEventKind::LockRestored);
+        let restored = self.lock_repo.restore(&lock).await.map_err(|err| {
+            tracing::error!("Error cancelling lock deletion: {err}");
+            AppError::DatabaseError(err)
+        })?;
+        if !restored {
+            return Err(LockPrecondition::Any.failed());
+        }
+        self.events.publish(&lock.events);
+        lock.deleted_at = None;
+        lock.version += 1;
+
+        Ok(AdminLockDTO::from(lock))
+    }
+}
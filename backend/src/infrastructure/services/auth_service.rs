@@ -1,33 +1,278 @@
 use std::{sync::Arc, time::Duration};
 
 use async_trait::async_trait;
-use jwtk::jwk::RemoteJwksVerifier;
+use chrono::{DateTime, Utc};
+use jwtk::{
+    Claims, HeaderAndClaims,
+    hmac::{HmacAlgorithm, HmacKey},
+    jwk::{JwkSet, JwkSetVerifier, RemoteJwksVerifier},
+};
 use serde_json::{Map, Value};
 
-use crate::application::{exceptions::AppError, services::auth_service::AuthServiceTrait};
+use crate::{
+    application::{exceptions::AppError, services::auth_service::AuthServiceTrait},
+    domain::auth::{
+        entity::{AuthMethod, PERSONAL_ACCESS_TOKEN_PREFIX, PersonalAccessToken, Principal},
+        repository::PersonalAccessTokenRepository as PersonalAccessTokenRepositoryInterface,
+    },
+    infrastructure::exceptions::InfrastructureError,
+    setup::config::Config,
+};
+
+/// How access tokens are verified, selected with `AUTH_MODE`.
+pub enum TokenVerifier {
+    /// `remote_jwks`, keys fetched from the identity provider's `AUTH_JWKS_URL`.
+    RemoteJwks(RemoteJwksVerifier),
+    /// `jwks_file`, a static key set read from `AUTH_JWKS_FILE`, for running without the
+    /// identity provider.
+    JwksFile(JwkSetVerifier),
+    /// `hs256`, a shared `AUTH_HS256_SECRET` for local development and CI.
+    Hs256(HmacKey),
+}
+
+impl TokenVerifier {
+    pub fn from_config(config: &Config) -> Result<Self, InfrastructureError> {
+        match config.auth_mode.as_str() {
+            "remote_jwks" => {
+                if config.auth_jwks_url.is_empty() {
+                    return Err(InfrastructureError::AuthConfigurationError(
+                        "AUTH_JWKS_URL must be set in remote_jwks mode".to_string(),
+                    ));
+                }
+                Ok(TokenVerifier::RemoteJwks(RemoteJwksVerifier::new(
+                    config.auth_jwks_url.clone(),
+                    None,
+                    Duration::from_secs(3600),
+                )))
+            }
+            "jwks_file" => {
+                let path = config.auth_jwks_file.as_deref().ok_or_else(|| {
+                    InfrastructureError::AuthConfigurationError(
+                        "AUTH_JWKS_FILE must be set in jwks_file mode".to_string(),
+                    )
+                })?;
+                let jwks = std::fs::read_to_string(path).map_err(|e| {
+                    InfrastructureError::AuthConfigurationError(format!(
+                        "Failed to read JWKS file {path}: {e}"
+                    ))
+                })?;
+                let jwks: JwkSet = serde_json::from_str(&jwks).map_err(|e| {
+                    InfrastructureError::AuthConfigurationError(format!(
+                        "Failed to parse JWKS file {path}: {e}"
+                    ))
+                })?;
+                Ok(TokenVerifier::JwksFile(jwks.verifier()))
+            }
+            "hs256" => {
+                if config.environment == "production" {
+                    return Err(InfrastructureError::AuthConfigurationError(
+                        "hs256 auth mode is for development only".to_string(),
+                    ));
+                }
+                let secret = config.auth_hs256_secret.as_deref().unwrap_or_default();
+                if secret.len() < MIN_HS256_SECRET_LEN {
+                    return Err(InfrastructureError::AuthConfigurationError(format!(
+                        "AUTH_HS256_SECRET must be at least {MIN_HS256_SECRET_LEN} bytes"
+                    )));
+                }
+                Ok(TokenVerifier::Hs256(HmacKey::from_bytes(
+                    secret.as_bytes(),
+                    HmacAlgorithm::HS256,
+                )))
+            }
+            mode => Err(InfrastructureError::AuthConfigurationError(format!(
+                "Unknown AUTH_MODE '{mode}', expected remote_jwks, jwks_file or hs256"
+            ))),
+        }
+    }
+
+    async fn verify(&self, token: &str) -> jwtk::Result<HeaderAndClaims<Map<String, Value>>> {
+        match self {
+            TokenVerifier::RemoteJwks(verifier) => verifier.verify(token).await,
+            TokenVerifier::JwksFile(verifier) => verifier.verify(token),
+            TokenVerifier::Hs256(key) => jwtk::verify(token, key),
+        }
+    }
+}
+
+/// HS256 keys shorter than the hash output weaken the signature.
+pub const MIN_HS256_SECRET_LEN: usize = 32;
 
 pub struct AuthService {
-    pub jwks_verifier: RemoteJwksVerifier,
+    pub verifier: TokenVerifier,
+    /// Required `iss` claim, not checked when unset.
+    pub issuer: Option<String>,
+    /// Value that must appear in the `aud` claim, not checked when unset.
+    pub audience: Option<String>,
+    /// Personal access tokens are accepted alongside JWTs, whatever the `AUTH_MODE`.
+    pub token_repo: Arc<dyn PersonalAccessTokenRepositoryInterface>,
 }
 
 impl AuthService {
-    pub fn create(auth_jwks_url: &str) -> Arc<dyn AuthServiceTrait> {
+    pub fn create(
+        verifier: TokenVerifier,
+        issuer: Option<String>,
+        audience: Option<String>,
+        token_repo: Arc<dyn PersonalAccessTokenRepositoryInterface>,
+    ) -> Arc<dyn AuthServiceTrait> {
         Arc::new(Self {
-            jwks_verifier: RemoteJwksVerifier::new(
-                auth_jwks_url.to_string(),
-                None,
-                Duration::from_secs(3600),
+            verifier,
+            issuer,
+            audience,
+            token_repo,
+        })
+    }
+
+    /// Build the service from `AUTH_*`. In production tokens must be checked against an issuer
+    /// and audience, otherwise a token the identity provider issued for any other application
+    /// would be accepted here.
+    pub fn from_config(
+        config: &Config,
+        token_repo: Arc<dyn PersonalAccessTokenRepositoryInterface>,
+    ) -> Result<Arc<dyn AuthServiceTrait>, InfrastructureError> {
+        if config.environment == "production" {
+            if config.auth_issuer.is_none() {
+                return Err(InfrastructureError::AuthConfigurationError(
+                    "AUTH_ISSUER must be set in production".to_string(),
+                ));
+            }
+            if config.auth_audience.is_none() {
+                return Err(InfrastructureError::AuthConfigurationError(
+                    "AUTH_AUDIENCE must be set in production".to_string(),
+                ));
+            }
+        }
+
+        Ok(Self::create(
+            TokenVerifier::from_config(config)?,
+            config.auth_issuer.clone(),
+            config.auth_audience.clone(),
+            token_repo,
+        ))
+    }
+
+    async fn _verify_personal_token(&self, secret: &str) -> Result<Principal, AppError> {
+        let token = self
+            .token_repo
+            .get_by_hash(&PersonalAccessToken::hash_secret(secret))
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| AppError::Unauthorised("Unknown access token".to_string()))?;
+
+        let now = Utc::now();
+        if !token.is_active(now) {
+            return Err(AppError::Unauthorised(
+                "Access token is expired or revoked".to_string(),
+            ));
+        }
+        if let Err(err) = self.token_repo.touch(token.id, now).await {
+            tracing::warn!("Failed to record token use - token_id: {}: {err}", token.id);
+        }
+
+        Ok(Principal {
+            subject: token.user_id,
+            issuer: None,
+            scopes: token.scopes,
+            roles: vec![],
+            email: None,
+            auth_time: None,
+            method: AuthMethod::PersonalAccessToken { token_id: token.id },
+        })
+    }
+
+    fn _check_issuer(&self, claims: &Claims<Map<String, Value>>) -> Result<(), AppError> {
+        match &self.issuer {
+            Some(issuer) if claims.iss.as_deref() != Some(issuer.as_str()) => Err(
+                AppError::Unauthorised("Token was not issued by the expected issuer".to_string()),
             ),
+            _ => Ok(()),
+        }
+    }
+
+    fn _check_audience(&self, claims: &Claims<Map<String, Value>>) -> Result<(), AppError> {
+        match &self.audience {
+            Some(audience) if !claims.aud.iter().any(|aud| aud == audience) => Err(
+                AppError::Unauthorised("Token is not intended for this audience".to_string()),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Build the principal from the standard claims and the common extensions for scopes
+    /// (`scope` as a space separated string, or `scp`), roles, email and `auth_time`.
+    fn _principal(&self, claims: &Claims<Map<String, Value>>) -> Result<Principal, AppError> {
+        let subject = claims
+            .sub
+            .clone()
+            .filter(|sub| !sub.is_empty())
+            .ok_or_else(|| AppError::Unauthorised("Token has no subject".to_string()))?;
+
+        let scopes = match claims
+            .extra
+            .get("scope")
+            .or_else(|| claims.extra.get("scp"))
+        {
+            Some(Value::String(scope)) => scope.split_whitespace().map(String::from).collect(),
+            Some(scopes) => claim_strings(scopes),
+            None => vec![],
+        };
+        let roles = claims
+            .extra
+            .get("roles")
+            .map(claim_strings)
+            .unwrap_or_default();
+        let email = claims
+            .extra
+            .get("email")
+            .and_then(Value::as_str)
+            .map(String::from);
+        let auth_time = claims
+            .extra
+            .get("auth_time")
+            .and_then(Value::as_i64)
+            .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0));
+
+        Ok(Principal {
+            subject,
+            issuer: claims.iss.clone(),
+            scopes,
+            roles,
+            email,
+            auth_time,
+            method: AuthMethod::Jwt,
         })
     }
 }
 
+/// A claim that may hold a single string or an array of them.
+fn claim_strings(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(values) => values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(String::from)
+            .collect(),
+        Value::String(value) => vec![value.clone()],
+        _ => vec![],
+    }
+}
+
 #[async_trait]
 impl AuthServiceTrait for AuthService {
-    async fn verify(&self, token: &str) -> Result<String, AppError> {
-        match self.jwks_verifier.verify::<Map<String, Value>>(token).await {
-            Ok(res) => Ok(res.claims().sub.clone().unwrap()),
-            Err(err) => Err(AppError::Unauthorised(err.to_string())),
+    async fn verify(&self, token: &str) -> Result<Principal, AppError> {
+        if token.starts_with(PERSONAL_ACCESS_TOKEN_PREFIX) {
+            return self._verify_personal_token(token).await;
         }
+
+        let res = self
+            .verifier
+            .verify(token)
+            .await
+            .map_err(|err| AppError::Unauthorised(err.to_string()))?;
+        let claims = res.claims();
+
+        self._check_issuer(claims)?;
+        self._check_audience(claims)?;
+        self._principal(claims)
     }
 }
@@ -0,0 +1,122 @@
+// TODO move to application layer at some point
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        exceptions::AppError,
+        services::event_stream_service::{EventStreamServiceTrait, StreamedEvent},
+    },
+    domain::{
+        auth::entity::Principal, event::entity::DomainEvent,
+        membership::repository::LockMembershipRepository as LockMembershipRepositoryInterface,
+    },
+};
+
+pub struct EventStreamService {
+    pub membership_repo: Arc<dyn LockMembershipRepositoryInterface>,
+    sender: broadcast::Sender<DomainEvent>,
+    /// The latest events, oldest first, for clients resuming with `Last-Event-ID`.
+    buffer: Mutex<VecDeque<DomainEvent>>,
+    buffer_size: usize,
+}
+
+impl EventStreamService {
+    pub fn create(
+        membership_repo: Arc<dyn LockMembershipRepositoryInterface>,
+        buffer_size: usize,
+    ) -> Arc<dyn EventStreamServiceTrait> {
+        let buffer_size = buffer_size.max(1);
+        let (sender, _) = broadcast::channel(buffer_size);
+        Arc::new(Self {
+            membership_repo,
+            sender,
+            buffer: Mutex::new(VecDeque::with_capacity(buffer_size)),
+            buffer_size,
+        })
+    }
+}
+
+#[async_trait]
+impl EventStreamServiceTrait for EventStreamService {
+    fn publish(&self, events: &[DomainEvent]) {
+        // Sent while holding the buffer, so a new subscriber sees each event exactly once,
+        // either in its snapshot of the buffer or on its receiver.
+        let mut buffer = self.buffer.lock().expect("event buffer poisoned");
+        for event in events {
+            if buffer.len() == self.buffer_size {
+                buffer.pop_front();
+            }
+            buffer.push_back(event.clone());
+            // Fails only when nobody is listening.
+            let _ = self.sender.send(event.clone());
+        }
+    }
+
+    async fn subscribe(
+        &self,
+        principal: &Principal,
+        last_event_id: Option<Uuid>,
+    ) -> Result<BoxStream<'static, StreamedEvent>, AppError> {
+        let user_id = principal.subject.clone();
+        info!("Subscribe to events - user_id: {}", user_id);
+
+        // Memberships accepted later only show up once the client reconnects.
+        let lock_ids: HashSet<Uuid> = self
+            .membership_repo
+            .get_by_user_id(user_id.clone())
+            .await
+            .map_err(AppError::DatabaseError)?
+            .into_iter()
+            .filter(|membership| membership.is_accepted())
+            .map(|membership| membership.lock_id)
+            .collect();
+
+        let (receiver, buffered) = {
+            let buffer = self.buffer.lock().expect("event buffer poisoned");
+            (
+                self.sender.subscribe(),
+                buffer.iter().cloned().collect::<Vec<_>>(),
+            )
+        };
+        let missed = match last_event_id {
+            None => vec![],
+            Some(id) => match buffered.iter().position(|event| event.id == id) {
+                Some(position) => buffered[position + 1..]
+                    .iter()
+                    .cloned()
+                    .map(StreamedEvent::Event)
+                    .collect(),
+                None => vec![StreamedEvent::Reset],
+            },
+        };
+
+        let live = stream::unfold(receiver, |mut receiver| async move {
+            match receiver.recv().await {
+                Ok(event) => Some((StreamedEvent::Event(event), receiver)),
+                // The client fell too far behind, it has to reload.
+                Err(RecvError::Lagged(_)) => Some((StreamedEvent::Reset, receiver)),
+                Err(RecvError::Closed) => None,
+            }
+        });
+
+        let visible = move |item: &StreamedEvent| match item {
+            StreamedEvent::Event(event) => {
+                event.user_id == user_id || lock_ids.contains(&event.lock_id)
+            }
+            StreamedEvent::Reset => true,
+        };
+        Ok(stream::iter(missed)
+            .chain(live)
+            .filter(move |item| std::future::ready(visible(item)))
+            .boxed())
+    }
+}
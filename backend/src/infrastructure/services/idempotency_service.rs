@@ -0,0 +1,131 @@
+// TODO move to application layer at some point
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use tracing::info;
+
+use crate::{
+    application::{
+        exceptions::AppError,
+        services::idempotency_service::{IdempotencyOutcome, IdempotencyServiceTrait},
+    },
+    domain::idempotency::{
+        entity::{IdempotencyRecord, StoredResponse},
+        repository::IdempotencyRepository as IdempotencyRepositoryInterface,
+    },
+};
+
+/// A reservation without a response after this long belongs to a request that never finished,
+/// e.g. the server went down mid-request, and may be taken over.
+const ABANDONED_AFTER_SECS: i64 = 300;
+
+pub struct IdempotencyService {
+    pub repo: Arc<dyn IdempotencyRepositoryInterface>,
+    /// How long a key and its response are kept.
+    pub ttl: Duration,
+}
+
+impl IdempotencyService {
+    pub fn create(
+        idempotency_repo: Arc<dyn IdempotencyRepositoryInterface>,
+        ttl: Duration,
+    ) -> Arc<dyn IdempotencyServiceTrait> {
+        Arc::new(Self {
+            repo: idempotency_repo,
+            ttl,
+        })
+    }
+
+    fn _is_stale(&self, record: &IdempotencyRecord) -> bool {
+        let now = Utc::now();
+        record.is_expired(now)
+            || (record.response.is_none()
+                && record.created_at + Duration::seconds(ABANDONED_AFTER_SECS) <= now)
+    }
+}
+
+#[async_trait]
+impl IdempotencyServiceTrait for IdempotencyService {
+    async fn begin(
+        &self,
+        user_id: String,
+        key: String,
+        fingerprint: String,
+    ) -> Result<IdempotencyOutcome, AppError> {
+        // The second attempt follows a stale key being removed, or another request releasing it.
+        for _ in 0..2 {
+            let record = IdempotencyRecord::reserve(
+                user_id.clone(),
+                key.clone(),
+                fingerprint.clone(),
+                self.ttl,
+            );
+            let reserved = self
+                .repo
+                .reserve(&record)
+                .await
+                .map_err(AppError::DatabaseError)?;
+            if reserved {
+                return Ok(IdempotencyOutcome::Proceed);
+            }
+
+            let Some(existing) = self
+                .repo
+                .get(user_id.clone(), key.clone())
+                .await
+                .map_err(AppError::DatabaseError)?
+            else {
+                continue;
+            };
+
+            if self._is_stale(&existing) {
+                self.repo
+                    .delete(user_id.clone(), key.clone())
+                    .await
+                    .map_err(AppError::DatabaseError)?;
+                continue;
+            }
+            if existing.fingerprint != fingerprint {
+                info!("Idempotency key reused - user_id: {}", user_id);
+                return Ok(IdempotencyOutcome::Mismatch);
+            }
+            return Ok(match existing.response {
+                Some(response) => {
+                    info!("Replay idempotent response - user_id: {}", user_id);
+                    IdempotencyOutcome::Replay(response)
+                }
+                None => IdempotencyOutcome::InProgress,
+            });
+        }
+        Ok(IdempotencyOutcome::InProgress)
+    }
+
+    async fn complete(
+        &self,
+        user_id: String,
+        key: String,
+        response: StoredResponse,
+    ) -> Result<(), AppError> {
+        self.repo
+            .save_response(user_id, key, &response)
+            .await
+            .map_err(AppError::DatabaseError)?;
+        Ok(())
+    }
+
+    async fn release(&self, user_id: String, key: String) -> Result<(), AppError> {
+        self.repo
+            .delete(user_id, key)
+            .await
+            .map_err(AppError::DatabaseError)?;
+        Ok(())
+    }
+
+    async fn purge_expired(&self) -> Result<u64, AppError> {
+        self.repo
+            .delete_expired(Utc::now())
+            .await
+            .map_err(AppError::DatabaseError)
+    }
+}
@@ -7,59 +7,181 @@ use uuid::Uuid;
 
 use crate::{
     application::{
-        dtos::lock::LockDTO, exceptions::AppError,
+        dtos::lock::LockDTO,
+        exceptions::{AppError, ErrorCode},
+        policy::{LockAction, authorize_lock, membership_role},
         services::lock_query_service::LockQueryServiceTrait,
     },
-    domain::lock::repository::LockRepository as LockRepositoryInterface,
+    domain::{
+        auth::entity::Principal,
+        event::entity::DomainEvent,
+        lock::entity::Lock,
+        lock::repository::{LockRepository as LockRepositoryInterface, ReadPreference},
+        membership::repository::LockMembershipRepository as LockMembershipRepositoryInterface,
+    },
 };
 
 pub struct LockQueryService {
     pub repo: Arc<dyn LockRepositoryInterface + Send + Sync>,
+    pub membership_repo: Arc<dyn LockMembershipRepositoryInterface + Send + Sync>,
 }
 
 impl LockQueryService {
-    pub fn create(lock_repo: Arc<dyn LockRepositoryInterface>) -> Arc<dyn LockQueryServiceTrait> {
-        Arc::new(Self { repo: lock_repo })
+    pub fn create(
+        lock_repo: Arc<dyn LockRepositoryInterface>,
+        membership_repo: Arc<dyn LockMembershipRepositoryInterface>,
+    ) -> Arc<dyn LockQueryServiceTrait> {
+        Arc::new(Self {
+            repo: lock_repo,
+            membership_repo,
+        })
+    }
+
+    /// Owners see their locks in full, every other lock is listed because it was shared.
+    fn _to_dto(&self, principal: &Principal, lock: Lock) -> LockDTO {
+        if lock.user_id == principal.subject {
+            LockDTO::from(lock)
+        } else {
+            LockDTO::redacted(lock)
+        }
     }
 
     fn _parse_id(&self, lock_id: &str) -> Result<Uuid, AppError> {
         match Uuid::try_parse(&lock_id) {
             Ok(id) => Ok(id),
-            Err(_) => return Err(AppError::ValidationError(lock_id.to_string())),
+            Err(_) => {
+                return Err(AppError::ValidationError(
+                    ErrorCode::InvalidId,
+                    lock_id.to_string(),
+                ));
+            }
         }
     }
 }
 
 #[async_trait]
 impl LockQueryServiceTrait for LockQueryService {
-    async fn get_lock_by_id(&self, user_id: String, lock_id: String) -> Result<LockDTO, AppError> {
-        info!("Get lock by id - user_id: {user_id}, lock_id: {lock_id}");
+    async fn get_lock_by_id(
+        &self,
+        principal: &Principal,
+        lock_id: String,
+        read: ReadPreference,
+    ) -> Result<LockDTO, AppError> {
+        info!(
+            "Get lock by id - user_id: {}, lock_id: {lock_id}",
+            principal.subject
+        );
         let parsed_lock_id = self._parse_id(&lock_id)?;
 
         let lock = self
             .repo
-            .get_by_id(parsed_lock_id)
+            .get_by_id(parsed_lock_id, read)
             .await
             .map_err(AppError::DatabaseError)?
-            .ok_or_else(|| AppError::NotFound("Lock not found".to_string()))?;
+            .ok_or_else(|| {
+                AppError::NotFound(ErrorCode::LockNotFound, "Lock not found".to_string())
+            })?;
+        let membership = membership_role(self.membership_repo.as_ref(), principal, &lock).await?;
+        let relationship = authorize_lock(principal, &lock, membership, LockAction::View)?;
+
+        if relationship.sees_secrets() {
+            Ok(LockDTO::from(lock))
+        } else {
+            Ok(LockDTO::redacted(lock))
+        }
+    }
 
-        if lock.user_id != user_id {
-            return Err(AppError::Unauthorised(
-                "You are not authorised to access this resource".to_string(),
-            ));
+    async fn get_locks_by_ids(
+        &self,
+        principal: &Principal,
+        lock_ids: Vec<String>,
+        read: ReadPreference,
+    ) -> Vec<Result<LockDTO, AppError>> {
+        info!(
+            "Get locks by ids - user_id: {}, count: {}",
+            principal.subject,
+            lock_ids.len()
+        );
+        // Each id goes through the same checks as a lookup of its own, so a lock the caller
+        // can't see fails as not found rather than failing the batch.
+        let mut results = Vec::with_capacity(lock_ids.len());
+        for lock_id in lock_ids {
+            results.push(self.get_lock_by_id(principal, lock_id, read).await);
         }
-        Ok(LockDTO::from(lock))
+        results
+    }
+
+    async fn get_lock_history(
+        &self,
+        principal: &Principal,
+        lock_id: String,
+        read: ReadPreference,
+    ) -> Result<Vec<DomainEvent>, AppError> {
+        info!(
+            "Get lock history - user_id: {}, lock_id: {lock_id}",
+            principal.subject
+        );
+        let parsed_lock_id = self._parse_id(&lock_id)?;
+
+        let lock = self
+            .repo
+            .get_by_id(parsed_lock_id, read)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| {
+                AppError::NotFound(ErrorCode::LockNotFound, "Lock not found".to_string())
+            })?;
+        let membership = membership_role(self.membership_repo.as_ref(), principal, &lock).await?;
+        authorize_lock(principal, &lock, membership, LockAction::View)?;
+
+        self.repo
+            .get_history(lock.id, read)
+            .await
+            .map_err(AppError::DatabaseError)
+    }
+
+    async fn get_locks(
+        &self,
+        principal: &Principal,
+        read: ReadPreference,
+    ) -> Result<Vec<LockDTO>, AppError> {
+        info!("Get locks request - user_id: {}", principal.subject);
+        // Only locks the caller owns or has accepted a membership of are listed, so there is
+        // nothing further to authorise.
+        let mut locks = self
+            .repo
+            .get_by_user_id(principal.subject.clone(), read)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        // Oldest first, a stable order keeps the list's ETag stable.
+        locks.sort_by_key(|lock| lock.id);
+        let lock_dtos = locks
+            .into_iter()
+            .map(|lock| self._to_dto(principal, lock))
+            .collect();
+
+        Ok(lock_dtos)
     }
 
-    async fn get_locks(&self, user_id: String) -> Result<Vec<LockDTO>, AppError> {
-        info!("Get locks request - user_id: {user_id}");
-        let locks = self
+    async fn get_shared_locks(
+        &self,
+        principal: &Principal,
+        read: ReadPreference,
+    ) -> Result<Vec<LockDTO>, AppError> {
+        info!("Get shared locks request - user_id: {}", principal.subject);
+        let mut locks = self
             .repo
-            .get_by_user_id(user_id)
+            .get_by_user_id(principal.subject.clone(), read)
             .await
             .map_err(AppError::DatabaseError)?;
 
-        let lock_dtos = locks.into_iter().map(|lock| LockDTO::from(lock)).collect();
+        locks.sort_by_key(|lock| lock.id);
+        let lock_dtos = locks
+            .into_iter()
+            .filter(|lock| lock.user_id != principal.subject)
+            .map(LockDTO::redacted)
+            .collect();
 
         Ok(lock_dtos)
     }
@@ -2,44 +2,116 @@
 use std::{collections::HashMap, str::FromStr, sync::Arc};
 
 use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use tracing::info;
 use uuid::Uuid;
 
 use crate::{
     application::{
-        dtos::lock::LockDTO, exceptions::AppError, services::lock_service::LockServiceTrait,
+        dtos::lock::{BatchMode, LockDTO, NewLock},
+        exceptions::{AppError, ErrorCode},
+        policy::{LockAction, authorize_lock, membership_role},
+        precondition::LockPrecondition,
+        services::{
+            event_stream_service::EventStreamServiceTrait, lock_service::LockServiceTrait,
+            metrics_service::MetricsServiceTrait,
+        },
     },
     domain::{
-        lock::{entity::Lock, repository::LockRepository as LockRepositoryInterface},
-        quest::{entity::Quest, enums::QuestType},
+        auth::entity::Principal,
+        event::entity::DomainEventKind,
+        lock::{
+            entity::Lock,
+            repository::{LockRepository as LockRepositoryInterface, ReadPreference},
+        },
+        membership::repository::LockMembershipRepository as LockMembershipRepositoryInterface,
+        quest::{
+            entity::Quest, enums::QuestType,
+            repository::QuestRepository as QuestRepositoryInterface,
+        },
     },
 };
 
 pub struct LockService {
     pub repo: Arc<dyn LockRepositoryInterface + Send + Sync>,
+    pub quest_repo: Arc<dyn QuestRepositoryInterface + Send + Sync>,
+    pub membership_repo: Arc<dyn LockMembershipRepositoryInterface + Send + Sync>,
+    /// Where the events of saved changes are published for live clients.
+    pub events: Arc<dyn EventStreamServiceTrait>,
+    pub metrics: Arc<dyn MetricsServiceTrait>,
+    /// How long a soft deleted lock can still be restored before it is purged.
+    pub retention: Duration,
 }
 
 impl LockService {
-    pub fn create(lock_repo: Arc<dyn LockRepositoryInterface>) -> Arc<dyn LockServiceTrait> {
-        Arc::new(Self { repo: lock_repo })
+    pub fn create(
+        lock_repo: Arc<dyn LockRepositoryInterface>,
+        quest_repo: Arc<dyn QuestRepositoryInterface>,
+        membership_repo: Arc<dyn LockMembershipRepositoryInterface>,
+        events: Arc<dyn EventStreamServiceTrait>,
+        metrics: Arc<dyn MetricsServiceTrait>,
+        retention: Duration,
+    ) -> Arc<dyn LockServiceTrait> {
+        Arc::new(Self {
+            repo: lock_repo,
+            quest_repo,
+            membership_repo,
+            events,
+            metrics,
+            retention,
+        })
     }
 
     fn _parse_id(&self, lock_id: &str) -> Result<Uuid, AppError> {
         match Uuid::try_parse(&lock_id) {
             Ok(id) => Ok(id),
-            Err(_) => return Err(AppError::ValidationError(lock_id.to_string())),
+            Err(_) => {
+                return Err(AppError::ValidationError(
+                    ErrorCode::InvalidId,
+                    lock_id.to_string(),
+                ));
+            }
         }
     }
 
     fn _parse_quest_type(&self, quest_type: &str) -> Result<QuestType, AppError> {
         match QuestType::from_str(quest_type) {
             Ok(quest_type) => Ok(quest_type),
-            Err(err) => Err(AppError::ValidationError(err.to_string())),
+            Err(err) => Err(AppError::ValidationError(
+                ErrorCode::InvalidQuestType,
+                err.to_string(),
+            )),
         }
     }
 
+    /// The lock with its quests and creation event, not saved yet.
+    fn _new_lock(&self, principal: &Principal, new_lock: NewLock) -> Result<Lock, AppError> {
+        let quests = new_lock
+            .quests
+            .into_iter()
+            .map(|(share, quest_type, data)| {
+                let quest_type = self._parse_quest_type(&quest_type)?;
+                Ok(Quest::create(Uuid::now_v7(), share, quest_type, None, data)) // TODO anything other than a placeholder ID
+            })
+            .collect::<Result<Vec<Quest>, AppError>>()?;
+        let user_id = principal.subject.clone();
+        let mut lock = Lock::create(
+            user_id,
+            new_lock.label,
+            new_lock.total_shares,
+            new_lock.threshold,
+            quests,
+        );
+        for quest in &mut lock.quests {
+            quest.lock_id = lock.id.clone();
+        }
+        lock.record(DomainEventKind::LockCreated);
+        Ok(lock)
+    }
+
     async fn _get_lock(&self, lock_id: &str) -> Result<Option<Lock>, AppError> {
-        let lock_id = self._parse_id(lock_id).unwrap();
-        match self.repo.get_by_id(lock_id).await {
+        let lock_id = self._parse_id(lock_id)?;
+        match self.repo.get_by_id(lock_id, ReadPreference::Primary).await {
             Ok(lock) => Ok(lock),
             Err(err) => Err(AppError::DatabaseError(err)),
         }
@@ -50,23 +122,32 @@ impl LockService {
 impl LockServiceTrait for LockService {
     async fn create_lock(
         &self,
-        user_id: String,
+        principal: &Principal,
         label: Option<String>,
         total_shares: u8,
         threshold: u8,
     ) -> Result<LockDTO, AppError> {
-        let lock = Lock::create(user_id, label, total_shares, threshold, vec![]);
+        let user_id = principal.subject.clone();
+        let mut lock = Lock::create(user_id, label, total_shares, threshold, vec![]);
+        lock.record(DomainEventKind::LockCreated);
 
-        if let Err(err) = self.repo.save(&lock).await {
+        let saved = self.repo.save(&lock).await.map_err(|err| {
             tracing::error!("Error creating lock: {err}");
-            return Err(AppError::DatabaseError(err));
+            AppError::DatabaseError(err)
+        })?;
+        if !saved {
+            tracing::error!("Error creating lock: the lock was not saved");
+            return Err(AppError::InternalError);
         }
+        self.events.publish(&lock.events);
+        self.metrics.lock_created();
 
         Ok(LockDTO::from(lock))
     }
 
     async fn plan_quest(
         &self,
+        principal: &Principal,
         lock_id: String,
         share: String,
         quest_type: String,
@@ -74,48 +155,238 @@ impl LockServiceTrait for LockService {
     ) -> Result<LockDTO, AppError> {
         let quest_type = QuestType::from_str(&quest_type);
         if let Err(error) = quest_type {
-            return Err(AppError::ValidationError(error.to_string()));
+            return Err(AppError::ValidationError(
+                ErrorCode::InvalidQuestType,
+                error.to_string(),
+            ));
         }
         let quest_type = quest_type.unwrap();
-        let mut lock = self._get_lock(&lock_id).await.unwrap().unwrap();
-        let quest = Quest::create(lock.id, share, quest_type, None, data);
+        let mut lock = self._get_lock(&lock_id).await?.ok_or_else(|| {
+            AppError::NotFound(ErrorCode::LockNotFound, "Lock not found".to_string())
+        })?;
+        let membership = membership_role(self.membership_repo.as_ref(), principal, &lock).await?;
+        authorize_lock(principal, &lock, membership, LockAction::Edit)?;
 
-        lock.quests.push(quest);
-        if let Err(err) = self.repo.save(&lock).await {
-            tracing::error!("Error planning quest: {err}");
-            return Err(AppError::DatabaseError(err));
+        let mut quest = Quest::create(lock.id, share, quest_type, None, data);
+        quest.record(
+            lock.user_id.clone(),
+            DomainEventKind::QuestPlanned {
+                quest_id: quest.id,
+                quest_type: quest.quest_type.clone(),
+            },
+        );
+
+        let saved = self
+            .quest_repo
+            .save(&quest, lock.version)
+            .await
+            .map_err(|err| {
+                tracing::error!("Error planning quest: {err}");
+                AppError::DatabaseError(err)
+            })?;
+        if !saved {
+            return Err(LockPrecondition::Any.failed());
         }
+        self.events.publish(&quest.events);
+        lock.quests.push(quest);
+        lock.version += 1;
 
         Ok(LockDTO::from(lock))
     }
 
     async fn create_lock_with_quests(
         &self,
-        user_id: String,
+        principal: &Principal,
         label: Option<String>,
         total_shares: u8,
         threshold: u8,
         quest_data: Vec<(String, String, HashMap<String, String>)>,
     ) -> Result<LockDTO, AppError> {
-        let quests: Vec<Quest> = quest_data
-            .into_iter()
-            .map(|(share, quest_type, data)| {
-                let quest_type = self
-                    ._parse_quest_type(&quest_type)
-                    .expect("Quest type parse failed"); // TODO
-                Quest::create(Uuid::now_v7(), share, quest_type, None, data) // TODO anything other than a placeholder ID
-            })
-            .collect();
-        let mut lock = Lock::create(user_id, label, total_shares, threshold, quests);
-        for quest in &mut lock.quests {
-            quest.lock_id = lock.id.clone();
-        }
+        let lock = self._new_lock(
+            principal,
+            NewLock {
+                label,
+                total_shares,
+                threshold,
+                quests: quest_data,
+            },
+        )?;
 
-        if let Err(err) = self.repo.save(&lock).await {
+        let saved = self.repo.save(&lock).await.map_err(|err| {
             tracing::error!("Error creating lock with quests: {err}");
-            return Err(AppError::DatabaseError(err));
+            AppError::DatabaseError(err)
+        })?;
+        if !saved {
+            tracing::error!("Error creating lock with quests: the lock was not saved");
+            return Err(AppError::InternalError);
+        }
+        self.events.publish(&lock.events);
+        self.metrics.lock_created();
+
+        Ok(LockDTO::from(lock))
+    }
+
+    async fn create_locks(
+        &self,
+        principal: &Principal,
+        locks: Vec<NewLock>,
+        mode: BatchMode,
+    ) -> Result<Vec<Result<LockDTO, AppError>>, AppError> {
+        info!(
+            "Create locks - user_id: {}, count: {}, mode: {mode:?}",
+            principal.subject,
+            locks.len()
+        );
+        match mode {
+            BatchMode::Atomic => {
+                let locks = locks
+                    .into_iter()
+                    .map(|new_lock| self._new_lock(principal, new_lock))
+                    .collect::<Result<Vec<Lock>, AppError>>()?;
+
+                let saved = self.repo.save_all(&locks).await.map_err(|err| {
+                    tracing::error!("Error creating locks: {err}");
+                    AppError::DatabaseError(err)
+                })?;
+                if !saved {
+                    tracing::error!("Error creating locks: a lock was not saved");
+                    return Err(AppError::InternalError);
+                }
+                for lock in &locks {
+                    self.events.publish(&lock.events);
+                    self.metrics.lock_created();
+                }
+
+                Ok(locks
+                    .into_iter()
+                    .map(|lock| Ok(LockDTO::from(lock)))
+                    .collect())
+            }
+            BatchMode::Partial => {
+                let mut results = Vec::with_capacity(locks.len());
+                for new_lock in locks {
+                    let NewLock {
+                        label,
+                        total_shares,
+                        threshold,
+                        quests,
+                    } = new_lock;
+                    results.push(
+                        self.create_lock_with_quests(
+                            principal,
+                            label,
+                            total_shares,
+                            threshold,
+                            quests,
+                        )
+                        .await,
+                    );
+                }
+                Ok(results)
+            }
+        }
+    }
+
+    async fn delete_lock(
+        &self,
+        principal: &Principal,
+        lock_id: String,
+        precondition: LockPrecondition,
+    ) -> Result<(), AppError> {
+        info!(
+            "Delete lock - user_id: {}, lock_id: {lock_id}",
+            principal.subject
+        );
+        let mut lock = self._get_lock(&lock_id).await?.ok_or_else(|| {
+            AppError::NotFound(ErrorCode::LockNotFound, "Lock not found".to_string())
+        })?;
+        let membership = membership_role(self.membership_repo.as_ref(), principal, &lock).await?;
+        authorize_lock(principal, &lock, membership, LockAction::Delete)?;
+        precondition.check(&lock)?;
+
+        lock.record(DomainEventKind::LockDeleted);
+
+        let deleted = self.repo.delete(&lock).await.map_err(|err| {
+            tracing::error!("Error deleting lock: {err}");
+            AppError::DatabaseError(err)
+        })?;
+        if !deleted {
+            return Err(precondition.failed());
+        }
+        self.events.publish(&lock.events);
+
+        Ok(())
+    }
+
+    async fn restore_lock(
+        &self,
+        principal: &Principal,
+        lock_id: String,
+        precondition: LockPrecondition,
+    ) -> Result<LockDTO, AppError> {
+        info!(
+            "Restore lock - user_id: {}, lock_id: {lock_id}",
+            principal.subject
+        );
+        let parsed_lock_id = self._parse_id(&lock_id)?;
+        let mut lock = self
+            .repo
+            .get_deleted_by_id(parsed_lock_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| {
+                AppError::NotFound(
+                    ErrorCode::LockNotFound,
+                    "Deleted lock not found".to_string(),
+                )
+            })?;
+        let membership = membership_role(self.membership_repo.as_ref(), principal, &lock).await?;
+        authorize_lock(principal, &lock, membership, LockAction::Restore).map_err(
+            |err| match err {
+                AppError::NotFound(..) => AppError::NotFound(
+                    ErrorCode::LockNotFound,
+                    "Deleted lock not found".to_string(),
+                ),
+                err => err,
+            },
+        )?;
+
+        if !lock.is_restorable(self.retention, Utc::now()) {
+            return Err(AppError::NotFound(
+                ErrorCode::LockRetentionExpired,
+                "Deleted lock is past its retention window".to_string(),
+            ));
+        }
+
+        precondition.check(&lock)?;
+
+        lock.record(DomainEventKind::LockRestored);
+
+        let restored = self.repo.restore(&lock).await.map_err(|err| {
+            tracing::error!("Error restoring lock: {err}");
+            AppError::DatabaseError(err)
+        })?;
+        if !restored {
+            return Err(precondition.failed());
         }
+        self.events.publish(&lock.events);
+        lock.deleted_at = None;
+        lock.version += 1;
 
         Ok(LockDTO::from(lock))
     }
+
+    async fn purge_deleted_locks(&self) -> Result<u64, AppError> {
+        let deleted_before = Utc::now() - self.retention;
+        let purged = self
+            .repo
+            .purge_deleted(deleted_before)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        if purged > 0 {
+            info!("Purged {purged} locks deleted before {deleted_before}");
+        }
+        Ok(purged)
+    }
 }
@@ -0,0 +1,37 @@
+// TODO move to application layer at some point
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::{
+    application::{exceptions::AppError, services::event_handler::EventHandlerTrait},
+    domain::event::entity::DomainEvent,
+};
+
+/// Logs every dispatched event, registered by default so the outbox always has a consumer.
+pub struct LoggingEventHandler;
+
+impl LoggingEventHandler {
+    pub fn create() -> Arc<dyn EventHandlerTrait> {
+        Arc::new(Self)
+    }
+}
+
+#[async_trait]
+impl EventHandlerTrait for LoggingEventHandler {
+    fn name(&self) -> &'static str {
+        "logging"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        info!(
+            "Domain event - type: {}, event_id: {}, lock_id: {}, user_id: {}",
+            event.event_type(),
+            event.id,
+            event.lock_id,
+            event.user_id
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,265 @@
+// TODO move to application layer at some point
+use std::{str::FromStr, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        dtos::membership::LockMembershipDTO,
+        exceptions::{AppError, ErrorCode},
+        policy::{LockAction, authorize_lock, membership_role},
+        services::membership_service::MembershipServiceTrait,
+    },
+    domain::{
+        auth::entity::Principal,
+        lock::{
+            entity::Lock,
+            repository::{LockRepository as LockRepositoryInterface, ReadPreference},
+        },
+        membership::{
+            entity::{LockMembership, MembershipRole},
+            repository::LockMembershipRepository as LockMembershipRepositoryInterface,
+        },
+    },
+};
+
+pub struct MembershipService {
+    pub repo: Arc<dyn LockMembershipRepositoryInterface + Send + Sync>,
+    pub lock_repo: Arc<dyn LockRepositoryInterface + Send + Sync>,
+}
+
+impl MembershipService {
+    pub fn create(
+        membership_repo: Arc<dyn LockMembershipRepositoryInterface>,
+        lock_repo: Arc<dyn LockRepositoryInterface>,
+    ) -> Arc<dyn MembershipServiceTrait> {
+        Arc::new(Self {
+            repo: membership_repo,
+            lock_repo,
+        })
+    }
+
+    fn _parse_id(&self, id: &str) -> Result<Uuid, AppError> {
+        match Uuid::try_parse(id) {
+            Ok(id) => Ok(id),
+            Err(_) => Err(AppError::ValidationError(
+                ErrorCode::InvalidId,
+                id.to_string(),
+            )),
+        }
+    }
+
+    fn _parse_role(&self, role: &str) -> Result<MembershipRole, AppError> {
+        match MembershipRole::from_str(role) {
+            Ok(role) => Ok(role),
+            Err(err) => Err(AppError::ValidationError(
+                ErrorCode::InvalidRole,
+                err.to_string(),
+            )),
+        }
+    }
+
+    /// Load a lock the caller may share.
+    async fn _get_shareable_lock(
+        &self,
+        principal: &Principal,
+        lock_id: Uuid,
+    ) -> Result<Lock, AppError> {
+        let lock = self
+            .lock_repo
+            .get_by_id(lock_id, ReadPreference::Primary)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| {
+                AppError::NotFound(ErrorCode::LockNotFound, "Lock not found".to_string())
+            })?;
+
+        let membership = membership_role(self.repo.as_ref(), principal, &lock).await?;
+        authorize_lock(principal, &lock, membership, LockAction::Share)?;
+
+        Ok(lock)
+    }
+}
+
+#[async_trait]
+impl MembershipServiceTrait for MembershipService {
+    async fn invite_member(
+        &self,
+        principal: &Principal,
+        lock_id: String,
+        user_id: String,
+        role: String,
+    ) -> Result<LockMembershipDTO, AppError> {
+        info!(
+            "Invite member - user_id: {}, lock_id: {lock_id}, member: {user_id}, role: {role}",
+            principal.subject
+        );
+        let role = self._parse_role(&role)?;
+        if role != MembershipRole::VIEWER {
+            return Err(AppError::ValidationError(
+                ErrorCode::InvalidRole,
+                format!(
+                    "Invitations can only grant the {} role",
+                    MembershipRole::VIEWER
+                ),
+            ));
+        }
+        let user_id = user_id.trim().to_string();
+        if user_id.is_empty() {
+            return Err(AppError::ValidationError(
+                ErrorCode::InvalidInvitee,
+                "The user to invite must not be empty".to_string(),
+            ));
+        }
+
+        let lock = self
+            ._get_shareable_lock(principal, self._parse_id(&lock_id)?)
+            .await?;
+        if user_id == lock.user_id {
+            return Err(AppError::ValidationError(
+                ErrorCode::InvalidInvitee,
+                "The owner of a lock can't be invited to it".to_string(),
+            ));
+        }
+
+        let membership = LockMembership::invite(lock.id, user_id, role, principal.subject.clone());
+        let saved = self
+            .repo
+            .save(&membership)
+            .await
+            .map_err(AppError::DatabaseError)?;
+        if !saved {
+            return Err(AppError::ValidationError(
+                ErrorCode::AlreadyMember,
+                "The user has already been invited to this lock".to_string(),
+            ));
+        }
+
+        Ok(LockMembershipDTO::from(membership))
+    }
+
+    async fn get_members(
+        &self,
+        principal: &Principal,
+        lock_id: String,
+    ) -> Result<Vec<LockMembershipDTO>, AppError> {
+        info!(
+            "Get members - user_id: {}, lock_id: {lock_id}",
+            principal.subject
+        );
+        let lock = self
+            ._get_shareable_lock(principal, self._parse_id(&lock_id)?)
+            .await?;
+
+        let memberships = self
+            .repo
+            .get_by_lock_id(lock.id)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(memberships
+            .into_iter()
+            .map(LockMembershipDTO::from)
+            .collect())
+    }
+
+    async fn get_invitations(
+        &self,
+        principal: &Principal,
+    ) -> Result<Vec<LockMembershipDTO>, AppError> {
+        info!("Get invitations - user_id: {}", principal.subject);
+        let memberships = self
+            .repo
+            .get_pending_by_user_id(principal.subject.clone())
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(memberships
+            .into_iter()
+            .map(LockMembershipDTO::from)
+            .collect())
+    }
+
+    async fn accept_invitation(
+        &self,
+        principal: &Principal,
+        membership_id: String,
+    ) -> Result<LockMembershipDTO, AppError> {
+        info!(
+            "Accept invitation - user_id: {}, membership_id: {membership_id}",
+            principal.subject
+        );
+        let id = self._parse_id(&membership_id)?;
+        let accepted_at = Utc::now();
+
+        let accepted = self
+            .repo
+            .accept(id, principal.subject.clone(), accepted_at)
+            .await
+            .map_err(AppError::DatabaseError)?;
+        if !accepted {
+            return Err(AppError::NotFound(
+                ErrorCode::InvitationNotFound,
+                "Invitation not found".to_string(),
+            ));
+        }
+
+        let membership = self
+            .repo
+            .get_by_id(id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| {
+                AppError::NotFound(
+                    ErrorCode::InvitationNotFound,
+                    "Invitation not found".to_string(),
+                )
+            })?;
+
+        Ok(LockMembershipDTO::from(membership))
+    }
+
+    async fn remove_membership(
+        &self,
+        principal: &Principal,
+        membership_id: String,
+    ) -> Result<(), AppError> {
+        info!(
+            "Remove membership - user_id: {}, membership_id: {membership_id}",
+            principal.subject
+        );
+        let membership = self
+            .repo
+            .get_by_id(self._parse_id(&membership_id)?)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| {
+                AppError::NotFound(
+                    ErrorCode::MembershipNotFound,
+                    "Membership not found".to_string(),
+                )
+            })?;
+
+        if membership.user_id != principal.subject {
+            self._get_shareable_lock(principal, membership.lock_id)
+                .await
+                .map_err(|err| match err {
+                    AppError::NotFound(..) => AppError::NotFound(
+                        ErrorCode::MembershipNotFound,
+                        "Membership not found".to_string(),
+                    ),
+                    err => err,
+                })?;
+        }
+
+        self.repo
+            .delete(membership.id)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+// TODO move to application layer at some point
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use prometheus::{
+    Encoder, GaugeVec, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec, Opts,
+    Registry, TextEncoder, core::Collector,
+};
+use tracing::error;
+
+use crate::{
+    application::services::metrics_service::{AttemptOutcome, MetricsServiceTrait},
+    domain::quest::enums::QuestType,
+    infrastructure::exceptions::InfrastructureError,
+    setup::config::DatabasePool,
+};
+
+/// A connection pool whose usage is reported, labelled `pool`.
+pub struct MonitoredPool {
+    pub name: &'static str,
+    pub pool: DatabasePool,
+    pub max_connections: u32,
+}
+
+pub struct MetricsService {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    locks_created: IntCounter,
+    quests_completed: IntCounterVec,
+    quest_attempts: IntCounterVec,
+    job_runs: IntCounterVec,
+    job_lag: GaugeVec,
+    db_connections: IntGaugeVec,
+    db_max_connections: IntGaugeVec,
+    pools: Vec<MonitoredPool>,
+    started_at: Instant,
+    /// When each background job last succeeded, or the process started if it never has.
+    job_last_success: Mutex<HashMap<&'static str, Instant>>,
+}
+
+impl MetricsService {
+    pub fn create(
+        pools: Vec<MonitoredPool>,
+    ) -> Result<Arc<dyn MetricsServiceTrait>, InfrastructureError> {
+        let registry =
+            Registry::new_custom(Some("quest_lock".to_string()), None).map_err(metrics_error)?;
+
+        let service = Self {
+            http_requests: IntCounterVec::new(
+                Opts::new("http_requests_total", "HTTP requests handled"),
+                &["method", "route", "status"],
+            )
+            .map_err(metrics_error)?,
+            http_request_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "http_request_duration_seconds",
+                    "Time taken to handle HTTP requests",
+                ),
+                &["method", "route", "status"],
+            )
+            .map_err(metrics_error)?,
+            locks_created: IntCounter::new("locks_created_total", "Locks created")
+                .map_err(metrics_error)?,
+            quests_completed: IntCounterVec::new(
+                Opts::new("quests_completed_total", "Quests completed"),
+                &["quest_type"],
+            )
+            .map_err(metrics_error)?,
+            quest_attempts: IntCounterVec::new(
+                Opts::new("quest_attempts_total", "Quest status updates, by outcome"),
+                &["outcome"],
+            )
+            .map_err(metrics_error)?,
+            job_runs: IntCounterVec::new(
+                Opts::new("job_runs_total", "Background job runs"),
+                &["job", "result"],
+            )
+            .map_err(metrics_error)?,
+            job_lag: GaugeVec::new(
+                Opts::new(
+                    "job_lag_seconds",
+                    "Time since a background job last ran successfully",
+                ),
+                &["job"],
+            )
+            .map_err(metrics_error)?,
+            db_connections: IntGaugeVec::new(
+                Opts::new("db_pool_connections", "Database pool connections, by state"),
+                &["pool", "state"],
+            )
+            .map_err(metrics_error)?,
+            db_max_connections: IntGaugeVec::new(
+                Opts::new("db_pool_max_connections", "Database pool size limit"),
+                &["pool"],
+            )
+            .map_err(metrics_error)?,
+            registry,
+            pools,
+            started_at: Instant::now(),
+            job_last_success: Mutex::new(HashMap::new()),
+        };
+
+        let collectors: [Box<dyn Collector>; 9] = [
+            Box::new(service.http_requests.clone()),
+            Box::new(service.http_request_duration.clone()),
+            Box::new(service.locks_created.clone()),
+            Box::new(service.quests_completed.clone()),
+            Box::new(service.quest_attempts.clone()),
+            Box::new(service.job_runs.clone()),
+            Box::new(service.job_lag.clone()),
+            Box::new(service.db_connections.clone()),
+            Box::new(service.db_max_connections.clone()),
+        ];
+        for collector in collectors {
+            service
+                .registry
+                .register(collector)
+                .map_err(metrics_error)?;
+        }
+
+        Ok(Arc::new(service))
+    }
+
+    /// Pool usage and job lag are sampled when scraped rather than kept up to date.
+    fn sample(&self) {
+        for monitored in &self.pools {
+            let size = i64::from(monitored.pool.size());
+            let idle = monitored.pool.num_idle() as i64;
+            self.db_connections
+                .with_label_values(&[monitored.name, "idle"])
+                .set(idle);
+            self.db_connections
+                .with_label_values(&[monitored.name, "in_use"])
+                .set((size - idle).max(0));
+            self.db_max_connections
+                .with_label_values(&[monitored.name])
+                .set(i64::from(monitored.max_connections));
+        }
+
+        let last_success = self.job_last_success.lock().expect("job metrics poisoned");
+        for (job, at) in last_success.iter() {
+            self.job_lag
+                .with_label_values(&[*job])
+                .set(at.elapsed().as_secs_f64());
+        }
+    }
+}
+
+fn metrics_error(err: prometheus::Error) -> InfrastructureError {
+    InfrastructureError::MetricsError(err.to_string())
+}
+
+impl MetricsServiceTrait for MetricsService {
+    fn observe_request(&self, method: &str, route: &str, status: u16, latency: Duration) {
+        let status = status.to_string();
+        let labels = [method, route, status.as_str()];
+        self.http_requests.with_label_values(&labels).inc();
+        self.http_request_duration
+            .with_label_values(&labels)
+            .observe(latency.as_secs_f64());
+    }
+
+    fn lock_created(&self) {
+        self.locks_created.inc();
+    }
+
+    fn quest_attempted(&self, outcome: AttemptOutcome) {
+        self.quest_attempts
+            .with_label_values(&[outcome.as_str()])
+            .inc();
+    }
+
+    fn quest_completed(&self, quest_type: &QuestType) {
+        self.quests_completed
+            .with_label_values(&[quest_type.to_string().to_lowercase()])
+            .inc();
+    }
+
+    fn job_ran(&self, job: &'static str, succeeded: bool) {
+        let result = if succeeded { "success" } else { "failure" };
+        self.job_runs.with_label_values(&[job, result]).inc();
+
+        let mut last_success = self.job_last_success.lock().expect("job metrics poisoned");
+        if succeeded {
+            last_success.insert(job, Instant::now());
+        } else {
+            last_success.entry(job).or_insert(self.started_at);
+        }
+    }
+
+    fn render(&self) -> String {
+        self.sample();
+
+        let mut buffer = Vec::new();
+        if let Err(err) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            error!("Failed to encode metrics: {err}");
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
@@ -1,3 +1,16 @@
+pub mod account_service;
+pub mod admin_service;
 pub mod auth_service;
+pub mod event_stream_service;
+pub mod idempotency_service;
 pub mod lock_query_service;
 pub mod lock_service;
+pub mod logging_event_handler;
+pub mod membership_service;
+pub mod metrics_service;
+pub mod outbox_dispatcher;
+pub mod quest_query_service;
+pub mod quest_service;
+pub mod token_service;
+pub mod webhook_event_handler;
+pub mod webhook_service;
@@ -0,0 +1,115 @@
+// TODO move to application layer at some point
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use tracing::{error, warn};
+
+use crate::{
+    application::{
+        exceptions::AppError,
+        services::{event_handler::EventHandlerTrait, outbox_dispatcher::OutboxDispatcherTrait},
+    },
+    domain::event::{
+        entity::OutboxMessage, repository::OutboxRepository as OutboxRepositoryInterface,
+    },
+};
+
+/// Longest wait between two delivery attempts of the same event.
+const MAX_BACKOFF_SECS: i64 = 3600;
+
+pub struct OutboxDispatcher {
+    pub repo: Arc<dyn OutboxRepositoryInterface>,
+    pub handlers: Vec<Arc<dyn EventHandlerTrait>>,
+    pub batch_size: i64,
+    /// Attempts after which a failing event is marked failed and no longer retried.
+    pub max_attempts: i32,
+    /// How long a claimed event stays hidden from other dispatchers before it can be
+    /// claimed again, covering a worker that dies mid batch.
+    pub lease: Duration,
+}
+
+impl OutboxDispatcher {
+    pub fn create(
+        outbox_repo: Arc<dyn OutboxRepositoryInterface>,
+        handlers: Vec<Arc<dyn EventHandlerTrait>>,
+        batch_size: i64,
+        max_attempts: i32,
+        lease: Duration,
+    ) -> Arc<dyn OutboxDispatcherTrait> {
+        Arc::new(Self {
+            repo: outbox_repo,
+            handlers,
+            batch_size,
+            max_attempts,
+            lease,
+        })
+    }
+
+    /// Exponential backoff, 2^attempts seconds capped at `MAX_BACKOFF_SECS`.
+    fn _backoff(&self, attempts: i32) -> Duration {
+        let secs = 2_i64
+            .checked_pow(attempts.max(0) as u32)
+            .unwrap_or(MAX_BACKOFF_SECS);
+        Duration::seconds(secs.min(MAX_BACKOFF_SECS))
+    }
+
+    /// Run the handlers that haven't handled the event yet. Those that succeed are recorded
+    /// along with a failure, so the retry only runs the handlers that failed.
+    async fn _dispatch(&self, message: &OutboxMessage) -> Result<(), AppError> {
+        let mut handled_by = message.handled_by.clone();
+        let mut errors = Vec::new();
+        for handler in &self.handlers {
+            if handled_by.iter().any(|name| name == handler.name()) {
+                continue;
+            }
+            match handler.handle(&message.event).await {
+                Ok(()) => handled_by.push(handler.name().to_string()),
+                Err(err) => {
+                    warn!(
+                        "Event handler '{}' failed - event_id: {}, attempt: {}, error: {err}",
+                        handler.name(),
+                        message.id,
+                        message.attempts
+                    );
+                    errors.push(format!("{}: {err}", handler.name()));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            self.repo.mark_done(message.id).await?;
+            return Ok(());
+        }
+
+        let retry_at = if message.attempts >= self.max_attempts {
+            error!(
+                "Giving up on event after {} attempts - event_id: {}, type: {}",
+                message.attempts,
+                message.id,
+                message.event.event_type()
+            );
+            None
+        } else {
+            Some(Utc::now() + self._backoff(message.attempts))
+        };
+        self.repo
+            .mark_failed(message.id, &errors.join("; "), &handled_by, retry_at)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OutboxDispatcherTrait for OutboxDispatcher {
+    async fn dispatch_batch(&self) -> Result<usize, AppError> {
+        let messages = self.repo.claim_batch(self.batch_size, self.lease).await?;
+
+        for message in &messages {
+            self._dispatch(message).await?;
+        }
+
+        Ok(messages.len())
+    }
+}
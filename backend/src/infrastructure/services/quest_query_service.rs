@@ -0,0 +1,92 @@
+// TODO move to application layer at some point
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        dtos::quest::QuestDTO,
+        exceptions::{AppError, ErrorCode},
+        policy::{LockAction, authorize_quest, membership_role},
+        services::quest_query_service::QuestQueryServiceTrait,
+    },
+    domain::{
+        auth::entity::Principal,
+        lock::repository::{LockRepository as LockRepositoryInterface, ReadPreference},
+        membership::repository::LockMembershipRepository as LockMembershipRepositoryInterface,
+        quest::repository::QuestRepository as QuestRepositoryInterface,
+    },
+};
+
+pub struct QuestQueryService {
+    pub repo: Arc<dyn QuestRepositoryInterface + Send + Sync>,
+    pub lock_repo: Arc<dyn LockRepositoryInterface + Send + Sync>,
+    pub membership_repo: Arc<dyn LockMembershipRepositoryInterface + Send + Sync>,
+}
+
+impl QuestQueryService {
+    pub fn create(
+        quest_repo: Arc<dyn QuestRepositoryInterface>,
+        lock_repo: Arc<dyn LockRepositoryInterface>,
+        membership_repo: Arc<dyn LockMembershipRepositoryInterface>,
+    ) -> Arc<dyn QuestQueryServiceTrait> {
+        Arc::new(Self {
+            repo: quest_repo,
+            lock_repo,
+            membership_repo,
+        })
+    }
+
+    fn _parse_id(&self, quest_id: &str) -> Result<Uuid, AppError> {
+        match Uuid::try_parse(quest_id) {
+            Ok(id) => Ok(id),
+            Err(_) => Err(AppError::ValidationError(
+                ErrorCode::InvalidId,
+                quest_id.to_string(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl QuestQueryServiceTrait for QuestQueryService {
+    async fn get_quest_by_id(
+        &self,
+        principal: &Principal,
+        quest_id: String,
+    ) -> Result<QuestDTO, AppError> {
+        info!(
+            "Get quest by id - user_id: {}, quest_id: {quest_id}",
+            principal.subject
+        );
+        let parsed_quest_id = self._parse_id(&quest_id)?;
+
+        let quest = self
+            .repo
+            .get_by_id(parsed_quest_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| {
+                AppError::NotFound(ErrorCode::QuestNotFound, "Quest not found".to_string())
+            })?;
+
+        let lock = self
+            .lock_repo
+            .get_by_id(quest.lock_id, ReadPreference::Primary)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| {
+                AppError::NotFound(ErrorCode::LockNotFound, "Lock not found".to_string())
+            })?;
+        let membership = membership_role(self.membership_repo.as_ref(), principal, &lock).await?;
+        let relationship = authorize_quest(principal, &lock, membership, LockAction::View)?;
+
+        if relationship.sees_secrets() {
+            Ok(QuestDTO::from(quest))
+        } else {
+            Ok(QuestDTO::redacted(quest))
+        }
+    }
+}
@@ -0,0 +1,187 @@
+// TODO move to application layer at some point
+use std::{str::FromStr, sync::Arc};
+
+use async_trait::async_trait;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        dtos::{lock::LockDTO, quest::QuestDTO},
+        exceptions::{AppError, ErrorCode},
+        policy::{LockAction, authorize_quest, membership_role},
+        precondition::LockPrecondition,
+        services::{
+            event_stream_service::EventStreamServiceTrait,
+            metrics_service::{AttemptOutcome, MetricsServiceTrait},
+            quest_service::QuestServiceTrait,
+        },
+    },
+    domain::{
+        auth::entity::Principal,
+        event::entity::DomainEventKind,
+        lock::{
+            entity::Lock,
+            repository::{LockRepository as LockRepositoryInterface, ReadPreference},
+        },
+        membership::repository::LockMembershipRepository as LockMembershipRepositoryInterface,
+        quest::{
+            entity::Quest, enums::QuestStatus,
+            repository::QuestRepository as QuestRepositoryInterface,
+        },
+    },
+};
+
+pub struct QuestService {
+    pub repo: Arc<dyn QuestRepositoryInterface + Send + Sync>,
+    pub lock_repo: Arc<dyn LockRepositoryInterface + Send + Sync>,
+    pub membership_repo: Arc<dyn LockMembershipRepositoryInterface + Send + Sync>,
+    /// Where the events of saved changes are published for live clients.
+    pub events: Arc<dyn EventStreamServiceTrait>,
+    pub metrics: Arc<dyn MetricsServiceTrait>,
+}
+
+impl QuestService {
+    pub fn create(
+        quest_repo: Arc<dyn QuestRepositoryInterface>,
+        lock_repo: Arc<dyn LockRepositoryInterface>,
+        membership_repo: Arc<dyn LockMembershipRepositoryInterface>,
+        events: Arc<dyn EventStreamServiceTrait>,
+        metrics: Arc<dyn MetricsServiceTrait>,
+    ) -> Arc<dyn QuestServiceTrait> {
+        Arc::new(Self {
+            repo: quest_repo,
+            lock_repo,
+            membership_repo,
+            events,
+            metrics,
+        })
+    }
+
+    fn _parse_id(&self, quest_id: &str) -> Result<Uuid, AppError> {
+        match Uuid::try_parse(quest_id) {
+            Ok(id) => Ok(id),
+            Err(_) => Err(AppError::ValidationError(
+                ErrorCode::InvalidId,
+                quest_id.to_string(),
+            )),
+        }
+    }
+
+    fn _parse_status(&self, status: &str) -> Result<QuestStatus, AppError> {
+        match QuestStatus::from_str(status) {
+            Ok(status) => Ok(status),
+            Err(err) => Err(AppError::ValidationError(
+                ErrorCode::InvalidQuestStatus,
+                err.to_string(),
+            )),
+        }
+    }
+
+    /// Load a quest and its parent lock, authorising the action through the lock.
+    async fn _get_authorised_quest(
+        &self,
+        principal: &Principal,
+        quest_id: &str,
+        action: LockAction,
+    ) -> Result<(Quest, Lock), AppError> {
+        let parsed_quest_id = self._parse_id(quest_id)?;
+
+        let quest = self
+            .repo
+            .get_by_id(parsed_quest_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| {
+                AppError::NotFound(ErrorCode::QuestNotFound, "Quest not found".to_string())
+            })?;
+
+        let lock = self
+            .lock_repo
+            .get_by_id(quest.lock_id, ReadPreference::Primary)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| {
+                AppError::NotFound(ErrorCode::LockNotFound, "Lock not found".to_string())
+            })?;
+        let membership = membership_role(self.membership_repo.as_ref(), principal, &lock).await?;
+        authorize_quest(principal, &lock, membership, action)?;
+
+        Ok((quest, lock))
+    }
+
+    async fn _update_quest_status(
+        &self,
+        principal: &Principal,
+        quest_id: String,
+        status: String,
+        precondition: LockPrecondition,
+    ) -> Result<(Quest, Lock), AppError> {
+        info!(
+            "Update quest status - user_id: {}, quest_id: {quest_id}, status: {status}",
+            principal.subject
+        );
+        let status = self._parse_status(&status)?;
+        let (mut quest, mut lock) = self
+            ._get_authorised_quest(principal, &quest_id, LockAction::Edit)
+            .await?;
+        precondition.check(&lock)?;
+
+        if !quest.status.can_transition_to(&status) {
+            return Err(AppError::ValidationError(
+                ErrorCode::InvalidStatusTransition,
+                format!("Quest cannot move from {} to {}", quest.status, status),
+            ));
+        }
+        quest.record(
+            lock.user_id.clone(),
+            DomainEventKind::QuestStatusChanged {
+                quest_id: quest.id,
+                from: quest.status.clone(),
+                to: status.clone(),
+            },
+        );
+        quest.status = status;
+
+        // None of the owner's transitions complete a quest, so the lock can't become
+        // unlockable here.
+        if let Some(lock_quest) = lock.quests.iter_mut().find(|q| q.id == quest.id) {
+            lock_quest.status = quest.status.clone();
+        }
+
+        let saved = self.repo.save(&quest, lock.version).await.map_err(|err| {
+            tracing::error!("Error updating quest status: {err}");
+            AppError::DatabaseError(err)
+        })?;
+        if !saved {
+            return Err(precondition.failed());
+        }
+        self.events.publish(&quest.events);
+        lock.version += 1;
+
+        Ok((quest, lock))
+    }
+}
+
+#[async_trait]
+impl QuestServiceTrait for QuestService {
+    async fn update_quest_status(
+        &self,
+        principal: &Principal,
+        quest_id: String,
+        status: String,
+        precondition: LockPrecondition,
+    ) -> Result<(QuestDTO, LockDTO), AppError> {
+        let result = self
+            ._update_quest_status(principal, quest_id, status, precondition)
+            .await;
+        let outcome = match &result {
+            Ok(_) => AttemptOutcome::Updated,
+            Err(err) => AttemptOutcome::from_error(err),
+        };
+        self.metrics.quest_attempted(outcome);
+
+        // Only owners may update a quest, they see the whole lock.
+        result.map(|(quest, lock)| (QuestDTO::from(quest), LockDTO::from(lock)))
+    }
+}
@@ -0,0 +1,150 @@
+// TODO move to application layer at some point
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Duration;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        dtos::token::{CreatedPersonalAccessTokenDTO, PersonalAccessTokenDTO},
+        exceptions::{AppError, ErrorCode},
+        services::token_service::TokenServiceTrait,
+    },
+    domain::auth::{
+        entity::{PersonalAccessToken, TOKEN_SCOPES},
+        repository::PersonalAccessTokenRepository as PersonalAccessTokenRepositoryInterface,
+    },
+};
+
+/// Longest lifetime a personal access token can be created with.
+const MAX_EXPIRES_IN_DAYS: u32 = 365;
+
+pub struct TokenService {
+    pub repo: Arc<dyn PersonalAccessTokenRepositoryInterface>,
+}
+
+impl TokenService {
+    pub fn create(
+        token_repo: Arc<dyn PersonalAccessTokenRepositoryInterface>,
+    ) -> Arc<dyn TokenServiceTrait> {
+        Arc::new(Self { repo: token_repo })
+    }
+
+    fn _parse_id(&self, token_id: &str) -> Result<Uuid, AppError> {
+        match Uuid::try_parse(token_id) {
+            Ok(id) => Ok(id),
+            Err(_) => Err(AppError::ValidationError(
+                ErrorCode::InvalidId,
+                token_id.to_string(),
+            )),
+        }
+    }
+
+    fn _validate(
+        &self,
+        name: &str,
+        scopes: &[String],
+        expires_in_days: u32,
+    ) -> Result<(), AppError> {
+        if name.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                ErrorCode::InvalidTokenName,
+                "Token name must not be empty".to_string(),
+            ));
+        }
+        if scopes.is_empty() {
+            return Err(AppError::ValidationError(
+                ErrorCode::InvalidScope,
+                "Token needs at least one scope".to_string(),
+            ));
+        }
+        if let Some(scope) = scopes
+            .iter()
+            .find(|scope| !TOKEN_SCOPES.contains(&scope.as_str()))
+        {
+            return Err(AppError::ValidationError(
+                ErrorCode::InvalidScope,
+                format!(
+                    "Unknown scope '{scope}', expected one of {}",
+                    TOKEN_SCOPES.join(", ")
+                ),
+            ));
+        }
+        if expires_in_days == 0 || expires_in_days > MAX_EXPIRES_IN_DAYS {
+            return Err(AppError::ValidationError(
+                ErrorCode::InvalidExpiry,
+                format!("Token expiry must be between 1 and {MAX_EXPIRES_IN_DAYS} days"),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TokenServiceTrait for TokenService {
+    async fn create_token(
+        &self,
+        user_id: String,
+        name: String,
+        mut scopes: Vec<String>,
+        expires_in_days: u32,
+    ) -> Result<CreatedPersonalAccessTokenDTO, AppError> {
+        info!("Create token - user_id: {user_id}, name: {name}, scopes: {scopes:?}");
+        self._validate(&name, &scopes, expires_in_days)?;
+        scopes.sort();
+        scopes.dedup();
+
+        let (token, secret) = PersonalAccessToken::generate(
+            user_id,
+            name.trim().to_string(),
+            scopes,
+            Duration::days(expires_in_days as i64),
+        );
+
+        if let Err(err) = self.repo.save(&token).await {
+            tracing::error!("Error creating token: {err}");
+            return Err(AppError::DatabaseError(err));
+        }
+
+        Ok(CreatedPersonalAccessTokenDTO {
+            token: PersonalAccessTokenDTO::from(token),
+            secret,
+        })
+    }
+
+    async fn list_tokens(&self, user_id: String) -> Result<Vec<PersonalAccessTokenDTO>, AppError> {
+        info!("List tokens - user_id: {user_id}");
+        let tokens = self
+            .repo
+            .get_by_user_id(user_id)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(tokens
+            .into_iter()
+            .map(PersonalAccessTokenDTO::from)
+            .collect())
+    }
+
+    async fn revoke_token(&self, user_id: String, token_id: String) -> Result<(), AppError> {
+        info!("Revoke token - user_id: {user_id}, token_id: {token_id}");
+        let parsed_token_id = self._parse_id(&token_id)?;
+
+        let revoked = self
+            .repo
+            .revoke(parsed_token_id, user_id)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        // Someone else's token looks the same as a missing one.
+        if !revoked {
+            return Err(AppError::NotFound(
+                ErrorCode::TokenNotFound,
+                "Token not found".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
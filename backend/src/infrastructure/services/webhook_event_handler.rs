@@ -0,0 +1,53 @@
+// TODO move to application layer at some point
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    application::{exceptions::AppError, services::event_handler::EventHandlerTrait},
+    domain::{
+        event::entity::DomainEvent,
+        webhook::{
+            entity::WebhookDelivery, repository::WebhookRepository as WebhookRepositoryInterface,
+        },
+    },
+};
+
+/// Queues a webhook delivery for every subscription of the lock's owner that wants the event.
+/// The deliveries are sent by `WebhookService`, so a slow receiver doesn't hold up the outbox.
+pub struct WebhookEventHandler {
+    pub repo: Arc<dyn WebhookRepositoryInterface>,
+}
+
+impl WebhookEventHandler {
+    pub fn create(webhook_repo: Arc<dyn WebhookRepositoryInterface>) -> Arc<dyn EventHandlerTrait> {
+        Arc::new(Self { repo: webhook_repo })
+    }
+}
+
+#[async_trait]
+impl EventHandlerTrait for WebhookEventHandler {
+    fn name(&self) -> &'static str {
+        "webhooks"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let deliveries: Vec<WebhookDelivery> = self
+            .repo
+            .get_subscriptions_by_user_id(event.user_id.clone())
+            .await
+            .map_err(AppError::DatabaseError)?
+            .iter()
+            .filter(|subscription| subscription.wants(event))
+            .map(|subscription| WebhookDelivery::create(subscription, event))
+            .collect();
+
+        if !deliveries.is_empty() {
+            self.repo
+                .enqueue_deliveries(&deliveries)
+                .await
+                .map_err(AppError::DatabaseError)?;
+        }
+        Ok(())
+    }
+}
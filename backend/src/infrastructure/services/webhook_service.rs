@@ -0,0 +1,515 @@
+// TODO move to application layer at some point
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use reqwest::{
+    Url,
+    dns::{Addrs, Name, Resolve, Resolving},
+};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        dtos::webhook::{CreatedWebhookDTO, WebhookDTO, WebhookDeliveryDTO},
+        exceptions::{AppError, ErrorCode},
+        services::webhook_service::{
+            WEBHOOK_DELIVERY_HEADER, WEBHOOK_EVENT_HEADER, WEBHOOK_SIGNATURE_HEADER,
+            WEBHOOK_TIMESTAMP_HEADER, WebhookServiceTrait,
+        },
+    },
+    domain::{
+        event::entity::EVENT_TYPES,
+        webhook::{
+            entity::{WebhookDelivery, WebhookSubscription},
+            repository::WebhookRepository as WebhookRepositoryInterface,
+        },
+    },
+    infrastructure::exceptions::InfrastructureError,
+};
+
+/// Wait before the first retry, doubled on each following one.
+const BACKOFF_BASE_SECS: i64 = 30;
+/// Longest wait between two attempts of the same delivery.
+const MAX_BACKOFF_SECS: i64 = 6 * 3600;
+const MAX_URL_LENGTH: usize = 2048;
+
+/// Whether `ip` is reachable from the internet at large. Anything else, loopback, private
+/// networks, link-local addresses such as the cloud metadata endpoint 169.254.169.254, is
+/// off-limits to webhooks, they would let users reach services behind our firewall.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                // 0.0.0.0/8, "this network"
+                || a == 0
+                // 100.64.0.0/10, carrier-grade NAT
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(ip) => match embedded_ipv4(ip) {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+/// The IPv4 address an IPv6 one stands for: IPv4-mapped (`::ffff:0:0/96`) and IPv4-compatible
+/// (`::/96`) addresses, NAT64 (`64:ff9b::/96`) and 6to4 (`2002::/16`). Such an address reaches
+/// the IPv4 one, so it is only as public as that.
+fn embedded_ipv4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    match ip.segments() {
+        [0x64, 0xff9b, 0, 0, 0, 0, high, low] | [0x2002, high, low, ..] => {
+            Some(Ipv4Addr::from((u32::from(high) << 16) | u32::from(low)))
+        }
+        _ => ip.to_ipv4(),
+    }
+}
+
+/// The address of a URL host given as one, IPv6 addresses being in brackets.
+fn ip_host(host: &str) -> Option<IpAddr> {
+    host.strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host)
+        .parse()
+        .ok()
+}
+
+/// Checks the scheme, and that the host only has public addresses. Hosts given as an IP
+/// address never reach the resolver, they are checked here.
+fn check_target(url: &Url, require_https: bool) -> Result<(), String> {
+    match url.scheme() {
+        "https" => {}
+        "http" if !require_https => {}
+        _ if require_https => {
+            return Err("Webhook URL must be an https URL".to_string());
+        }
+        _ => return Err("Webhook URL must be an http or https URL".to_string()),
+    }
+    let Some(host) = url.host_str() else {
+        return Err("Webhook URL must have a host".to_string());
+    };
+    match ip_host(host) {
+        Some(ip) if !is_public(ip) => Err(format!("{ip} isn't a public address")),
+        _ => Ok(()),
+    }
+}
+
+/// The addresses `host` resolves to, refused if any of them isn't public.
+async fn resolve_public(host: &str, port: u16) -> Result<Vec<SocketAddr>, String> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("Failed to resolve {host}: {e}"))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("{host} doesn't resolve to any address"));
+    }
+    if let Some(addr) = addrs.iter().find(|addr| !is_public(addr.ip())) {
+        return Err(format!(
+            "{host} resolves to {}, which isn't public",
+            addr.ip()
+        ));
+    }
+    Ok(addrs)
+}
+
+/// The error and its causes, reqwest only names the failed request in its own message.
+fn describe(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        source = err.source();
+    }
+    message
+}
+
+/// Resolves the hosts of webhook deliveries, refusing those that resolve to an address that
+/// isn't public. Checking when the webhook is registered isn't enough, the name can be pointed
+/// elsewhere afterwards.
+struct PublicAddressResolver;
+
+impl Resolve for PublicAddressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs = resolve_public(name.as_str(), 0).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+pub struct WebhookService {
+    pub repo: Arc<dyn WebhookRepositoryInterface>,
+    client: reqwest::Client,
+    pub batch_size: i64,
+    /// Attempts after which a failing delivery becomes a dead letter.
+    pub max_attempts: i32,
+    /// How long a claimed delivery stays hidden from other workers, see `OutboxDispatcher`.
+    pub lease: Duration,
+    /// Refuse plain http endpoints, everywhere but development.
+    pub require_https: bool,
+}
+
+impl WebhookService {
+    pub fn create(
+        webhook_repo: Arc<dyn WebhookRepositoryInterface>,
+        timeout: std::time::Duration,
+        batch_size: i64,
+        max_attempts: i32,
+        lease: Duration,
+        require_https: bool,
+    ) -> Result<Arc<dyn WebhookServiceTrait>, InfrastructureError> {
+        // A redirect would send the signed payload somewhere the user didn't ask for.
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(PublicAddressResolver))
+            .build()
+            .map_err(|e| InfrastructureError::HttpClientError(e.to_string()))?;
+
+        Ok(Arc::new(Self {
+            repo: webhook_repo,
+            client,
+            batch_size,
+            max_attempts,
+            lease,
+            require_https,
+        }))
+    }
+
+    fn _parse_id(&self, id: &str) -> Result<Uuid, AppError> {
+        match Uuid::try_parse(id) {
+            Ok(id) => Ok(id),
+            Err(_) => Err(AppError::ValidationError(
+                ErrorCode::InvalidId,
+                id.to_string(),
+            )),
+        }
+    }
+
+    async fn _validate(&self, url: &str, event_types: &[String]) -> Result<(), AppError> {
+        let parsed = Url::parse(url).map_err(|e| {
+            AppError::ValidationError(ErrorCode::InvalidWebhookUrl, format!("Invalid URL: {e}"))
+        })?;
+        check_target(&parsed, self.require_https)
+            .map_err(|message| AppError::ValidationError(ErrorCode::InvalidWebhookUrl, message))?;
+        if let Some(host) = parsed.host_str().filter(|host| ip_host(host).is_none()) {
+            let port = parsed.port_or_known_default().unwrap_or(443);
+            resolve_public(host, port).await.map_err(|message| {
+                AppError::ValidationError(ErrorCode::InvalidWebhookUrl, message)
+            })?;
+        }
+        if url.len() > MAX_URL_LENGTH {
+            return Err(AppError::ValidationError(
+                ErrorCode::InvalidWebhookUrl,
+                format!("Webhook URL must be at most {MAX_URL_LENGTH} characters"),
+            ));
+        }
+        if let Some(event_type) = event_types
+            .iter()
+            .find(|event_type| !EVENT_TYPES.contains(&event_type.as_str()))
+        {
+            return Err(AppError::ValidationError(
+                ErrorCode::InvalidEventType,
+                format!(
+                    "Unknown event type '{event_type}', expected one of {}",
+                    EVENT_TYPES.join(", ")
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Exponential backoff, `BACKOFF_BASE_SECS * 2^(attempts - 1)` capped at `MAX_BACKOFF_SECS`.
+    fn _backoff(&self, attempts: i32) -> Duration {
+        let secs = 2_i64
+            .checked_pow((attempts - 1).max(0) as u32)
+            .and_then(|factor| factor.checked_mul(BACKOFF_BASE_SECS))
+            .unwrap_or(MAX_BACKOFF_SECS);
+        Duration::seconds(secs.min(MAX_BACKOFF_SECS))
+    }
+
+    async fn _send(
+        &self,
+        subscription: &WebhookSubscription,
+        delivery: &WebhookDelivery,
+    ) -> Result<(), String> {
+        let url = Url::parse(&subscription.url).map_err(|e| e.to_string())?;
+        check_target(&url, self.require_https)?;
+        let body = serde_json::to_vec(&delivery.event).map_err(|e| e.to_string())?;
+        let timestamp = Utc::now().timestamp();
+        let signature = subscription.sign(timestamp, &body);
+
+        let res = self
+            .client
+            .post(url)
+            .header("content-type", "application/json")
+            .header(WEBHOOK_EVENT_HEADER, delivery.event.event_type())
+            .header(WEBHOOK_DELIVERY_HEADER, delivery.id.to_string())
+            .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+            .header(WEBHOOK_SIGNATURE_HEADER, format!("sha256={signature}"))
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| describe(&e))?;
+
+        if res.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("Receiver responded with {}", res.status()))
+        }
+    }
+
+    async fn _deliver(
+        &self,
+        subscription: &WebhookSubscription,
+        delivery: &WebhookDelivery,
+    ) -> Result<(), AppError> {
+        let Err(err) = self._send(subscription, delivery).await else {
+            self.repo
+                .mark_delivered(delivery.id)
+                .await
+                .map_err(AppError::DatabaseError)?;
+            return Ok(());
+        };
+        warn!(
+            "Webhook delivery failed - delivery_id: {}, attempt: {}, error: {err}",
+            delivery.id, delivery.attempts
+        );
+
+        let retry_at = if delivery.attempts >= self.max_attempts {
+            error!(
+                "Giving up on webhook delivery after {} attempts - delivery_id: {}, user_id: {}",
+                delivery.attempts, delivery.id, delivery.user_id
+            );
+            None
+        } else {
+            Some(Utc::now() + self._backoff(delivery.attempts))
+        };
+        self.repo
+            .mark_failed(delivery.id, &err, retry_at)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WebhookServiceTrait for WebhookService {
+    async fn create_webhook(
+        &self,
+        user_id: String,
+        url: String,
+        event_types: Vec<String>,
+    ) -> Result<CreatedWebhookDTO, AppError> {
+        let url = url.trim().to_string();
+        let mut event_types = event_types;
+        event_types.sort();
+        event_types.dedup();
+        self._validate(&url, &event_types).await?;
+
+        info!("Create webhook - user_id: {}", user_id);
+        let subscription = WebhookSubscription::generate(user_id, url, event_types);
+        self.repo
+            .save_subscription(&subscription)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        let secret = subscription.secret.clone();
+        Ok(CreatedWebhookDTO {
+            webhook: WebhookDTO::from(subscription),
+            secret,
+        })
+    }
+
+    async fn list_webhooks(&self, user_id: String) -> Result<Vec<WebhookDTO>, AppError> {
+        let subscriptions = self
+            .repo
+            .get_subscriptions_by_user_id(user_id)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(subscriptions.into_iter().map(WebhookDTO::from).collect())
+    }
+
+    async fn delete_webhook(&self, user_id: String, webhook_id: String) -> Result<(), AppError> {
+        info!(
+            "Delete webhook - user_id: {}, webhook_id: {webhook_id}",
+            user_id
+        );
+        let id = self._parse_id(&webhook_id)?;
+
+        let deleted = self
+            .repo
+            .delete_subscription(id, user_id)
+            .await
+            .map_err(AppError::DatabaseError)?;
+        if !deleted {
+            return Err(AppError::NotFound(
+                ErrorCode::WebhookNotFound,
+                "Webhook not found".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn list_dead_letters(
+        &self,
+        user_id: String,
+    ) -> Result<Vec<WebhookDeliveryDTO>, AppError> {
+        let deliveries = self
+            .repo
+            .get_dead_letters(user_id)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(deliveries
+            .into_iter()
+            .map(WebhookDeliveryDTO::from)
+            .collect())
+    }
+
+    async fn replay_dead_letter(
+        &self,
+        user_id: String,
+        delivery_id: String,
+    ) -> Result<WebhookDeliveryDTO, AppError> {
+        info!(
+            "Replay webhook delivery - user_id: {}, delivery_id: {delivery_id}",
+            user_id
+        );
+        let id = self._parse_id(&delivery_id)?;
+
+        self.repo
+            .replay_dead_letter(id, user_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .map(WebhookDeliveryDTO::from)
+            .ok_or_else(|| {
+                AppError::NotFound(
+                    ErrorCode::DeadLetterNotFound,
+                    "Dead letter not found".to_string(),
+                )
+            })
+    }
+
+    async fn deliver_batch(&self) -> Result<usize, AppError> {
+        let deliveries = self
+            .repo
+            .claim_deliveries(self.batch_size, self.lease)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        let mut subscriptions: HashMap<Uuid, Option<WebhookSubscription>> = HashMap::new();
+        for delivery in &deliveries {
+            let subscription = match subscriptions.entry(delivery.subscription_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(
+                    self.repo
+                        .get_subscription(delivery.subscription_id)
+                        .await
+                        .map_err(AppError::DatabaseError)?,
+                ),
+            };
+            // Deleted meanwhile, its deliveries went with it.
+            let Some(subscription) = subscription else {
+                continue;
+            };
+            self._deliver(subscription, delivery).await?;
+        }
+
+        Ok(deliveries.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_public_addresses_are_public() {
+        let cases = [
+            ("93.184.216.34", true),
+            ("2606:2800:220:1:248:1893:25c8:1946", true),
+            ("127.0.0.1", false),
+            ("10.0.0.5", false),
+            ("172.16.0.1", false),
+            ("192.168.1.1", false),
+            ("169.254.169.254", false),
+            ("100.64.0.1", false),
+            ("0.0.0.0", false),
+            ("255.255.255.255", false),
+            ("224.0.0.1", false),
+            ("::", false),
+            ("::1", false),
+            ("fd00::1", false),
+            ("fe80::1", false),
+            ("ff02::1", false),
+            // IPv4-mapped
+            ("::ffff:169.254.169.254", false),
+            ("::ffff:93.184.216.34", true),
+            // IPv4-compatible
+            ("::a9fe:a9fe", false),
+            ("::7f00:1", false),
+            // NAT64
+            ("64:ff9b::a9fe:a9fe", false),
+            ("64:ff9b::a00:5", false),
+            ("64:ff9b::5db8:d822", true),
+            // 6to4
+            ("2002:a9fe:a9fe::1", false),
+            ("2002:c0a8:101::1", false),
+            ("2002:5db8:d822::1", true),
+        ];
+        for (ip, public) in cases {
+            assert_eq!(is_public(ip.parse().unwrap()), public, "{ip}");
+        }
+    }
+
+    #[test]
+    fn check_target_refuses_other_schemes_and_private_hosts() {
+        let cases = [
+            ("https://example.com/hook", true, true),
+            ("http://example.com/hook", false, true),
+            ("http://example.com/hook", true, false),
+            ("ftp://example.com/hook", false, false),
+            ("ftp://example.com/hook", true, false),
+            ("https://93.184.216.34/hook", true, true),
+            ("https://127.0.0.1/hook", true, false),
+            ("https://169.254.169.254/latest/meta-data", true, false),
+            ("https://[::1]/hook", true, false),
+            ("https://[::ffff:a9fe:a9fe]/hook", true, false),
+            ("https://[64:ff9b::a9fe:a9fe]/hook", true, false),
+            ("https://[2002:a00:5::1]/hook", true, false),
+            // Names are checked once resolved.
+            ("https://localhost/hook", true, true),
+        ];
+        for (url, require_https, allowed) in cases {
+            let result = check_target(&Url::parse(url).unwrap(), require_https);
+            assert_eq!(result.is_ok(), allowed, "{url}: {result:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn names_resolving_to_private_addresses_are_refused() {
+        assert!(resolve_public("localhost", 443).await.is_err());
+    }
+}
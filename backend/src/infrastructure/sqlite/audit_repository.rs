@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use crate::domain::audit::{
+    entity::AuditRecord, repository::AuditRepository as AuditRepositoryInterface,
+};
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite, Transaction, types::Json};
+
+#[derive(Debug, Clone)]
+pub struct AuditRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl AuditRepository {
+    pub fn create(pool: Pool<Sqlite>) -> Arc<dyn AuditRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+
+    /// Write an audit record as part of the caller's transaction.
+    pub async fn insert(
+        tx: &mut Transaction<'_, Sqlite>,
+        record: &AuditRecord,
+    ) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(
+            r#"
+            INSERT INTO admin_audit_log (
+                id, operator, action, target_id, reason, details, created_at
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7
+            )
+            "#,
+        )
+        .bind(record.id.to_string())
+        .bind(&record.operator)
+        .bind(record.action.name())
+        .bind(&record.target_id)
+        .bind(&record.reason)
+        .bind(Json(&record.action))
+        .bind(record.created_at)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl AuditRepositoryInterface for AuditRepository {
+    async fn save(&self, record: &AuditRecord) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let saved = Self::insert(&mut tx, record).await?;
+        tx.commit().await?;
+
+        Ok(saved)
+    }
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use crate::domain::idempotency::{
+    entity::{IdempotencyRecord, StoredResponse},
+    repository::IdempotencyRepository as IdempotencyRepositoryInterface,
+};
+use crate::infrastructure::sqlite::models::IdempotencyKeyRow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Sqlite, types::Json};
+
+#[derive(Debug, Clone)]
+pub struct IdempotencyKeyRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl IdempotencyKeyRepository {
+    pub fn create(pool: Pool<Sqlite>) -> Arc<dyn IdempotencyRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+}
+
+#[async_trait]
+impl IdempotencyRepositoryInterface for IdempotencyKeyRepository {
+    async fn reserve(&self, record: &IdempotencyRecord) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (
+                user_id, idempotency_key, fingerprint, created_at, expires_at
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5
+            )
+            ON CONFLICT (user_id, idempotency_key) DO NOTHING
+            "#,
+        )
+        .bind(&record.user_id)
+        .bind(&record.key)
+        .bind(&record.fingerprint)
+        .bind(record.created_at)
+        .bind(record.expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn get(
+        &self,
+        user_id: String,
+        key: String,
+    ) -> Result<Option<IdempotencyRecord>, sqlx::Error> {
+        let row = sqlx::query_as::<_, IdempotencyKeyRow>(
+            r#"SELECT user_id, idempotency_key, fingerprint, response_status,
+                response_content_type, response_headers, response_body, created_at, expires_at
+            FROM idempotency_keys
+            WHERE user_id = ?1 AND idempotency_key = ?2"#,
+        )
+        .bind(user_id)
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(IdempotencyRecord::try_from).transpose()
+    }
+
+    async fn save_response(
+        &self,
+        user_id: String,
+        key: String,
+        response: &StoredResponse,
+    ) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(
+            r#"UPDATE idempotency_keys
+            SET response_status = ?3, response_content_type = ?4, response_headers = ?5,
+                response_body = ?6
+            WHERE user_id = ?1 AND idempotency_key = ?2"#,
+        )
+        .bind(user_id)
+        .bind(key)
+        .bind(i64::from(response.status))
+        .bind(&response.content_type)
+        .bind(Json(&response.headers))
+        .bind(&response.body)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn delete(&self, user_id: String, key: String) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(
+            r#"DELETE FROM idempotency_keys WHERE user_id = ?1 AND idempotency_key = ?2"#,
+        )
+        .bind(user_id)
+        .bind(key)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let res = sqlx::query(
+            r#"DELETE FROM idempotency_keys WHERE julianday(expires_at) <= julianday(?1)"#,
+        )
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+}
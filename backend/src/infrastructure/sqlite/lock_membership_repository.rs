@@ -0,0 +1,156 @@
+use std::{str::FromStr, sync::Arc};
+
+use crate::domain::membership::{
+    entity::{LockMembership, MembershipRole},
+    repository::LockMembershipRepository as LockMembershipRepositoryInterface,
+};
+use crate::infrastructure::sqlite::models::LockMembershipRow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Sqlite};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct LockMembershipRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl LockMembershipRepository {
+    pub fn create(pool: Pool<Sqlite>) -> Arc<dyn LockMembershipRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+}
+
+#[async_trait]
+impl LockMembershipRepositoryInterface for LockMembershipRepository {
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<LockMembership>, sqlx::Error> {
+        let row = sqlx::query_as::<_, LockMembershipRow>(
+            r#"SELECT id, lock_id, user_id, role, invited_by, created_at, accepted_at
+            FROM lock_memberships
+            WHERE id = ?1"#,
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(LockMembership::try_from).transpose()
+    }
+
+    async fn get_by_lock_id(&self, lock_id: Uuid) -> Result<Vec<LockMembership>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, LockMembershipRow>(
+            r#"SELECT id, lock_id, user_id, role, invited_by, created_at, accepted_at
+            FROM lock_memberships
+            WHERE lock_id = ?1
+            ORDER BY julianday(created_at)"#,
+        )
+        .bind(lock_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(LockMembership::try_from).collect()
+    }
+
+    async fn get_pending_by_user_id(
+        &self,
+        user_id: String,
+    ) -> Result<Vec<LockMembership>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, LockMembershipRow>(
+            r#"SELECT m.id, m.lock_id, m.user_id, m.role, m.invited_by, m.created_at,
+                m.accepted_at
+            FROM lock_memberships m
+            JOIN locks l ON l.id = m.lock_id
+            WHERE m.user_id = ?1 AND m.accepted_at IS NULL AND l.deleted_at IS NULL
+            ORDER BY julianday(m.created_at)"#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(LockMembership::try_from).collect()
+    }
+
+    async fn get_by_user_id(&self, user_id: String) -> Result<Vec<LockMembership>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, LockMembershipRow>(
+            r#"SELECT id, lock_id, user_id, role, invited_by, created_at, accepted_at
+            FROM lock_memberships
+            WHERE user_id = ?1
+            ORDER BY julianday(created_at)"#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(LockMembership::try_from).collect()
+    }
+
+    async fn get_role(
+        &self,
+        lock_id: Uuid,
+        user_id: String,
+    ) -> Result<Option<MembershipRole>, sqlx::Error> {
+        let role = sqlx::query_scalar::<_, String>(
+            r#"SELECT role FROM lock_memberships
+            WHERE lock_id = ?1 AND user_id = ?2 AND accepted_at IS NOT NULL"#,
+        )
+        .bind(lock_id.to_string())
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        role.map(|role| MembershipRole::from_str(&role))
+            .transpose()
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+    }
+
+    async fn save(&self, membership: &LockMembership) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(
+            r#"
+            INSERT INTO lock_memberships (
+                id, lock_id, user_id, role, invited_by, created_at, accepted_at
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7
+            )
+            ON CONFLICT (lock_id, user_id) DO NOTHING
+            "#,
+        )
+        .bind(membership.id.to_string())
+        .bind(membership.lock_id.to_string())
+        .bind(&membership.user_id)
+        .bind(membership.role.to_string())
+        .bind(&membership.invited_by)
+        .bind(membership.created_at)
+        .bind(membership.accepted_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn accept(
+        &self,
+        id: Uuid,
+        user_id: String,
+        accepted_at: DateTime<Utc>,
+    ) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(
+            r#"UPDATE lock_memberships SET accepted_at = ?3
+            WHERE id = ?1 AND user_id = ?2 AND accepted_at IS NULL"#,
+        )
+        .bind(id.to_string())
+        .bind(user_id)
+        .bind(accepted_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(r#"DELETE FROM lock_memberships WHERE id = ?1"#)
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+}
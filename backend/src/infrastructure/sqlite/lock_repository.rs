@@ -0,0 +1,365 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::{
+    event::entity::DomainEvent,
+    lock::entity::Lock,
+    lock::repository::{LockRepository as LockRepositoryInterface, ReadPreference},
+};
+use crate::infrastructure::exceptions::InfrastructureError;
+use crate::infrastructure::models::{LockModel, LockWithQuests, QuestModel};
+use crate::infrastructure::sqlite::models::{FlatLockQuestRow, LockRow, QuestRow, parse_uuid};
+use crate::infrastructure::sqlite::outbox_repository::OutboxRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Sqlite, Transaction, types::Json};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct LockRepository {
+    pool: Pool<Sqlite>,
+    /// Replica for reads that tolerate lag, the primary pool when no replica is configured.
+    read_pool: Pool<Sqlite>,
+}
+
+impl LockRepository {
+    pub fn create(
+        pool: Pool<Sqlite>,
+        read_pool: Option<Pool<Sqlite>>,
+    ) -> Arc<dyn LockRepositoryInterface> {
+        let read_pool = read_pool.unwrap_or_else(|| pool.clone());
+        Arc::new(Self { pool, read_pool })
+    }
+
+    fn reader(&self, read: ReadPreference) -> &Pool<Sqlite> {
+        match read {
+            ReadPreference::Primary => &self.pool,
+            ReadPreference::Replica => &self.read_pool,
+        }
+    }
+
+    /// Write the lock, its quests and their events within `tx`.
+    async fn save_in(tx: &mut Transaction<'_, Sqlite>, lock: &Lock) -> Result<bool, sqlx::Error> {
+        let lock_res = sqlx::query(
+            r#"
+            INSERT INTO locks (
+                id, user_id, label, total_shares, threshold
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5
+            )
+            ON CONFLICT (id) DO UPDATE SET
+                label = excluded.label,
+                updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'),
+                version = locks.version + 1
+            WHERE locks.deleted_at IS NULL
+            "#,
+        )
+        .bind(lock.id.to_string())
+        .bind(&lock.user_id)
+        .bind(&lock.label)
+        .bind(lock.total_shares as i16)
+        .bind(lock.threshold as i16)
+        .execute(&mut **tx)
+        .await?;
+
+        for quest in &lock.quests {
+            sqlx::query(
+                r#"
+                INSERT INTO quests (
+                    id, lock_id, share, quest_type, status, data
+                ) VALUES (
+                    ?1, ?2, ?3, ?4, ?5, ?6
+                )
+                ON CONFLICT (id) DO UPDATE SET
+                    status = excluded.status,
+                    updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+                "#,
+            )
+            .bind(quest.id.to_string())
+            .bind(quest.lock_id.to_string())
+            .bind(&quest.share)
+            .bind(quest.quest_type.to_string())
+            .bind(quest.status.to_string())
+            .bind(Json(&quest.data))
+            .execute(&mut **tx)
+            .await?;
+
+            OutboxRepository::insert_events(tx, &quest.events).await?;
+        }
+        OutboxRepository::insert_events(tx, &lock.events).await?;
+
+        Ok(lock_res.rows_affected() > 0)
+    }
+
+    async fn with_quests(
+        &self,
+        pool: &Pool<Sqlite>,
+        lock_row: LockRow,
+    ) -> Result<Lock, sqlx::Error> {
+        let quest_rows = sqlx::query_as::<_, QuestRow>(
+            r#"SELECT id, lock_id, share, quest_type, status, data
+            FROM quests
+            WHERE lock_id = ?1
+            ORDER BY id"#,
+        )
+        .bind(&lock_row.id)
+        .fetch_all(pool)
+        .await?;
+
+        let quest_models: Result<Vec<QuestModel>, sqlx::Error> =
+            quest_rows.into_iter().map(QuestModel::try_from).collect();
+
+        let lock_with_quests = LockWithQuests {
+            lock: LockModel::try_from(lock_row)?,
+            quests: quest_models?,
+        };
+
+        Lock::try_from(lock_with_quests).map_err(|conv_err| sqlx::Error::Decode(Box::new(conv_err)))
+    }
+}
+
+#[async_trait]
+impl LockRepositoryInterface for LockRepository {
+    async fn get_by_id(&self, id: Uuid, read: ReadPreference) -> Result<Option<Lock>, sqlx::Error> {
+        let lock_row = sqlx::query_as::<_, LockRow>(
+            r#"SELECT id, user_id, label, total_shares, threshold, deleted_at, version
+            FROM locks
+            WHERE id = ?1 AND deleted_at IS NULL"#,
+        )
+        .bind(id.to_string())
+        .fetch_optional(self.reader(read))
+        .await?;
+
+        match lock_row {
+            Some(lock_row) => self
+                .with_quests(self.reader(read), lock_row)
+                .await
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_by_user_id(
+        &self,
+        user_id: String,
+        read: ReadPreference,
+    ) -> Result<Vec<Lock>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, FlatLockQuestRow>(
+            r#"
+            SELECT
+                l.id as lock_id,
+                l.user_id as lock_user_id,
+                l.label as lock_label,
+                l.total_shares as lock_total_shares,
+                l.threshold as lock_threshold,
+                l.version as lock_version,
+                q.id as quest_id,
+                q.share as quest_share,
+                q.quest_type,
+                q.status as quest_status,
+                q.data as quest_data
+            FROM
+                locks l
+            LEFT JOIN
+                quests q ON l.id = q.lock_id
+            WHERE
+                (
+                    l.user_id = ?1
+                    OR l.id IN (
+                        SELECT lock_id FROM lock_memberships
+                        WHERE user_id = ?1 AND accepted_at IS NOT NULL
+                    )
+                )
+                AND l.deleted_at IS NULL
+            ORDER BY
+                l.id, q.id
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(self.reader(read))
+        .await?;
+
+        let mut locks_with_quests_map: HashMap<Uuid, LockWithQuests> = HashMap::new();
+
+        for row in rows {
+            let lock_id = parse_uuid(&row.lock_id)?;
+            let lock_entry =
+                locks_with_quests_map
+                    .entry(lock_id)
+                    .or_insert_with(|| LockWithQuests {
+                        lock: LockModel::create(
+                            lock_id,
+                            row.lock_user_id.clone(),
+                            row.lock_label.clone(),
+                            row.lock_total_shares,
+                            row.lock_threshold,
+                            None,
+                            row.lock_version,
+                        ),
+                        quests: Vec::new(),
+                    });
+
+            if let (Some(quest_id), Some(share), Some(quest_type), Some(status), Some(data)) = (
+                row.quest_id,
+                row.quest_share,
+                row.quest_type,
+                row.quest_status,
+                row.quest_data,
+            ) {
+                let quest_model = QuestModel::create(
+                    parse_uuid(&quest_id)?,
+                    lock_id,
+                    share,
+                    quest_type,
+                    status,
+                    data,
+                );
+                lock_entry.quests.push(quest_model);
+            }
+        }
+
+        let locks_result: Result<Vec<Lock>, InfrastructureError> = locks_with_quests_map
+            .into_values()
+            .map(Lock::try_from)
+            .collect();
+
+        locks_result.map_err(|e| sqlx::Error::Decode(Box::new(e)))
+    }
+
+    async fn get_history(
+        &self,
+        lock_id: Uuid,
+        read: ReadPreference,
+    ) -> Result<Vec<DomainEvent>, sqlx::Error> {
+        let payloads = sqlx::query_scalar::<_, Json<DomainEvent>>(
+            r#"SELECT payload
+            FROM outbox
+            WHERE aggregate_id = ?1
+            ORDER BY julianday(created_at), id"#,
+        )
+        .bind(lock_id.to_string())
+        .fetch_all(self.reader(read))
+        .await?;
+
+        Ok(payloads.into_iter().map(|payload| payload.0).collect())
+    }
+
+    async fn save(&self, lock: &Lock) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        if !Self::save_in(&mut tx, lock).await? {
+            // Dropping the transaction rolls back the quests and events written with it.
+            return Ok(false);
+        }
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn save_all(&self, locks: &[Lock]) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        for lock in locks {
+            if !Self::save_in(&mut tx, lock).await? {
+                // Dropping the transaction rolls back the locks saved so far.
+                return Ok(false);
+            }
+        }
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn delete(&self, lock: &Lock) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let res = sqlx::query(
+            r#"UPDATE locks
+            SET deleted_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'),
+                updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'),
+                version = version + 1
+            WHERE id = ?1 AND deleted_at IS NULL AND version = ?2"#,
+        )
+        .bind(lock.id.to_string())
+        .bind(lock.version)
+        .execute(&mut *tx)
+        .await?;
+        if res.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        OutboxRepository::insert_events(&mut tx, &lock.events).await?;
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn get_deleted_by_id(&self, id: Uuid) -> Result<Option<Lock>, sqlx::Error> {
+        let lock_row = sqlx::query_as::<_, LockRow>(
+            r#"SELECT id, user_id, label, total_shares, threshold, deleted_at, version
+            FROM locks
+            WHERE id = ?1 AND deleted_at IS NOT NULL"#,
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match lock_row {
+            Some(lock_row) => self.with_quests(&self.pool, lock_row).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_deleted_by_user_id(&self, user_id: String) -> Result<Vec<Lock>, sqlx::Error> {
+        let lock_rows = sqlx::query_as::<_, LockRow>(
+            r#"SELECT id, user_id, label, total_shares, threshold, deleted_at, version
+            FROM locks
+            WHERE user_id = ?1 AND deleted_at IS NOT NULL
+            ORDER BY julianday(deleted_at)"#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut locks = Vec::new();
+        for lock_row in lock_rows {
+            locks.push(self.with_quests(&self.pool, lock_row).await?);
+        }
+        Ok(locks)
+    }
+
+    async fn restore(&self, lock: &Lock) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let res = sqlx::query(
+            r#"UPDATE locks
+            SET deleted_at = NULL,
+                updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'),
+                version = version + 1
+            WHERE id = ?1 AND deleted_at IS NOT NULL AND version = ?2"#,
+        )
+        .bind(lock.id.to_string())
+        .bind(lock.version)
+        .execute(&mut *tx)
+        .await?;
+        if res.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        OutboxRepository::insert_events(&mut tx, &lock.events).await?;
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        // Quests, and with them the shares, go through ON DELETE CASCADE.
+        let res = sqlx::query(
+            r#"DELETE FROM locks
+            WHERE deleted_at IS NOT NULL AND julianday(deleted_at) < julianday(?1)"#,
+        )
+        .bind(deleted_before)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+}
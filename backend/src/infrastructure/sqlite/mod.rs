@@ -0,0 +1,14 @@
+//! SQLite storage backend, for single-user self-hosting where a Postgres server is overkill.
+//!
+//! Mirrors the Postgres repositories one-to-one. Ids are stored as hyphenated text and
+//! timestamps as RFC 3339 text, see `migrations/sqlite`.
+pub mod account_repository;
+pub mod audit_repository;
+pub mod idempotency_key_repository;
+pub mod lock_membership_repository;
+pub mod lock_repository;
+pub mod models;
+pub mod outbox_repository;
+pub mod personal_access_token_repository;
+pub mod quest_repository;
+pub mod webhook_repository;
@@ -0,0 +1,268 @@
+use std::{collections::HashMap, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, types::Json};
+use uuid::Uuid;
+
+use crate::domain::account::entity::AccountErasure;
+use crate::domain::auth::entity::PersonalAccessToken;
+use crate::domain::event::entity::DomainEvent;
+use crate::domain::idempotency::entity::{IdempotencyRecord, StoredResponse};
+use crate::domain::membership::entity::{LockMembership, MembershipRole};
+use crate::domain::webhook::entity::{DeliveryStatus, WebhookDelivery, WebhookSubscription};
+use crate::infrastructure::models::{LockModel, QuestModel};
+
+pub fn parse_uuid(value: &str) -> Result<Uuid, sqlx::Error> {
+    Uuid::try_parse(value).map_err(|e| sqlx::Error::Decode(Box::new(e)))
+}
+
+#[derive(FromRow, Debug)]
+pub struct LockRow {
+    pub id: String,
+    pub user_id: String,
+    pub label: Option<String>,
+    pub total_shares: i16,
+    pub threshold: i16,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub version: i64,
+}
+
+impl TryFrom<LockRow> for LockModel {
+    type Error = sqlx::Error;
+
+    fn try_from(row: LockRow) -> Result<Self, Self::Error> {
+        Ok(LockModel::create(
+            parse_uuid(&row.id)?,
+            row.user_id,
+            row.label,
+            row.total_shares,
+            row.threshold,
+            row.deleted_at,
+            row.version,
+        ))
+    }
+}
+
+#[derive(FromRow, Debug)]
+pub struct QuestRow {
+    pub id: String,
+    pub lock_id: String,
+    pub share: String,
+    pub quest_type: String,
+    pub status: String,
+    pub data: Json<HashMap<String, String>>,
+}
+
+impl TryFrom<QuestRow> for QuestModel {
+    type Error = sqlx::Error;
+
+    fn try_from(row: QuestRow) -> Result<Self, Self::Error> {
+        Ok(QuestModel::create(
+            parse_uuid(&row.id)?,
+            parse_uuid(&row.lock_id)?,
+            row.share,
+            row.quest_type,
+            row.status,
+            row.data,
+        ))
+    }
+}
+
+#[derive(FromRow, Debug)]
+pub struct FlatLockQuestRow {
+    pub lock_id: String,
+    pub lock_user_id: String,
+    pub lock_label: Option<String>,
+    pub lock_total_shares: i16,
+    pub lock_threshold: i16,
+    pub lock_version: i64,
+
+    pub quest_id: Option<String>,
+    pub quest_share: Option<String>,
+    pub quest_type: Option<String>,
+    pub quest_status: Option<String>,
+    pub quest_data: Option<Json<HashMap<String, String>>>,
+}
+
+#[derive(FromRow, Debug)]
+pub struct OutboxRow {
+    pub id: String,
+    pub payload: Json<DomainEvent>,
+    pub attempts: i32,
+    pub handled_by: Json<Vec<String>>,
+}
+
+#[derive(FromRow, Debug)]
+pub struct PersonalAccessTokenRow {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub token_hash: String,
+    pub scopes: Json<Vec<String>>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<PersonalAccessTokenRow> for PersonalAccessToken {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PersonalAccessTokenRow) -> Result<Self, Self::Error> {
+        Ok(PersonalAccessToken {
+            id: parse_uuid(&row.id)?,
+            user_id: row.user_id,
+            name: row.name,
+            token_hash: row.token_hash,
+            scopes: row.scopes.0,
+            expires_at: row.expires_at,
+            created_at: row.created_at,
+            last_used_at: row.last_used_at,
+            revoked_at: row.revoked_at,
+        })
+    }
+}
+
+#[derive(FromRow, Debug)]
+pub struct LockMembershipRow {
+    pub id: String,
+    pub lock_id: String,
+    pub user_id: String,
+    pub role: String,
+    pub invited_by: String,
+    pub created_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<LockMembershipRow> for LockMembership {
+    type Error = sqlx::Error;
+
+    fn try_from(row: LockMembershipRow) -> Result<Self, Self::Error> {
+        Ok(LockMembership {
+            id: parse_uuid(&row.id)?,
+            lock_id: parse_uuid(&row.lock_id)?,
+            user_id: row.user_id,
+            role: MembershipRole::from_str(&row.role)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            invited_by: row.invited_by,
+            created_at: row.created_at,
+            accepted_at: row.accepted_at,
+        })
+    }
+}
+
+#[derive(FromRow, Debug)]
+pub struct AccountErasureRow {
+    pub user_id: String,
+    pub requested_at: DateTime<Utc>,
+    pub scheduled_for: DateTime<Utc>,
+}
+
+impl From<AccountErasureRow> for AccountErasure {
+    fn from(row: AccountErasureRow) -> Self {
+        AccountErasure {
+            user_id: row.user_id,
+            requested_at: row.requested_at,
+            scheduled_for: row.scheduled_for,
+        }
+    }
+}
+
+#[derive(FromRow, Debug)]
+pub struct IdempotencyKeyRow {
+    pub user_id: String,
+    pub idempotency_key: String,
+    pub fingerprint: String,
+    pub response_status: Option<i64>,
+    pub response_content_type: Option<String>,
+    pub response_headers: Option<Json<Vec<(String, String)>>>,
+    pub response_body: Option<Vec<u8>>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl TryFrom<IdempotencyKeyRow> for IdempotencyRecord {
+    type Error = sqlx::Error;
+
+    fn try_from(row: IdempotencyKeyRow) -> Result<Self, Self::Error> {
+        let response = match (row.response_status, row.response_body) {
+            (Some(status), Some(body)) => Some(StoredResponse {
+                status: u16::try_from(status).map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+                content_type: row.response_content_type,
+                headers: row
+                    .response_headers
+                    .map(|headers| headers.0)
+                    .unwrap_or_default(),
+                body,
+            }),
+            _ => None,
+        };
+        Ok(IdempotencyRecord {
+            user_id: row.user_id,
+            key: row.idempotency_key,
+            fingerprint: row.fingerprint,
+            response,
+            created_at: row.created_at,
+            expires_at: row.expires_at,
+        })
+    }
+}
+
+#[derive(FromRow, Debug)]
+pub struct WebhookSubscriptionRow {
+    pub id: String,
+    pub user_id: String,
+    pub url: String,
+    pub event_types: Json<Vec<String>>,
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<WebhookSubscriptionRow> for WebhookSubscription {
+    type Error = sqlx::Error;
+
+    fn try_from(row: WebhookSubscriptionRow) -> Result<Self, Self::Error> {
+        Ok(WebhookSubscription {
+            id: parse_uuid(&row.id)?,
+            user_id: row.user_id,
+            url: row.url,
+            event_types: row.event_types.0,
+            secret: row.secret,
+            created_at: row.created_at,
+        })
+    }
+}
+
+#[derive(FromRow, Debug)]
+pub struct WebhookDeliveryRow {
+    pub id: String,
+    pub subscription_id: String,
+    pub user_id: String,
+    pub payload: Json<DomainEvent>,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub available_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<WebhookDeliveryRow> for WebhookDelivery {
+    type Error = sqlx::Error;
+
+    fn try_from(row: WebhookDeliveryRow) -> Result<Self, Self::Error> {
+        Ok(WebhookDelivery {
+            id: parse_uuid(&row.id)?,
+            subscription_id: parse_uuid(&row.subscription_id)?,
+            user_id: row.user_id,
+            event: row.payload.0,
+            status: DeliveryStatus::from_str(&row.status)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            attempts: row.attempts,
+            last_error: row.last_error,
+            available_at: row.available_at,
+            created_at: row.created_at,
+            processed_at: row.processed_at,
+        })
+    }
+}
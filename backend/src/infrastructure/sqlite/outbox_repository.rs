@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use crate::domain::event::{
+    entity::{DomainEvent, OutboxMessage},
+    repository::OutboxRepository as OutboxRepositoryInterface,
+};
+use crate::infrastructure::sqlite::models::{OutboxRow, parse_uuid};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{Pool, Sqlite, Transaction, types::Json};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct OutboxRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl OutboxRepository {
+    pub fn create(pool: Pool<Sqlite>) -> Arc<dyn OutboxRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+
+    /// Write events to the outbox as part of the caller's transaction.
+    pub async fn insert_events(
+        tx: &mut Transaction<'_, Sqlite>,
+        events: &[DomainEvent],
+    ) -> Result<(), sqlx::Error> {
+        for event in events {
+            sqlx::query(
+                r#"
+                INSERT INTO outbox (
+                    id, event_type, aggregate_id, payload
+                ) VALUES (
+                    ?1, ?2, ?3, ?4
+                )
+                ON CONFLICT (id) DO NOTHING
+                "#,
+            )
+            .bind(event.id.to_string())
+            .bind(event.event_type())
+            .bind(event.lock_id.to_string())
+            .bind(Json(event))
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OutboxRepositoryInterface for OutboxRepository {
+    async fn claim_batch(
+        &self,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<OutboxMessage>, sqlx::Error> {
+        // SQLite serialises writers, so the UPDATE itself is the lock.
+        let rows = sqlx::query_as::<_, OutboxRow>(
+            r#"
+            UPDATE outbox SET
+                attempts = attempts + 1,
+                available_at = ?2
+            WHERE id IN (
+                SELECT id FROM outbox
+                WHERE status = 'pending' AND julianday(available_at) <= julianday('now')
+                ORDER BY created_at
+                LIMIT ?1
+            )
+            RETURNING id, payload, attempts, handled_by
+            "#,
+        )
+        .bind(limit)
+        .bind(Utc::now() + lease)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut messages = Vec::new();
+        for row in rows {
+            messages.push(OutboxMessage {
+                id: parse_uuid(&row.id)?,
+                event: row.payload.0,
+                attempts: row.attempts,
+                handled_by: row.handled_by.0,
+            });
+        }
+        // Event ids are v7 uuids, so this restores the order they were recorded in.
+        messages.sort_by_key(|message| message.id);
+
+        Ok(messages)
+    }
+
+    async fn mark_done(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(
+            r#"UPDATE outbox SET status = 'done', last_error = NULL,
+                processed_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+            WHERE id = ?1"#,
+        )
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        handled_by: &[String],
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<bool, sqlx::Error> {
+        let res = match retry_at {
+            Some(retry_at) => {
+                sqlx::query(
+                    r#"UPDATE outbox SET last_error = ?2, handled_by = ?3, available_at = ?4
+                    WHERE id = ?1"#,
+                )
+                .bind(id.to_string())
+                .bind(error)
+                .bind(Json(handled_by))
+                .bind(retry_at)
+                .execute(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query(
+                    r#"UPDATE outbox SET status = 'failed', last_error = ?2, handled_by = ?3,
+                        processed_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+                    WHERE id = ?1"#,
+                )
+                .bind(id.to_string())
+                .bind(error)
+                .bind(Json(handled_by))
+                .execute(&self.pool)
+                .await?
+            }
+        };
+
+        Ok(res.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use crate::domain::auth::{
+    entity::PersonalAccessToken,
+    repository::PersonalAccessTokenRepository as PersonalAccessTokenRepositoryInterface,
+};
+use crate::infrastructure::sqlite::models::PersonalAccessTokenRow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Sqlite, types::Json};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct PersonalAccessTokenRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl PersonalAccessTokenRepository {
+    pub fn create(pool: Pool<Sqlite>) -> Arc<dyn PersonalAccessTokenRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+}
+
+#[async_trait]
+impl PersonalAccessTokenRepositoryInterface for PersonalAccessTokenRepository {
+    async fn get_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PersonalAccessToken>, sqlx::Error> {
+        let row = sqlx::query_as::<_, PersonalAccessTokenRow>(
+            r#"SELECT id, user_id, name, token_hash, scopes, expires_at, created_at,
+                last_used_at, revoked_at
+            FROM personal_access_tokens
+            WHERE token_hash = ?1"#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(PersonalAccessToken::try_from).transpose()
+    }
+
+    async fn get_by_user_id(
+        &self,
+        user_id: String,
+    ) -> Result<Vec<PersonalAccessToken>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, PersonalAccessTokenRow>(
+            r#"SELECT id, user_id, name, token_hash, scopes, expires_at, created_at,
+                last_used_at, revoked_at
+            FROM personal_access_tokens
+            WHERE user_id = ?1
+            ORDER BY created_at DESC"#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(PersonalAccessToken::try_from)
+            .collect()
+    }
+
+    async fn save(&self, token: &PersonalAccessToken) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(
+            r#"
+            INSERT INTO personal_access_tokens (
+                id, user_id, name, token_hash, scopes, expires_at, created_at
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7
+            )
+            "#,
+        )
+        .bind(token.id.to_string())
+        .bind(&token.user_id)
+        .bind(&token.name)
+        .bind(&token.token_hash)
+        .bind(Json(&token.scopes))
+        .bind(token.expires_at)
+        .bind(token.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn revoke(&self, id: Uuid, user_id: String) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(
+            r#"UPDATE personal_access_tokens
+            SET revoked_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+            WHERE id = ?1 AND user_id = ?2 AND revoked_at IS NULL"#,
+        )
+        .bind(id.to_string())
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn touch(&self, id: Uuid, used_at: DateTime<Utc>) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(
+            r#"UPDATE personal_access_tokens SET last_used_at = ?2
+            WHERE id = ?1
+                AND (last_used_at IS NULL
+                    OR julianday(last_used_at) < julianday(?2) - 1.0 / 1440)"#,
+        )
+        .bind(id.to_string())
+        .bind(used_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use crate::domain::quest::{
+    entity::Quest, repository::QuestRepository as QuestRepositoryInterface,
+};
+use crate::infrastructure::models::QuestModel;
+use crate::infrastructure::sqlite::models::QuestRow;
+use crate::infrastructure::sqlite::outbox_repository::OutboxRepository;
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite, types::Json};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct QuestRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl QuestRepository {
+    pub fn create(pool: Pool<Sqlite>) -> Arc<dyn QuestRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+}
+
+#[async_trait]
+impl QuestRepositoryInterface for QuestRepository {
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Quest>, sqlx::Error> {
+        let quest_row = sqlx::query_as::<_, QuestRow>(
+            r#"SELECT q.id, q.lock_id, q.share, q.quest_type, q.status, q.data
+            FROM quests q
+            JOIN locks l ON l.id = q.lock_id
+            WHERE q.id = ?1 AND l.deleted_at IS NULL"#,
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match quest_row {
+            Some(quest_row) => match Quest::try_from(QuestModel::try_from(quest_row)?) {
+                Ok(quest) => Ok(Some(quest)),
+                Err(conv_err) => Err(sqlx::Error::Decode(Box::new(conv_err))),
+            },
+            None => Ok(None),
+        }
+    }
+
+    async fn save(&self, quest: &Quest, lock_version: i64) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let quest_res = sqlx::query(
+            r#"
+            INSERT INTO quests (
+                id, lock_id, share, quest_type, status, data
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6
+            )
+            ON CONFLICT (id) DO UPDATE SET
+                status = excluded.status,
+                updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+            "#,
+        )
+        .bind(quest.id.to_string())
+        .bind(quest.lock_id.to_string())
+        .bind(&quest.share)
+        .bind(quest.quest_type.to_string())
+        .bind(quest.status.to_string())
+        .bind(Json(&quest.data))
+        .execute(&mut *tx)
+        .await?;
+
+        // A quest change is a change to its lock, move the parent to its next version.
+        let lock_res = sqlx::query(
+            r#"UPDATE locks
+            SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), version = version + 1
+            WHERE id = ?1 AND version = ?2"#,
+        )
+        .bind(quest.lock_id.to_string())
+        .bind(lock_version)
+        .execute(&mut *tx)
+        .await?;
+        if lock_res.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        OutboxRepository::insert_events(&mut tx, &quest.events).await?;
+        tx.commit().await?;
+
+        Ok(quest_res.rows_affected() > 0)
+    }
+
+    async fn delete(&self, quest: &Quest) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(r#"DELETE FROM quests WHERE id = ?1"#)
+            .bind(quest.id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+}
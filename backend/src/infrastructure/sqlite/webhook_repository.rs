@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use crate::domain::webhook::{
+    entity::{WebhookDelivery, WebhookSubscription},
+    repository::WebhookRepository as WebhookRepositoryInterface,
+};
+use crate::infrastructure::sqlite::models::{WebhookDeliveryRow, WebhookSubscriptionRow};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{Pool, Sqlite, types::Json};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct WebhookRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl WebhookRepository {
+    pub fn create(pool: Pool<Sqlite>) -> Arc<dyn WebhookRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+}
+
+#[async_trait]
+impl WebhookRepositoryInterface for WebhookRepository {
+    async fn save_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(
+            r#"
+            INSERT INTO webhook_subscriptions (
+                id, user_id, url, event_types, secret, created_at
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6
+            )
+            "#,
+        )
+        .bind(subscription.id.to_string())
+        .bind(&subscription.user_id)
+        .bind(&subscription.url)
+        .bind(Json(&subscription.event_types))
+        .bind(&subscription.secret)
+        .bind(subscription.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn get_subscription(&self, id: Uuid) -> Result<Option<WebhookSubscription>, sqlx::Error> {
+        let row = sqlx::query_as::<_, WebhookSubscriptionRow>(
+            r#"SELECT id, user_id, url, event_types, secret, created_at
+            FROM webhook_subscriptions
+            WHERE id = ?1"#,
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(WebhookSubscription::try_from).transpose()
+    }
+
+    async fn get_subscriptions_by_user_id(
+        &self,
+        user_id: String,
+    ) -> Result<Vec<WebhookSubscription>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, WebhookSubscriptionRow>(
+            r#"SELECT id, user_id, url, event_types, secret, created_at
+            FROM webhook_subscriptions
+            WHERE user_id = ?1
+            ORDER BY julianday(created_at)"#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(WebhookSubscription::try_from)
+            .collect()
+    }
+
+    async fn delete_subscription(&self, id: Uuid, user_id: String) -> Result<bool, sqlx::Error> {
+        let res =
+            sqlx::query(r#"DELETE FROM webhook_subscriptions WHERE id = ?1 AND user_id = ?2"#)
+                .bind(id.to_string())
+                .bind(user_id)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn enqueue_deliveries(&self, deliveries: &[WebhookDelivery]) -> Result<u64, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let mut enqueued = 0;
+        for delivery in deliveries {
+            let res = sqlx::query(
+                r#"
+                INSERT INTO webhook_deliveries (
+                    id, subscription_id, user_id, event_id, payload, status, available_at,
+                    created_at
+                ) VALUES (
+                    ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8
+                )
+                ON CONFLICT (subscription_id, event_id) DO NOTHING
+                "#,
+            )
+            .bind(delivery.id.to_string())
+            .bind(delivery.subscription_id.to_string())
+            .bind(&delivery.user_id)
+            .bind(delivery.event.id.to_string())
+            .bind(Json(&delivery.event))
+            .bind(delivery.status.to_string())
+            .bind(delivery.available_at)
+            .bind(delivery.created_at)
+            .execute(&mut *tx)
+            .await?;
+            enqueued += res.rows_affected();
+        }
+        tx.commit().await?;
+
+        Ok(enqueued)
+    }
+
+    async fn claim_deliveries(
+        &self,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+        // SQLite serialises writers, so the UPDATE itself is the lock.
+        let rows = sqlx::query_as::<_, WebhookDeliveryRow>(
+            r#"
+            UPDATE webhook_deliveries SET
+                attempts = attempts + 1,
+                available_at = ?2
+            WHERE id IN (
+                SELECT id FROM webhook_deliveries
+                WHERE status = 'PENDING' AND julianday(available_at) <= julianday('now')
+                ORDER BY julianday(created_at)
+                LIMIT ?1
+            )
+            RETURNING id, subscription_id, user_id, payload, status, attempts, last_error,
+                available_at, created_at, processed_at
+            "#,
+        )
+        .bind(limit)
+        .bind(Utc::now() + lease)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut deliveries = rows
+            .into_iter()
+            .map(WebhookDelivery::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        deliveries.sort_by_key(|delivery| delivery.id);
+
+        Ok(deliveries)
+    }
+
+    async fn mark_delivered(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(
+            r#"UPDATE webhook_deliveries SET status = 'DELIVERED', last_error = NULL,
+                processed_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+            WHERE id = ?1"#,
+        )
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<bool, sqlx::Error> {
+        let res = match retry_at {
+            Some(retry_at) => {
+                sqlx::query(
+                    r#"UPDATE webhook_deliveries SET last_error = ?2, available_at = ?3
+                    WHERE id = ?1"#,
+                )
+                .bind(id.to_string())
+                .bind(error)
+                .bind(retry_at)
+                .execute(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query(
+                    r#"UPDATE webhook_deliveries SET status = 'DEAD', last_error = ?2,
+                        processed_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+                    WHERE id = ?1"#,
+                )
+                .bind(id.to_string())
+                .bind(error)
+                .execute(&self.pool)
+                .await?
+            }
+        };
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn get_dead_letters(&self, user_id: String) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, WebhookDeliveryRow>(
+            r#"SELECT id, subscription_id, user_id, payload, status, attempts, last_error,
+                available_at, created_at, processed_at
+            FROM webhook_deliveries
+            WHERE user_id = ?1 AND status = 'DEAD'
+            ORDER BY julianday(processed_at) DESC"#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(WebhookDelivery::try_from).collect()
+    }
+
+    async fn replay_dead_letter(
+        &self,
+        id: Uuid,
+        user_id: String,
+    ) -> Result<Option<WebhookDelivery>, sqlx::Error> {
+        let row = sqlx::query_as::<_, WebhookDeliveryRow>(
+            r#"UPDATE webhook_deliveries SET
+                status = 'PENDING',
+                attempts = 0,
+                available_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'),
+                processed_at = NULL
+            WHERE id = ?1 AND user_id = ?2 AND status = 'DEAD'
+            RETURNING id, subscription_id, user_id, payload, status, attempts, last_error,
+                available_at, created_at, processed_at"#,
+        )
+        .bind(id.to_string())
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(WebhookDelivery::try_from).transpose()
+    }
+}
@@ -0,0 +1,353 @@
+use std::{str::FromStr, sync::Arc};
+
+use crate::domain::{
+    event::entity::DomainEvent,
+    webhook::{
+        entity::{DeliveryStatus, WebhookDelivery, WebhookSubscription},
+        repository::WebhookRepository as WebhookRepositoryInterface,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Debug)]
+struct WebhookSubscriptionRow {
+    id: Uuid,
+    user_id: String,
+    url: String,
+    event_types: Vec<String>,
+    secret: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<WebhookSubscriptionRow> for WebhookSubscription {
+    fn from(row: WebhookSubscriptionRow) -> Self {
+        Self {
+            id: row.id,
+            user_id: row.user_id,
+            url: row.url,
+            event_types: row.event_types,
+            secret: row.secret,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct WebhookDeliveryRow {
+    id: Uuid,
+    subscription_id: Uuid,
+    user_id: String,
+    payload: serde_json::Value,
+    status: String,
+    attempts: i32,
+    last_error: Option<String>,
+    available_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    processed_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<WebhookDeliveryRow> for WebhookDelivery {
+    type Error = sqlx::Error;
+
+    fn try_from(row: WebhookDeliveryRow) -> Result<Self, Self::Error> {
+        let event: DomainEvent =
+            serde_json::from_value(row.payload).map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+        Ok(Self {
+            id: row.id,
+            subscription_id: row.subscription_id,
+            user_id: row.user_id,
+            event,
+            status: DeliveryStatus::from_str(&row.status)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            attempts: row.attempts,
+            last_error: row.last_error,
+            available_at: row.available_at,
+            created_at: row.created_at,
+            processed_at: row.processed_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookRepository {
+    pool: Pool<Postgres>,
+}
+
+impl WebhookRepository {
+    pub fn create(pool: Pool<Postgres>) -> Arc<dyn WebhookRepositoryInterface> {
+        Arc::new(Self { pool })
+    }
+}
+
+#[async_trait]
+impl WebhookRepositoryInterface for WebhookRepository {
+    async fn save_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query!(
+            r#"
+            INSERT INTO webhook_subscriptions (
+                id, user_id, url, event_types, secret, created_at
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6
+            )
+            "#,
+            subscription.id,
+            subscription.user_id,
+            subscription.url,
+            &subscription.event_types,
+            subscription.secret,
+            subscription.created_at as _
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn get_subscription(&self, id: Uuid) -> Result<Option<WebhookSubscription>, sqlx::Error> {
+        let row = sqlx::query_as!(
+            WebhookSubscriptionRow,
+            r#"SELECT
+                id,
+                user_id,
+                url,
+                event_types,
+                secret,
+                created_at as "created_at: DateTime<Utc>"
+            FROM webhook_subscriptions
+            WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(WebhookSubscription::from))
+    }
+
+    async fn get_subscriptions_by_user_id(
+        &self,
+        user_id: String,
+    ) -> Result<Vec<WebhookSubscription>, sqlx::Error> {
+        let rows = sqlx::query_as!(
+            WebhookSubscriptionRow,
+            r#"SELECT
+                id,
+                user_id,
+                url,
+                event_types,
+                secret,
+                created_at as "created_at: DateTime<Utc>"
+            FROM webhook_subscriptions
+            WHERE user_id = $1
+            ORDER BY created_at"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(WebhookSubscription::from).collect())
+    }
+
+    async fn delete_subscription(&self, id: Uuid, user_id: String) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query!(
+            r#"DELETE FROM webhook_subscriptions WHERE id = $1 AND user_id = $2"#,
+            id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn enqueue_deliveries(&self, deliveries: &[WebhookDelivery]) -> Result<u64, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let mut enqueued = 0;
+        for delivery in deliveries {
+            let payload = serde_json::to_value(&delivery.event)
+                .map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+
+            let res = sqlx::query!(
+                r#"
+                INSERT INTO webhook_deliveries (
+                    id, subscription_id, user_id, event_id, payload, status, available_at,
+                    created_at
+                ) VALUES (
+                    $1, $2, $3, $4, $5, $6, $7, $8
+                )
+                ON CONFLICT (subscription_id, event_id) DO NOTHING
+                "#,
+                delivery.id,
+                delivery.subscription_id,
+                delivery.user_id,
+                delivery.event.id,
+                payload,
+                delivery.status.to_string(),
+                delivery.available_at as _,
+                delivery.created_at as _
+            )
+            .execute(&mut *tx)
+            .await?;
+            enqueued += res.rows_affected();
+        }
+        tx.commit().await?;
+
+        Ok(enqueued)
+    }
+
+    async fn claim_deliveries(
+        &self,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+        let lease_until = Utc::now() + lease;
+        let rows = sqlx::query_as!(
+            WebhookDeliveryRow,
+            r#"
+            UPDATE webhook_deliveries SET
+                attempts = attempts + 1,
+                available_at = $2
+            WHERE id IN (
+                SELECT id FROM webhook_deliveries
+                WHERE status = 'PENDING' AND available_at <= NOW()
+                ORDER BY created_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING
+                id,
+                subscription_id,
+                user_id,
+                payload as "payload: serde_json::Value",
+                status,
+                attempts,
+                last_error,
+                available_at as "available_at: DateTime<Utc>",
+                created_at as "created_at: DateTime<Utc>",
+                processed_at as "processed_at: DateTime<Utc>"
+            "#,
+            limit,
+            lease_until as _
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut deliveries = rows
+            .into_iter()
+            .map(WebhookDelivery::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        deliveries.sort_by_key(|delivery| delivery.id);
+
+        Ok(deliveries)
+    }
+
+    async fn mark_delivered(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query!(
+            r#"UPDATE webhook_deliveries
+            SET status = 'DELIVERED', last_error = NULL, processed_at = NOW()
+            WHERE id = $1"#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<bool, sqlx::Error> {
+        let res = match retry_at {
+            Some(retry_at) => {
+                sqlx::query!(
+                    r#"UPDATE webhook_deliveries SET last_error = $2, available_at = $3
+                    WHERE id = $1"#,
+                    id,
+                    error,
+                    retry_at as _
+                )
+                .execute(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query!(
+                    r#"UPDATE webhook_deliveries
+                    SET status = 'DEAD', last_error = $2, processed_at = NOW()
+                    WHERE id = $1"#,
+                    id,
+                    error
+                )
+                .execute(&self.pool)
+                .await?
+            }
+        };
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn get_dead_letters(&self, user_id: String) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+        let rows = sqlx::query_as!(
+            WebhookDeliveryRow,
+            r#"SELECT
+                id,
+                subscription_id,
+                user_id,
+                payload as "payload: serde_json::Value",
+                status,
+                attempts,
+                last_error,
+                available_at as "available_at: DateTime<Utc>",
+                created_at as "created_at: DateTime<Utc>",
+                processed_at as "processed_at: DateTime<Utc>"
+            FROM webhook_deliveries
+            WHERE user_id = $1 AND status = 'DEAD'
+            ORDER BY processed_at DESC"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(WebhookDelivery::try_from).collect()
+    }
+
+    async fn replay_dead_letter(
+        &self,
+        id: Uuid,
+        user_id: String,
+    ) -> Result<Option<WebhookDelivery>, sqlx::Error> {
+        let row = sqlx::query_as!(
+            WebhookDeliveryRow,
+            r#"UPDATE webhook_deliveries SET
+                status = 'PENDING',
+                attempts = 0,
+                available_at = NOW(),
+                processed_at = NULL
+            WHERE id = $1 AND user_id = $2 AND status = 'DEAD'
+            RETURNING
+                id,
+                subscription_id,
+                user_id,
+                payload as "payload: serde_json::Value",
+                status,
+                attempts,
+                last_error,
+                available_at as "available_at: DateTime<Utc>",
+                created_at as "created_at: DateTime<Utc>",
+                processed_at as "processed_at: DateTime<Utc>""#,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(WebhookDelivery::try_from).transpose()
+    }
+}
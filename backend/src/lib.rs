@@ -1,5 +1,10 @@
+#[cfg(not(any(feature = "postgres", feature = "sqlite")))]
+compile_error!("at least one storage backend feature (`postgres` or `sqlite`) must be enabled");
+
 pub mod api;
 pub mod application;
 pub mod domain;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod infrastructure;
 pub mod setup;
@@ -1,8 +1,9 @@
 use quest_lock_backend::{
-    api::router::create_router,
+    api::{metrics::metrics_router, router::create_router},
     setup::{
         bootstrap::{build_app_state, setup_tracing, shutdown_signal},
-        config::{Config, setup_database},
+        config::{Config, setup_database, setup_read_database},
+        jobs::spawn_background_jobs,
     },
 };
 use tracing::info;
@@ -13,7 +14,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let config = Config::from_env()?;
     let pool = setup_database(&config).await?;
-    let state = build_app_state(pool, config.clone());
+    let read_pool = setup_read_database(&config).await?;
+    let state = build_app_state(pool, read_pool, config.clone())?;
+    let _jobs = spawn_background_jobs(&state);
+    #[cfg(feature = "grpc")]
+    let grpc = {
+        let addr = format!("{}:{}", config.service_host, config.grpc_port).parse()?;
+        info!("gRPC server running at {addr}");
+        tokio::spawn(quest_lock_backend::grpc::serve(
+            state.clone(),
+            addr,
+            shutdown_signal(),
+        ))
+    };
+    let metrics = {
+        let addr = format!("{}:{}", config.service_host, config.metrics_port);
+        info!("Metrics server running at {addr}");
+        let listener = tokio::net::TcpListener::bind(&addr).await?;
+        let app = metrics_router().with_state(state.clone());
+        tokio::spawn(async move {
+            axum::serve(listener, app)
+                .with_graceful_shutdown(shutdown_signal())
+                .await
+        })
+    };
     let app = create_router(state);
 
     let addr = format!("{}:{}", config.service_host, config.service_port);
@@ -26,6 +50,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_graceful_shutdown(shutdown_signal())
         .await?;
 
+    #[cfg(feature = "grpc")]
+    grpc.await??;
+    metrics.await??;
+
     info!("Server shutdown complete");
 
     Ok(())
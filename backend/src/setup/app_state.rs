@@ -1,8 +1,13 @@
 use std::sync::Arc;
 
 use crate::application::services::{
-    auth_service::AuthServiceTrait, lock_query_service::LockQueryServiceTrait,
-    lock_service::LockServiceTrait,
+    account_service::AccountServiceTrait, admin_service::AdminServiceTrait,
+    auth_service::AuthServiceTrait, event_stream_service::EventStreamServiceTrait,
+    idempotency_service::IdempotencyServiceTrait, lock_query_service::LockQueryServiceTrait,
+    lock_service::LockServiceTrait, membership_service::MembershipServiceTrait,
+    metrics_service::MetricsServiceTrait, outbox_dispatcher::OutboxDispatcherTrait,
+    quest_query_service::QuestQueryServiceTrait, quest_service::QuestServiceTrait,
+    token_service::TokenServiceTrait, webhook_service::WebhookServiceTrait,
 };
 
 use super::config::Config;
@@ -12,20 +17,54 @@ pub struct AppState {
     pub config: Config,
     pub lock_service: Arc<dyn LockServiceTrait>,
     pub lock_query_service: Arc<dyn LockQueryServiceTrait>,
+    pub quest_service: Arc<dyn QuestServiceTrait>,
+    pub quest_query_service: Arc<dyn QuestQueryServiceTrait>,
+    pub membership_service: Arc<dyn MembershipServiceTrait>,
+    pub outbox_dispatcher: Arc<dyn OutboxDispatcherTrait>,
+    pub token_service: Arc<dyn TokenServiceTrait>,
+    pub admin_service: Arc<dyn AdminServiceTrait>,
+    pub account_service: Arc<dyn AccountServiceTrait>,
+    pub idempotency_service: Arc<dyn IdempotencyServiceTrait>,
+    pub event_stream_service: Arc<dyn EventStreamServiceTrait>,
+    pub webhook_service: Arc<dyn WebhookServiceTrait>,
+    pub metrics_service: Arc<dyn MetricsServiceTrait>,
     pub auth_service: Arc<dyn AuthServiceTrait>,
 }
 
 impl AppState {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         config: Config,
         lock_service: Arc<dyn LockServiceTrait>,
         lock_query_service: Arc<dyn LockQueryServiceTrait>,
+        quest_service: Arc<dyn QuestServiceTrait>,
+        quest_query_service: Arc<dyn QuestQueryServiceTrait>,
+        membership_service: Arc<dyn MembershipServiceTrait>,
+        outbox_dispatcher: Arc<dyn OutboxDispatcherTrait>,
+        token_service: Arc<dyn TokenServiceTrait>,
+        admin_service: Arc<dyn AdminServiceTrait>,
+        account_service: Arc<dyn AccountServiceTrait>,
+        idempotency_service: Arc<dyn IdempotencyServiceTrait>,
+        event_stream_service: Arc<dyn EventStreamServiceTrait>,
+        webhook_service: Arc<dyn WebhookServiceTrait>,
+        metrics_service: Arc<dyn MetricsServiceTrait>,
         auth_service: Arc<dyn AuthServiceTrait>,
     ) -> Self {
         Self {
             config,
             lock_service,
             lock_query_service,
+            quest_service,
+            quest_query_service,
+            membership_service,
+            outbox_dispatcher,
+            token_service,
+            admin_service,
+            account_service,
+            idempotency_service,
+            event_stream_service,
+            webhook_service,
+            metrics_service,
             auth_service,
         }
     }
@@ -1,23 +1,240 @@
-use sqlx::PgPool;
+use std::sync::Arc;
 
+use chrono::Duration;
+
+use crate::domain::account::repository::AccountRepository as AccountRepositoryInterface;
+use crate::domain::audit::repository::AuditRepository as AuditRepositoryInterface;
+use crate::domain::auth::repository::PersonalAccessTokenRepository as PersonalAccessTokenRepositoryInterface;
+use crate::domain::event::repository::OutboxRepository as OutboxRepositoryInterface;
+use crate::domain::idempotency::repository::IdempotencyRepository as IdempotencyRepositoryInterface;
+use crate::domain::lock::repository::LockRepository as LockRepositoryInterface;
+use crate::domain::membership::repository::LockMembershipRepository as LockMembershipRepositoryInterface;
+use crate::domain::quest::repository::QuestRepository as QuestRepositoryInterface;
+use crate::domain::webhook::repository::WebhookRepository as WebhookRepositoryInterface;
+use crate::infrastructure::exceptions::InfrastructureError;
+use crate::infrastructure::services::account_service::AccountService;
+use crate::infrastructure::services::admin_service::AdminService;
 use crate::infrastructure::services::auth_service::AuthService;
+use crate::infrastructure::services::event_stream_service::EventStreamService;
+use crate::infrastructure::services::idempotency_service::IdempotencyService;
 use crate::infrastructure::services::lock_query_service::LockQueryService;
-use crate::infrastructure::{lock_repository::LockRepository, services::lock_service::LockService};
+use crate::infrastructure::services::lock_service::LockService;
+use crate::infrastructure::services::logging_event_handler::LoggingEventHandler;
+use crate::infrastructure::services::membership_service::MembershipService;
+use crate::infrastructure::services::metrics_service::{MetricsService, MonitoredPool};
+use crate::infrastructure::services::outbox_dispatcher::OutboxDispatcher;
+use crate::infrastructure::services::quest_query_service::QuestQueryService;
+use crate::infrastructure::services::quest_service::QuestService;
+use crate::infrastructure::services::token_service::TokenService;
+use crate::infrastructure::services::webhook_event_handler::WebhookEventHandler;
+use crate::infrastructure::services::webhook_service::WebhookService;
 use crate::setup::app_state::AppState;
-use crate::setup::config::Config;
+use crate::setup::config::{Config, DatabasePool};
 
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-pub fn build_app_state(pool: PgPool, config: Config) -> AppState {
-    let lock_repository = LockRepository::create(pool.clone());
+/// The repositories backing the services, built for whichever storage backend is in use.
+pub struct Repositories {
+    pub lock: Arc<dyn LockRepositoryInterface>,
+    pub quest: Arc<dyn QuestRepositoryInterface>,
+    pub membership: Arc<dyn LockMembershipRepositoryInterface>,
+    pub outbox: Arc<dyn OutboxRepositoryInterface>,
+    pub personal_access_token: Arc<dyn PersonalAccessTokenRepositoryInterface>,
+    pub audit: Arc<dyn AuditRepositoryInterface>,
+    pub account: Arc<dyn AccountRepositoryInterface>,
+    pub idempotency: Arc<dyn IdempotencyRepositoryInterface>,
+    pub webhook: Arc<dyn WebhookRepositoryInterface>,
+}
+
+impl Repositories {
+    /// `read_pool` is the optional read replica, `setup_read_database` guarantees it uses the
+    /// same backend as `pool`.
+    pub fn create(pool: &DatabasePool, read_pool: Option<&DatabasePool>) -> Self {
+        match pool {
+            #[cfg(feature = "postgres")]
+            DatabasePool::Postgres(pool) => {
+                use crate::infrastructure::{
+                    account_repository::AccountRepository, audit_repository::AuditRepository,
+                    idempotency_key_repository::IdempotencyKeyRepository,
+                    lock_membership_repository::LockMembershipRepository,
+                    lock_repository::LockRepository, outbox_repository::OutboxRepository,
+                    personal_access_token_repository::PersonalAccessTokenRepository,
+                    quest_repository::QuestRepository, webhook_repository::WebhookRepository,
+                };
+
+                #[allow(unreachable_patterns)]
+                let read_pool = read_pool.and_then(|read_pool| match read_pool {
+                    DatabasePool::Postgres(read_pool) => Some(read_pool.clone()),
+                    _ => None,
+                });
+
+                Self {
+                    lock: LockRepository::create(pool.clone(), read_pool),
+                    quest: QuestRepository::create(pool.clone()),
+                    membership: LockMembershipRepository::create(pool.clone()),
+                    outbox: OutboxRepository::create(pool.clone()),
+                    personal_access_token: PersonalAccessTokenRepository::create(pool.clone()),
+                    audit: AuditRepository::create(pool.clone()),
+                    account: AccountRepository::create(pool.clone()),
+                    idempotency: IdempotencyKeyRepository::create(pool.clone()),
+                    webhook: WebhookRepository::create(pool.clone()),
+                }
+            }
+            #[cfg(feature = "sqlite")]
+            DatabasePool::Sqlite(pool) => {
+                use crate::infrastructure::sqlite::{
+                    account_repository::AccountRepository, audit_repository::AuditRepository,
+                    idempotency_key_repository::IdempotencyKeyRepository,
+                    lock_membership_repository::LockMembershipRepository,
+                    lock_repository::LockRepository, outbox_repository::OutboxRepository,
+                    personal_access_token_repository::PersonalAccessTokenRepository,
+                    quest_repository::QuestRepository, webhook_repository::WebhookRepository,
+                };
+
+                #[allow(unreachable_patterns)]
+                let read_pool = read_pool.and_then(|read_pool| match read_pool {
+                    DatabasePool::Sqlite(read_pool) => Some(read_pool.clone()),
+                    _ => None,
+                });
+
+                Self {
+                    lock: LockRepository::create(pool.clone(), read_pool),
+                    quest: QuestRepository::create(pool.clone()),
+                    membership: LockMembershipRepository::create(pool.clone()),
+                    outbox: OutboxRepository::create(pool.clone()),
+                    personal_access_token: PersonalAccessTokenRepository::create(pool.clone()),
+                    audit: AuditRepository::create(pool.clone()),
+                    account: AccountRepository::create(pool.clone()),
+                    idempotency: IdempotencyKeyRepository::create(pool.clone()),
+                    webhook: WebhookRepository::create(pool.clone()),
+                }
+            }
+        }
+    }
+}
+
+pub fn build_app_state(
+    pool: DatabasePool,
+    read_pool: Option<DatabasePool>,
+    config: Config,
+) -> Result<AppState, InfrastructureError> {
+    let repositories = Repositories::create(&pool, read_pool.as_ref());
+
+    let mut monitored_pools = vec![MonitoredPool {
+        name: "primary",
+        pool,
+        max_connections: config.database_max_connections,
+    }];
+    if let Some(read_pool) = read_pool {
+        monitored_pools.push(MonitoredPool {
+            name: "read",
+            pool: read_pool,
+            max_connections: config.database_read_max_connections,
+        });
+    }
+    let metrics_service = MetricsService::create(monitored_pools)?;
+
+    let lock_repository = repositories.lock;
+    let quest_repository = repositories.quest;
+
+    let event_stream_service = EventStreamService::create(
+        repositories.membership.clone(),
+        config.event_stream_buffer_size,
+    );
+
+    let lock_service = LockService::create(
+        lock_repository.clone(),
+        quest_repository.clone(),
+        repositories.membership.clone(),
+        event_stream_service.clone(),
+        metrics_service.clone(),
+        Duration::days(config.lock_retention_days),
+    );
+
+    let lock_query_service =
+        LockQueryService::create(lock_repository.clone(), repositories.membership.clone());
+
+    let quest_service = QuestService::create(
+        quest_repository.clone(),
+        lock_repository.clone(),
+        repositories.membership.clone(),
+        event_stream_service.clone(),
+        metrics_service.clone(),
+    );
+
+    let quest_query_service = QuestQueryService::create(
+        quest_repository.clone(),
+        lock_repository.clone(),
+        repositories.membership.clone(),
+    );
+
+    let membership_service =
+        MembershipService::create(repositories.membership.clone(), lock_repository.clone());
+
+    let outbox_dispatcher = OutboxDispatcher::create(
+        repositories.outbox.clone(),
+        vec![
+            LoggingEventHandler::create(),
+            WebhookEventHandler::create(repositories.webhook.clone()),
+        ],
+        config.outbox_batch_size,
+        config.outbox_max_attempts,
+        Duration::seconds(config.outbox_lease_secs),
+    );
+
+    let auth_service =
+        AuthService::from_config(&config, repositories.personal_access_token.clone())?;
+
+    let token_service = TokenService::create(repositories.personal_access_token.clone());
+
+    let admin_service = AdminService::create(
+        lock_repository.clone(),
+        quest_repository.clone(),
+        repositories.audit.clone(),
+        event_stream_service.clone(),
+        metrics_service.clone(),
+    );
 
-    let lock_service = LockService::create(lock_repository.clone());
+    let account_service = AccountService::create(
+        repositories.account.clone(),
+        lock_repository.clone(),
+        repositories.membership.clone(),
+        repositories.personal_access_token.clone(),
+        repositories.webhook.clone(),
+        Duration::days(config.account_erasure_grace_days),
+    );
 
-    let lock_query_service = LockQueryService::create(lock_repository.clone());
+    let idempotency_service = IdempotencyService::create(
+        repositories.idempotency.clone(),
+        Duration::seconds(config.idempotency_key_ttl_secs),
+    );
 
-    let auth_service = AuthService::create(&config.auth_jwks_url);
+    let webhook_service = WebhookService::create(
+        repositories.webhook.clone(),
+        std::time::Duration::from_secs(config.webhook_timeout_secs),
+        config.webhook_batch_size,
+        config.webhook_max_attempts,
+        Duration::seconds(config.webhook_lease_secs),
+        !config.is_development(),
+    )?;
 
-    AppState::new(config, lock_service, lock_query_service, auth_service)
+    Ok(AppState::new(
+        config,
+        lock_service,
+        lock_query_service,
+        quest_service,
+        quest_query_service,
+        membership_service,
+        outbox_dispatcher,
+        token_service,
+        admin_service,
+        account_service,
+        idempotency_service,
+        event_stream_service,
+        webhook_service,
+        metrics_service,
+        auth_service,
+    ))
 }
 
 pub fn setup_tracing() {
@@ -1,8 +1,14 @@
 use serde::Deserialize;
+#[cfg(feature = "postgres")]
 use sqlx::{
     PgPool,
     postgres::{PgConnectOptions, PgPoolOptions},
 };
+#[cfg(feature = "sqlite")]
+use sqlx::{
+    SqlitePool,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+};
 use std::{env, str::FromStr};
 
 #[derive(Default, Clone, Debug, Deserialize)]
@@ -14,12 +20,68 @@ pub struct Config {
     pub database_max_connections: u32,
     pub database_min_connections: u32,
 
+    /// Optional read replica used by the query side, falls back to `database_url`.
+    pub database_read_url: Option<String>,
+    pub database_read_max_connections: u32,
+    pub database_read_min_connections: u32,
+
     pub cors_origins: String,
 
     pub service_host: String,
     pub service_port: String,
+    /// Port of the gRPC server, which only runs when built with the `grpc` feature.
+    pub grpc_port: String,
+    /// Port of the listener serving `/metrics`, which is never served on `service_port` so it
+    /// stays off the public listener.
+    pub metrics_port: String,
 
+    /// One of `remote_jwks`, `jwks_file` or `hs256`.
+    pub auth_mode: String,
     pub auth_jwks_url: String,
+    pub auth_jwks_file: Option<String>,
+    pub auth_hs256_secret: Option<String>,
+    pub auth_issuer: Option<String>,
+    pub auth_audience: Option<String>,
+
+    pub lock_retention_days: i64,
+    pub lock_purge_interval_secs: u64,
+
+    pub account_erasure_grace_days: i64,
+    pub account_erasure_interval_secs: u64,
+
+    /// How long a command's `Idempotency-Key` and response are kept.
+    pub idempotency_key_ttl_secs: i64,
+    pub idempotency_purge_interval_secs: u64,
+
+    /// Events kept for clients resuming the event stream with `Last-Event-ID`.
+    pub event_stream_buffer_size: usize,
+    pub event_stream_heartbeat_secs: u64,
+
+    pub outbox_poll_interval_ms: u64,
+    pub outbox_batch_size: i64,
+    pub outbox_max_attempts: i32,
+    pub outbox_lease_secs: i64,
+
+    /// A failing webhook delivery is retried with exponential backoff until it has been
+    /// attempted `webhook_max_attempts` times, then kept as a dead letter.
+    pub webhook_poll_interval_ms: u64,
+    pub webhook_batch_size: i64,
+    pub webhook_max_attempts: i32,
+    pub webhook_lease_secs: i64,
+    pub webhook_timeout_secs: u64,
+
+    /// Queries to `/api/v1/graphql` nested deeper, or costing more, than this are refused
+    /// before they run.
+    pub graphql_max_depth: usize,
+    pub graphql_max_complexity: usize,
+
+    /// Comma separated JSON fields and headers masked in request/response logs.
+    pub log_redacted_fields: String,
+    pub log_redacted_headers: String,
+    /// Bodies larger than this are passed through rather than buffered for debug logging.
+    pub log_max_body_bytes: usize,
+    /// Requests with a larger body are refused with a 413.
+    pub max_request_body_bytes: usize,
 }
 
 impl Config {
@@ -38,29 +100,267 @@ impl Config {
                 .map(|s| s.parse::<u32>().unwrap_or(1))
                 .unwrap_or(1),
 
+            database_read_url: env::var("DATABASE_READ_URL").ok().filter(|s| !s.is_empty()),
+            database_read_max_connections: env::var("DATABASE_READ_MAX_CONNECTIONS")
+                .map(|s| s.parse::<u32>().unwrap_or(5))
+                .unwrap_or(5),
+            database_read_min_connections: env::var("DATABASE_READ_MIN_CONNECTIONS")
+                .map(|s| s.parse::<u32>().unwrap_or(1))
+                .unwrap_or(1),
+
             cors_origins: env::var("BACKEND_CORS_ORIGINS")?,
 
             service_host: env::var("SERVICE_HOST")?,
             service_port: env::var("SERVICE_PORT")?,
+            grpc_port: env::var("GRPC_PORT").unwrap_or_else(|_| "50051".to_string()),
+            metrics_port: env::var("METRICS_PORT")
+                .ok()
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| "9100".to_string()),
+
+            auth_mode: env::var("AUTH_MODE").unwrap_or_else(|_| "remote_jwks".to_string()),
+            auth_jwks_url: env::var("AUTH_JWKS_URL").unwrap_or_default(),
+            auth_jwks_file: env::var("AUTH_JWKS_FILE").ok().filter(|s| !s.is_empty()),
+            auth_hs256_secret: env::var("AUTH_HS256_SECRET").ok().filter(|s| !s.is_empty()),
+            auth_issuer: env::var("AUTH_ISSUER").ok().filter(|s| !s.is_empty()),
+            auth_audience: env::var("AUTH_AUDIENCE").ok().filter(|s| !s.is_empty()),
+
+            lock_retention_days: env::var("LOCK_RETENTION_DAYS")
+                .map(|s| s.parse::<i64>().unwrap_or(30))
+                .unwrap_or(30),
+            // Intervals are at least one unit, a zero interval would panic the job's ticker.
+            lock_purge_interval_secs: env::var("LOCK_PURGE_INTERVAL_SECS")
+                .map(|s| s.parse::<u64>().unwrap_or(3600))
+                .unwrap_or(3600)
+                .max(1),
+
+            account_erasure_grace_days: env::var("ACCOUNT_ERASURE_GRACE_DAYS")
+                .map(|s| s.parse::<i64>().unwrap_or(14))
+                .unwrap_or(14),
+            account_erasure_interval_secs: env::var("ACCOUNT_ERASURE_INTERVAL_SECS")
+                .map(|s| s.parse::<u64>().unwrap_or(3600))
+                .unwrap_or(3600)
+                .max(1),
+
+            idempotency_key_ttl_secs: env::var("IDEMPOTENCY_KEY_TTL_SECS")
+                .map(|s| s.parse::<i64>().unwrap_or(86400))
+                .unwrap_or(86400),
+            idempotency_purge_interval_secs: env::var("IDEMPOTENCY_PURGE_INTERVAL_SECS")
+                .map(|s| s.parse::<u64>().unwrap_or(3600))
+                .unwrap_or(3600)
+                .max(1),
+
+            event_stream_buffer_size: env::var("EVENT_STREAM_BUFFER_SIZE")
+                .map(|s| s.parse::<usize>().unwrap_or(1024))
+                .unwrap_or(1024),
+            event_stream_heartbeat_secs: env::var("EVENT_STREAM_HEARTBEAT_SECS")
+                .map(|s| s.parse::<u64>().unwrap_or(15))
+                .unwrap_or(15)
+                .max(1),
+
+            outbox_poll_interval_ms: env::var("OUTBOX_POLL_INTERVAL_MS")
+                .map(|s| s.parse::<u64>().unwrap_or(1000))
+                .unwrap_or(1000)
+                .max(1),
+            outbox_batch_size: env::var("OUTBOX_BATCH_SIZE")
+                .map(|s| s.parse::<i64>().unwrap_or(50))
+                .unwrap_or(50),
+            outbox_max_attempts: env::var("OUTBOX_MAX_ATTEMPTS")
+                .map(|s| s.parse::<i32>().unwrap_or(10))
+                .unwrap_or(10),
+            outbox_lease_secs: env::var("OUTBOX_LEASE_SECS")
+                .map(|s| s.parse::<i64>().unwrap_or(60))
+                .unwrap_or(60),
 
-            auth_jwks_url: env::var("AUTH_JWKS_URL")?,
+            webhook_poll_interval_ms: env::var("WEBHOOK_POLL_INTERVAL_MS")
+                .map(|s| s.parse::<u64>().unwrap_or(1000))
+                .unwrap_or(1000)
+                .max(1),
+            webhook_batch_size: env::var("WEBHOOK_BATCH_SIZE")
+                .map(|s| s.parse::<i64>().unwrap_or(20))
+                .unwrap_or(20),
+            webhook_max_attempts: env::var("WEBHOOK_MAX_ATTEMPTS")
+                .map(|s| s.parse::<i32>().unwrap_or(8))
+                .unwrap_or(8),
+            webhook_lease_secs: env::var("WEBHOOK_LEASE_SECS")
+                .map(|s| s.parse::<i64>().unwrap_or(120))
+                .unwrap_or(120),
+            webhook_timeout_secs: env::var("WEBHOOK_TIMEOUT_SECS")
+                .map(|s| s.parse::<u64>().unwrap_or(10))
+                .unwrap_or(10),
+
+            graphql_max_depth: env::var("GRAPHQL_MAX_DEPTH")
+                .map(|s| s.parse::<usize>().unwrap_or(10))
+                .unwrap_or(10),
+            graphql_max_complexity: env::var("GRAPHQL_MAX_COMPLEXITY")
+                .map(|s| s.parse::<usize>().unwrap_or(250))
+                .unwrap_or(250),
+
+            log_redacted_fields: env::var("LOG_REDACTED_FIELDS")
+                .unwrap_or_else(|_| "share,shares,secret,token,access_token,password".to_string()),
+            log_redacted_headers: env::var("LOG_REDACTED_HEADERS")
+                .unwrap_or_else(|_| "authorization,cookie,set-cookie".to_string()),
+            log_max_body_bytes: env::var("LOG_MAX_BODY_BYTES")
+                .map(|s| s.parse::<usize>().unwrap_or(65536))
+                .unwrap_or(65536),
+            max_request_body_bytes: env::var("MAX_REQUEST_BODY_BYTES")
+                .map(|s| s.parse::<usize>().unwrap_or(1048576))
+                .unwrap_or(1048576),
         })
     }
+
+    /// Running on a developer's machine, `ENVIRONMENT` is `local` or `development`.
+    pub fn is_development(&self) -> bool {
+        matches!(self.environment.as_str(), "local" | "development")
+    }
 }
 
-pub async fn setup_database(config: &Config) -> Result<PgPool, sqlx::Error> {
-    let connect_options = PgConnectOptions::from_str(&config.database_url)
+/// Connection pool for the storage backend selected by the `DATABASE_URL` scheme.
+#[derive(Clone, Debug)]
+pub enum DatabasePool {
+    #[cfg(feature = "postgres")]
+    Postgres(PgPool),
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqlitePool),
+}
+
+impl DatabasePool {
+    /// Open connections, idle or in use.
+    pub fn size(&self) -> u32 {
+        match self {
+            #[cfg(feature = "postgres")]
+            DatabasePool::Postgres(pool) => pool.size(),
+            #[cfg(feature = "sqlite")]
+            DatabasePool::Sqlite(pool) => pool.size(),
+        }
+    }
+
+    pub fn num_idle(&self) -> usize {
+        match self {
+            #[cfg(feature = "postgres")]
+            DatabasePool::Postgres(pool) => pool.num_idle(),
+            #[cfg(feature = "sqlite")]
+            DatabasePool::Sqlite(pool) => pool.num_idle(),
+        }
+    }
+}
+
+pub async fn setup_database(config: &Config) -> Result<DatabasePool, sqlx::Error> {
+    connect(
+        &config.database_url,
+        config.database_max_connections,
+        config.database_min_connections,
+        false,
+    )
+    .await
+}
+
+/// Connect to the read replica, if one is configured. Its scheme must match the primary's.
+pub async fn setup_read_database(config: &Config) -> Result<Option<DatabasePool>, sqlx::Error> {
+    match &config.database_read_url {
+        Some(url) if scheme(url) != scheme(&config.database_url) => {
+            Err(sqlx::Error::Configuration(
+                "DATABASE_READ_URL must use the same backend as DATABASE_URL".into(),
+            ))
+        }
+        Some(url) => connect(
+            url,
+            config.database_read_max_connections,
+            config.database_read_min_connections,
+            true,
+        )
+        .await
+        .map(Some),
+        None => Ok(None),
+    }
+}
+
+fn scheme(url: &str) -> &str {
+    url.split_once(':')
+        .map(|(scheme, _)| scheme)
+        .unwrap_or_default()
+}
+
+async fn connect(
+    url: &str,
+    max_connections: u32,
+    min_connections: u32,
+    read_only: bool,
+) -> Result<DatabasePool, sqlx::Error> {
+    let scheme = scheme(url);
+    match scheme {
+        #[cfg(feature = "postgres")]
+        "postgres" | "postgresql" => {
+            setup_postgres(url, max_connections, min_connections, read_only)
+                .await
+                .map(DatabasePool::Postgres)
+        }
+        #[cfg(feature = "sqlite")]
+        "sqlite" => setup_sqlite(url, max_connections, min_connections, read_only)
+            .await
+            .map(DatabasePool::Sqlite),
+        _ => {
+            tracing::error!("Unsupported database URL scheme: '{scheme}'");
+            Err(sqlx::Error::Configuration(
+                format!("no storage backend compiled in for '{scheme}' URLs").into(),
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+async fn setup_postgres(
+    url: &str,
+    max_connections: u32,
+    min_connections: u32,
+    read_only: bool,
+) -> Result<PgPool, sqlx::Error> {
+    let mut connect_options = PgConnectOptions::from_str(url)
         .map_err(|e| {
             tracing::error!("Failed to parse database URL: {}", e);
             e
         })?
         .clone();
+    if read_only {
+        connect_options = connect_options.options([("default_transaction_read_only", "on")]);
+    }
 
     let pool = PgPoolOptions::new()
-        .max_connections(config.database_max_connections)
-        .min_connections(config.database_min_connections)
+        .max_connections(max_connections)
+        .min_connections(min_connections)
+        .connect_with(connect_options)
+        .await?;
+
+    Ok(pool)
+}
+
+#[cfg(feature = "sqlite")]
+async fn setup_sqlite(
+    url: &str,
+    max_connections: u32,
+    min_connections: u32,
+    read_only: bool,
+) -> Result<SqlitePool, sqlx::Error> {
+    let connect_options = SqliteConnectOptions::from_str(url)
+        .map_err(|e| {
+            tracing::error!("Failed to parse database URL: {}", e);
+            e
+        })?
+        .create_if_missing(!read_only)
+        .read_only(read_only)
+        .foreign_keys(true);
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(max_connections)
+        .min_connections(min_connections)
         .connect_with(connect_options)
         .await?;
 
+    // A self-hosted SQLite database has no separate provisioning step, so keep
+    // its schema up to date on startup. Replicas get theirs from the primary.
+    if !read_only {
+        sqlx::migrate!("./migrations/sqlite").run(&pool).await?;
+    }
+
     Ok(pool)
 }
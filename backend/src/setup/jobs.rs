@@ -0,0 +1,142 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::task::JoinHandle;
+use tracing::error;
+
+use crate::{
+    application::services::{
+        account_service::AccountServiceTrait, idempotency_service::IdempotencyServiceTrait,
+        lock_service::LockServiceTrait, metrics_service::MetricsServiceTrait,
+        outbox_dispatcher::OutboxDispatcherTrait, webhook_service::WebhookServiceTrait,
+    },
+    setup::app_state::AppState,
+};
+
+/// Start the periodic background jobs. They run until the process exits.
+pub fn spawn_background_jobs(state: &AppState) -> Vec<JoinHandle<()>> {
+    vec![
+        spawn_lock_purge_job(
+            state.lock_service.clone(),
+            state.metrics_service.clone(),
+            Duration::from_secs(state.config.lock_purge_interval_secs),
+        ),
+        spawn_outbox_dispatch_job(
+            state.outbox_dispatcher.clone(),
+            state.metrics_service.clone(),
+            Duration::from_millis(state.config.outbox_poll_interval_ms),
+        ),
+        spawn_account_erasure_job(
+            state.account_service.clone(),
+            state.metrics_service.clone(),
+            Duration::from_secs(state.config.account_erasure_interval_secs),
+        ),
+        spawn_idempotency_purge_job(
+            state.idempotency_service.clone(),
+            state.metrics_service.clone(),
+            Duration::from_secs(state.config.idempotency_purge_interval_secs),
+        ),
+        spawn_webhook_delivery_job(
+            state.webhook_service.clone(),
+            state.metrics_service.clone(),
+            Duration::from_millis(state.config.webhook_poll_interval_ms),
+        ),
+    ]
+}
+
+/// Periodically remove soft deleted locks whose retention window has passed.
+pub fn spawn_lock_purge_job(
+    lock_service: Arc<dyn LockServiceTrait>,
+    metrics: Arc<dyn MetricsServiceTrait>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let result = lock_service.purge_deleted_locks().await;
+            metrics.job_ran("lock_purge", result.is_ok());
+            if let Err(err) = result {
+                error!("Lock purge job failed: {err}");
+            }
+        }
+    })
+}
+
+/// Drain the outbox, polling again after `interval` once it is empty.
+pub fn spawn_outbox_dispatch_job(
+    dispatcher: Arc<dyn OutboxDispatcherTrait>,
+    metrics: Arc<dyn MetricsServiceTrait>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let result = dispatcher.dispatch_batch().await;
+            metrics.job_ran("outbox_dispatch", result.is_ok());
+            match result {
+                // A non-empty batch means there may be more waiting, go straight back.
+                Ok(claimed) if claimed > 0 => continue,
+                Ok(_) => {}
+                Err(err) => error!("Outbox dispatch job failed: {err}"),
+            }
+            tokio::time::sleep(interval).await;
+        }
+    })
+}
+
+/// Periodically erase the accounts whose erasure grace period has passed.
+pub fn spawn_account_erasure_job(
+    account_service: Arc<dyn AccountServiceTrait>,
+    metrics: Arc<dyn MetricsServiceTrait>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let result = account_service.erase_due_accounts().await;
+            metrics.job_ran("account_erasure", result.is_ok());
+            if let Err(err) = result {
+                error!("Account erasure job failed: {err}");
+            }
+        }
+    })
+}
+
+/// Periodically remove the idempotency keys that expired.
+pub fn spawn_idempotency_purge_job(
+    idempotency_service: Arc<dyn IdempotencyServiceTrait>,
+    metrics: Arc<dyn MetricsServiceTrait>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let result = idempotency_service.purge_expired().await;
+            metrics.job_ran("idempotency_purge", result.is_ok());
+            if let Err(err) = result {
+                error!("Idempotency key purge job failed: {err}");
+            }
+        }
+    })
+}
+
+/// Send the webhook deliveries that are due, polling again after `interval` once none are.
+pub fn spawn_webhook_delivery_job(
+    webhook_service: Arc<dyn WebhookServiceTrait>,
+    metrics: Arc<dyn MetricsServiceTrait>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let result = webhook_service.deliver_batch().await;
+            metrics.job_ran("webhook_delivery", result.is_ok());
+            match result {
+                Ok(attempted) if attempted > 0 => continue,
+                Ok(_) => {}
+                Err(err) => error!("Webhook delivery job failed: {err}"),
+            }
+            tokio::time::sleep(interval).await;
+        }
+    })
+}
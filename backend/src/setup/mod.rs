@@ -1,3 +1,4 @@
 pub mod app_state;
 pub mod bootstrap;
 pub mod config;
+pub mod jobs;
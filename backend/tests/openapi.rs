@@ -0,0 +1,26 @@
+//! Keeps the checked in `openapi.json` in step with the handlers. After changing a route or a
+//! schema, regenerate it with `UPDATE_OPENAPI=1 cargo test --test openapi`.
+use std::{env, fs};
+
+use quest_lock_backend::api::openapi::openapi;
+
+const SPEC_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+#[test]
+fn openapi_json_is_up_to_date() {
+    let generated = openapi()
+        .to_pretty_json()
+        .expect("OpenAPI document serializes")
+        + "\n";
+
+    if env::var_os("UPDATE_OPENAPI").is_some() {
+        fs::write(SPEC_PATH, &generated).expect("openapi.json is writable");
+        return;
+    }
+
+    let committed = fs::read_to_string(SPEC_PATH).unwrap_or_default();
+    assert!(
+        committed == generated,
+        "openapi.json is out of date, regenerate it with `UPDATE_OPENAPI=1 cargo test --test openapi`"
+    );
+}
@@ -0,0 +1,597 @@
+//! Runs the same checks against every storage backend compiled in, through the repository
+//! traits the services use.
+//!
+//! SQLite runs in memory, build with `--features sqlite`. Postgres needs an empty database it
+//! may write to, given as `TEST_DATABASE_URL`, so its test is ignored unless asked for with
+//! `make test-postgres`. The `db-seed` schema is applied to it when its tables don't exist yet.
+use std::collections::HashMap;
+
+use chrono::{Duration, Utc};
+use quest_lock_backend::{
+    domain::{
+        auth::entity::PersonalAccessToken,
+        event::entity::DomainEventKind,
+        idempotency::entity::{IdempotencyRecord, StoredResponse},
+        lock::{entity::Lock, repository::ReadPreference},
+        quest::{
+            entity::Quest,
+            enums::{QuestStatus, QuestType},
+        },
+        webhook::entity::{DeliveryStatus, WebhookDelivery, WebhookSubscription},
+    },
+    setup::{
+        bootstrap::Repositories,
+        config::{Config, DatabasePool, setup_database},
+    },
+};
+use uuid::Uuid;
+
+async fn connect(url: &str) -> DatabasePool {
+    let config = Config {
+        database_url: url.to_string(),
+        // Every connection to an in-memory SQLite database gets a database of its own.
+        database_max_connections: 1,
+        database_min_connections: 1,
+        ..Config::default()
+    };
+    setup_database(&config)
+        .await
+        .expect("test database is reachable")
+}
+
+/// A user id no other run has used, so checks don't see each other's rows.
+fn user_id() -> String {
+    format!("test-user-{}", Uuid::now_v7())
+}
+
+fn new_lock(user_id: &str) -> Lock {
+    let mut lock = Lock::create(user_id.to_string(), Some("test".to_string()), 2, 1, vec![]);
+    lock.quests = vec![Quest::create(
+        lock.id,
+        "c2hhcmU=".to_string(),
+        QuestType::TIME,
+        None,
+        HashMap::from([("release_date".to_string(), "2030-01-01".to_string())]),
+    )];
+    lock.record(DomainEventKind::LockCreated);
+    lock
+}
+
+async fn run_all(pool: DatabasePool) {
+    let repos = Repositories::create(&pool, None);
+    locks(&repos).await;
+    quests(&repos).await;
+    outbox(&repos).await;
+    personal_access_tokens(&repos).await;
+    webhooks(&repos).await;
+    idempotency_keys(&repos).await;
+}
+
+async fn locks(repos: &Repositories) {
+    let user_id = user_id();
+    let lock = new_lock(&user_id);
+    assert!(repos.lock.save(&lock).await.unwrap());
+
+    let saved = repos
+        .lock
+        .get_by_id(lock.id, ReadPreference::Primary)
+        .await
+        .unwrap()
+        .expect("saved lock is found");
+    assert_eq!(saved.user_id, user_id);
+    assert_eq!(saved.label.as_deref(), Some("test"));
+    assert_eq!(saved.quests.len(), 1);
+    assert_eq!(saved.quests[0].quest_type, QuestType::TIME);
+
+    let owned = repos
+        .lock
+        .get_by_user_id(user_id.clone(), ReadPreference::Primary)
+        .await
+        .unwrap();
+    assert_eq!(
+        owned.iter().map(|l| l.id).collect::<Vec<_>>(),
+        vec![lock.id]
+    );
+
+    let history = repos
+        .lock
+        .get_history(lock.id, ReadPreference::Primary)
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].kind, DomainEventKind::LockCreated);
+
+    // Deleting only applies at the lock's current version.
+    let mut stale = saved.clone();
+    stale.version -= 1;
+    assert!(!repos.lock.delete(&stale).await.unwrap());
+    assert!(repos.lock.delete(&saved).await.unwrap());
+    assert!(
+        repos
+            .lock
+            .get_by_id(lock.id, ReadPreference::Primary)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        repos
+            .lock
+            .get_by_user_id(user_id.clone(), ReadPreference::Primary)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let deleted = repos
+        .lock
+        .get_deleted_by_id(lock.id)
+        .await
+        .unwrap()
+        .expect("deleted lock is found");
+    assert!(deleted.deleted_at.is_some());
+
+    // Saving doesn't bring a deleted lock back, nor write its events.
+    let mut resaved = saved.clone();
+    resaved.record(DomainEventKind::LockCreated);
+    assert!(!repos.lock.save(&resaved).await.unwrap());
+    assert_eq!(
+        repos
+            .lock
+            .get_history(lock.id, ReadPreference::Primary)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        repos
+            .lock
+            .get_deleted_by_user_id(user_id.clone())
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    assert!(repos.lock.restore(&deleted).await.unwrap());
+    assert!(
+        repos
+            .lock
+            .get_by_id(lock.id, ReadPreference::Primary)
+            .await
+            .unwrap()
+            .is_some()
+    );
+
+    // Only tombstoned locks are purged.
+    let restored = repos
+        .lock
+        .get_by_id(lock.id, ReadPreference::Primary)
+        .await
+        .unwrap()
+        .unwrap();
+    repos
+        .lock
+        .purge_deleted(Utc::now() + Duration::days(1))
+        .await
+        .unwrap();
+    assert!(
+        repos
+            .lock
+            .get_by_id(lock.id, ReadPreference::Primary)
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert!(repos.lock.delete(&restored).await.unwrap());
+    assert!(
+        repos
+            .lock
+            .purge_deleted(Utc::now() + Duration::days(1))
+            .await
+            .unwrap()
+            >= 1
+    );
+    assert!(
+        repos
+            .lock
+            .get_deleted_by_id(lock.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+async fn quests(repos: &Repositories) {
+    let lock = new_lock(&user_id());
+    assert!(repos.lock.save(&lock).await.unwrap());
+    let saved = repos
+        .lock
+        .get_by_id(lock.id, ReadPreference::Primary)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let mut quest = repos
+        .quest
+        .get_by_id(lock.quests[0].id)
+        .await
+        .unwrap()
+        .expect("saved quest is found");
+    assert_eq!(quest.lock_id, lock.id);
+    assert_eq!(quest.status, QuestStatus::PENDING);
+
+    quest.status = QuestStatus::IN_PROGRESS;
+    assert!(repos.quest.save(&quest, saved.version).await.unwrap());
+    // The save moved the lock on, the version it was based on no longer applies.
+    assert!(!repos.quest.save(&quest, saved.version).await.unwrap());
+
+    let updated = repos
+        .lock
+        .get_by_id(lock.id, ReadPreference::Primary)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.version, saved.version + 1);
+    assert_eq!(updated.quests[0].status, QuestStatus::IN_PROGRESS);
+
+    assert!(repos.quest.delete(&quest).await.unwrap());
+    assert!(repos.quest.get_by_id(quest.id).await.unwrap().is_none());
+}
+
+async fn outbox(repos: &Repositories) {
+    let lock = new_lock(&user_id());
+    let event_id = lock.events[0].id;
+    assert!(repos.lock.save(&lock).await.unwrap());
+
+    let lease = Duration::seconds(60);
+    let claimed = repos.outbox.claim_batch(1000, lease).await.unwrap();
+    let message = claimed
+        .iter()
+        .find(|message| message.id == event_id)
+        .expect("event written with the lock is claimed");
+    assert_eq!(message.attempts, 1);
+    assert_eq!(message.event.lock_id, lock.id);
+    assert!(message.handled_by.is_empty());
+
+    // Leased to this dispatcher, others skip it.
+    let claimed = repos.outbox.claim_batch(1000, lease).await.unwrap();
+    assert!(claimed.iter().all(|message| message.id != event_id));
+
+    // A failure is retried once due, the handlers that succeeded are kept for the retry.
+    let handled_by = vec!["logging".to_string()];
+    assert!(
+        repos
+            .outbox
+            .mark_failed(
+                event_id,
+                "webhooks: failed",
+                &handled_by,
+                Some(Utc::now() - Duration::seconds(1))
+            )
+            .await
+            .unwrap()
+    );
+    let claimed = repos.outbox.claim_batch(1000, lease).await.unwrap();
+    let message = claimed
+        .iter()
+        .find(|message| message.id == event_id)
+        .expect("failed event is claimed again");
+    assert_eq!(message.attempts, 2);
+    assert_eq!(message.handled_by, handled_by);
+
+    assert!(repos.outbox.mark_done(event_id).await.unwrap());
+}
+
+async fn personal_access_tokens(repos: &Repositories) {
+    let user_id = user_id();
+    let (token, secret) = PersonalAccessToken::generate(
+        user_id.clone(),
+        "ci".to_string(),
+        vec!["locks:read".to_string()],
+        Duration::days(30),
+    );
+    assert!(repos.personal_access_token.save(&token).await.unwrap());
+
+    let found = repos
+        .personal_access_token
+        .get_by_hash(&PersonalAccessToken::hash_secret(&secret))
+        .await
+        .unwrap()
+        .expect("token is found by the hash of its secret");
+    assert_eq!(found.id, token.id);
+    assert_eq!(found.scopes, vec!["locks:read".to_string()]);
+    assert!(found.last_used_at.is_none());
+
+    assert!(
+        repos
+            .personal_access_token
+            .touch(token.id, Utc::now())
+            .await
+            .unwrap()
+    );
+    let touched = repos
+        .personal_access_token
+        .get_by_hash(&token.token_hash)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(touched.last_used_at.is_some());
+
+    assert!(
+        !repos
+            .personal_access_token
+            .revoke(token.id, "someone-else".to_string())
+            .await
+            .unwrap()
+    );
+    assert!(
+        repos
+            .personal_access_token
+            .revoke(token.id, user_id.clone())
+            .await
+            .unwrap()
+    );
+
+    // Revoked tokens stay listed.
+    let listed = repos
+        .personal_access_token
+        .get_by_user_id(user_id)
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+    assert!(listed[0].revoked_at.is_some());
+}
+
+async fn webhooks(repos: &Repositories) {
+    let user_id = user_id();
+    let subscription = WebhookSubscription::generate(
+        user_id.clone(),
+        "https://example.com/hooks".to_string(),
+        vec!["lock.created".to_string()],
+    );
+    assert!(
+        repos
+            .webhook
+            .save_subscription(&subscription)
+            .await
+            .unwrap()
+    );
+
+    let found = repos
+        .webhook
+        .get_subscription(subscription.id)
+        .await
+        .unwrap()
+        .expect("subscription is found");
+    assert_eq!(found.url, subscription.url);
+    assert_eq!(found.event_types, subscription.event_types);
+    assert_eq!(found.secret, subscription.secret);
+    assert_eq!(
+        repos
+            .webhook
+            .get_subscriptions_by_user_id(user_id.clone())
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let lock = new_lock(&user_id);
+    let event = &lock.events[0];
+    let delivery = WebhookDelivery::create(&subscription, event);
+    assert_eq!(
+        repos
+            .webhook
+            .enqueue_deliveries(std::slice::from_ref(&delivery))
+            .await
+            .unwrap(),
+        1
+    );
+    // The outbox may hand over the same event again, it is only delivered once.
+    let repeat = WebhookDelivery::create(&subscription, event);
+    assert_eq!(
+        repos.webhook.enqueue_deliveries(&[repeat]).await.unwrap(),
+        0
+    );
+
+    let lease = Duration::seconds(60);
+    let claimed = repos.webhook.claim_deliveries(1000, lease).await.unwrap();
+    let claimed = claimed
+        .iter()
+        .find(|claimed| claimed.id == delivery.id)
+        .expect("queued delivery is claimed");
+    assert_eq!(claimed.event.id, event.id);
+    assert_eq!(claimed.attempts, 1);
+
+    assert!(
+        repos
+            .webhook
+            .mark_failed(delivery.id, "Receiver responded with 500", None)
+            .await
+            .unwrap()
+    );
+    let dead_letters = repos
+        .webhook
+        .get_dead_letters(user_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(dead_letters[0].status, DeliveryStatus::DEAD);
+    assert_eq!(
+        dead_letters[0].last_error.as_deref(),
+        Some("Receiver responded with 500")
+    );
+
+    assert!(
+        repos
+            .webhook
+            .replay_dead_letter(delivery.id, "someone-else".to_string())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let replayed = repos
+        .webhook
+        .replay_dead_letter(delivery.id, user_id.clone())
+        .await
+        .unwrap()
+        .expect("dead letter is replayed");
+    assert_eq!(replayed.status, DeliveryStatus::PENDING);
+    assert_eq!(replayed.attempts, 0);
+    assert!(
+        repos
+            .webhook
+            .get_dead_letters(user_id.clone())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let claimed = repos.webhook.claim_deliveries(1000, lease).await.unwrap();
+    assert!(claimed.iter().any(|claimed| claimed.id == delivery.id));
+    assert!(repos.webhook.mark_delivered(delivery.id).await.unwrap());
+
+    assert!(
+        !repos
+            .webhook
+            .delete_subscription(subscription.id, "someone-else".to_string())
+            .await
+            .unwrap()
+    );
+    assert!(
+        repos
+            .webhook
+            .delete_subscription(subscription.id, user_id.clone())
+            .await
+            .unwrap()
+    );
+    assert!(
+        repos
+            .webhook
+            .get_subscription(subscription.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+async fn idempotency_keys(repos: &Repositories) {
+    let user_id = user_id();
+    let record = IdempotencyRecord::reserve(
+        user_id.clone(),
+        "key-1".to_string(),
+        "fingerprint".to_string(),
+        Duration::hours(1),
+    );
+    assert!(repos.idempotency.reserve(&record).await.unwrap());
+    assert!(!repos.idempotency.reserve(&record).await.unwrap());
+
+    let pending = repos
+        .idempotency
+        .get(user_id.clone(), "key-1".to_string())
+        .await
+        .unwrap()
+        .expect("reserved key is found");
+    assert_eq!(pending.fingerprint, "fingerprint");
+    assert!(pending.response.is_none());
+
+    let response = StoredResponse {
+        status: 201,
+        content_type: Some("application/json".to_string()),
+        headers: vec![("etag".to_string(), "\"3-owner\"".to_string())],
+        body: br#"{"status":201}"#.to_vec(),
+    };
+    assert!(
+        repos
+            .idempotency
+            .save_response(user_id.clone(), "key-1".to_string(), &response)
+            .await
+            .unwrap()
+    );
+    let completed = repos
+        .idempotency
+        .get(user_id.clone(), "key-1".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(completed.response, Some(response));
+
+    assert!(
+        repos
+            .idempotency
+            .delete(user_id.clone(), "key-1".to_string())
+            .await
+            .unwrap()
+    );
+    assert!(
+        repos
+            .idempotency
+            .get(user_id.clone(), "key-1".to_string())
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    assert!(repos.idempotency.reserve(&record).await.unwrap());
+    assert!(
+        repos
+            .idempotency
+            .delete_expired(Utc::now() + Duration::hours(2))
+            .await
+            .unwrap()
+            >= 1
+    );
+    assert!(
+        repos
+            .idempotency
+            .get(user_id, "key-1".to_string())
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_repositories() {
+    run_all(connect("sqlite::memory:").await).await;
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test]
+#[ignore = "needs a Postgres database in TEST_DATABASE_URL, run with `make test-postgres`"]
+async fn postgres_repositories() {
+    let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL is set");
+    let pool = connect(&url).await;
+    #[cfg(not(feature = "sqlite"))]
+    let DatabasePool::Postgres(pg_pool) = &pool;
+    #[cfg(feature = "sqlite")]
+    let DatabasePool::Postgres(pg_pool) = &pool else {
+        panic!("TEST_DATABASE_URL must be a postgres:// URL");
+    };
+
+    let seeded: Option<String> = sqlx::query_scalar("SELECT to_regclass('public.locks')::text")
+        .fetch_one(pg_pool)
+        .await
+        .unwrap();
+    if seeded.is_none() {
+        let seed_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/db-seed");
+        let mut files: Vec<_> = std::fs::read_dir(seed_dir)
+            .expect("db-seed is readable")
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "sql"))
+            .collect();
+        files.sort();
+        for file in files {
+            let sql = std::fs::read_to_string(&file).unwrap();
+            sqlx::raw_sql(&sql)
+                .execute(pg_pool)
+                .await
+                .unwrap_or_else(|e| panic!("applying {}: {e}", file.display()));
+        }
+    }
+
+    run_all(pool).await;
+}
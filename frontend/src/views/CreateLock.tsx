@@ -15,6 +15,19 @@ interface Quest {
   id: string
 }
 
+type ErrorDetail = {
+  field: string
+  message: string
+}
+
+const fieldClass = (error?: string) =>
+  `w-full p-3 border rounded-md focus:ring-2 focus:ring-shire-sun focus:border-transparent ${
+    error ? 'border-red-600' : 'border-shire-bark'
+  }`
+
+const FieldError = ({ message }: { message?: string }) =>
+  message ? <p className="text-xs text-red-600 mt-1">{message}</p> : null
+
 const QuestTypeInfo = {
   GEO: {
     icon: MapPin,
@@ -48,6 +61,8 @@ export const CreateLock = () => {
   const [password, setPassword] = useState('')
   const [isPasswordGenerated, setIsPasswordGenerated] = useState(false)
   const [expandedQuest, setExpandedQuest] = useState<string | null>(null)
+  // Validation errors from the API, keyed by the JSON pointer of the rejected field
+  const [fieldErrors, setFieldErrors] = useState<Record<string, string>>({})
   const { getAccessTokenSilently } = useAuth0()
   const navigate = useNavigate()
   const apiBaseUrl = import.meta.env.VITE_API_URL
@@ -78,6 +93,9 @@ export const CreateLock = () => {
     ))
   }
 
+  const questError = (quest: Quest, key: string): string | undefined =>
+    fieldErrors[`/quests/${quests.indexOf(quest)}/data/${key}`]
+
   const toggleQuestExpansion = (questId: string) => {
     setExpandedQuest(expandedQuest === questId ? null : questId)
   }
@@ -136,6 +154,7 @@ export const CreateLock = () => {
     }
 
     try {
+      setFieldErrors({})
       const token = await getAccessTokenSilently()
       const response = await fetch(`${apiBaseUrl}/api/v1/lock/`, {
         method: 'POST',
@@ -151,6 +170,16 @@ export const CreateLock = () => {
         navigate('/vault')
       } else {
         const errorData = await response.json()
+        const details: ErrorDetail[] = errorData.error?.details ?? []
+        setFieldErrors(
+          Object.fromEntries(details.map((detail) => [detail.field, detail.message]))
+        )
+        const firstQuest = details
+          .map((detail) => detail.field.match(/^\/quests\/(\d+)/))
+          .find((match) => match !== null)
+        if (firstQuest) {
+          setExpandedQuest(quests[Number(firstQuest[1])]?.id ?? null)
+        }
         alert(
           `Failed to create lock: ${errorData.message || response.statusText}`
         )
@@ -217,8 +246,9 @@ export const CreateLock = () => {
                     value={quest.data.location_name || ''}
                     onChange={(e) => updateQuestData(quest.id, { ...quest.data, location_name: e.target.value })}
                     placeholder="e.g., Stonehenge, Big Ben, Your childhood home"
-                    className="w-full p-3 border border-shire-bark rounded-md focus:ring-2 focus:ring-shire-sun focus:border-transparent"
+                    className={fieldClass(questError(quest, 'location_name'))}
                   />
+                  <FieldError message={questError(quest, 'location_name')} />
                 </div>
 
                 <div className="grid grid-cols-2 gap-4">
@@ -232,8 +262,9 @@ export const CreateLock = () => {
                       value={quest.data.latitude || ''}
                       onChange={(e) => updateQuestData(quest.id, { ...quest.data, latitude: e.target.value })}
                       placeholder="51.1789"
-                      className="w-full p-3 border border-shire-bark rounded-md focus:ring-2 focus:ring-shire-sun focus:border-transparent"
+                      className={fieldClass(questError(quest, 'latitude'))}
                     />
+                    <FieldError message={questError(quest, 'latitude')} />
                   </div>
                   <div>
                     <label className="block text-sm font-semibold text-shire-bark mb-2">
@@ -245,8 +276,9 @@ export const CreateLock = () => {
                       value={quest.data.longitude || ''}
                       onChange={(e) => updateQuestData(quest.id, { ...quest.data, longitude: e.target.value })}
                       placeholder="-1.8262"
-                      className="w-full p-3 border border-shire-bark rounded-md focus:ring-2 focus:ring-shire-sun focus:border-transparent"
+                      className={fieldClass(questError(quest, 'longitude'))}
                     />
+                    <FieldError message={questError(quest, 'longitude')} />
                   </div>
                 </div>
 
@@ -269,10 +301,11 @@ export const CreateLock = () => {
                     type="number"
                     value={quest.data.proximity_range || '100'}
                     onChange={(e) => updateQuestData(quest.id, { ...quest.data, proximity_range: e.target.value })}
-                    className="w-full p-3 border border-shire-bark rounded-md focus:ring-2 focus:ring-shire-sun focus:border-transparent"
+                    className={fieldClass(questError(quest, 'proximity_range'))}
                     min="10"
                     max="1000"
                   />
+                  <FieldError message={questError(quest, 'proximity_range')} />
                   <p className="text-xs text-shire-bark mt-1">How close you need to be to the location (10-1000 meters)</p>
                 </div>
               </div>
@@ -298,9 +331,10 @@ export const CreateLock = () => {
                     type="datetime-local"
                     value={quest.data.release_date || ''}
                     onChange={(e) => updateQuestData(quest.id, { ...quest.data, release_date: e.target.value })}
-                    className="w-full p-3 border border-shire-bark rounded-md focus:ring-2 focus:ring-shire-sun focus:border-transparent"
+                    className={fieldClass(questError(quest, 'release_date'))}
                     min={new Date().toISOString().slice(0, 16)}
                   />
+                  <FieldError message={questError(quest, 'release_date')} />
                   <p className="text-xs text-shire-bark mt-1">The earliest moment this share can be claimed</p>
                 </div>
 
@@ -312,9 +346,10 @@ export const CreateLock = () => {
                     value={quest.data.description || ''}
                     onChange={(e) => updateQuestData(quest.id, { ...quest.data, description: e.target.value })}
                     placeholder="e.g., After 30 days of reflection, After my birthday, When the project is complete..."
-                    className="w-full p-3 border border-shire-bark rounded-md focus:ring-2 focus:ring-shire-sun focus:border-transparent"
+                    className={fieldClass(questError(quest, 'description'))}
                     rows={3}
                   />
+                  <FieldError message={questError(quest, 'description')} />
                 </div>
               </div>
             )}
@@ -340,8 +375,9 @@ export const CreateLock = () => {
                     value={quest.data.friend_name || ''}
                     onChange={(e) => updateQuestData(quest.id, { ...quest.data, friend_name: e.target.value })}
                     placeholder="Their full name"
-                    className="w-full p-3 border border-shire-bark rounded-md focus:ring-2 focus:ring-shire-sun focus:border-transparent"
+                    className={fieldClass(questError(quest, 'friend_name'))}
                   />
+                  <FieldError message={questError(quest, 'friend_name')} />
                 </div>
 
                 <div>
@@ -353,8 +389,9 @@ export const CreateLock = () => {
                     value={quest.data.friend_email || ''}
                     onChange={(e) => updateQuestData(quest.id, { ...quest.data, friend_email: e.target.value })}
                     placeholder="their.email@example.com"
-                    className="w-full p-3 border border-shire-bark rounded-md focus:ring-2 focus:ring-shire-sun focus:border-transparent"
+                    className={fieldClass(questError(quest, 'friend_email'))}
                   />
+                  <FieldError message={questError(quest, 'friend_email')} />
                 </div>
 
                 <div>
@@ -365,9 +402,10 @@ export const CreateLock = () => {
                     value={quest.data.message || ''}
                     onChange={(e) => updateQuestData(quest.id, { ...quest.data, message: e.target.value })}
                     placeholder="Explain why you're entrusting them with this responsibility..."
-                    className="w-full p-3 border border-shire-bark rounded-md focus:ring-2 focus:ring-shire-sun focus:border-transparent"
+                    className={fieldClass(questError(quest, 'message'))}
                     rows={4}
                   />
+                  <FieldError message={questError(quest, 'message')} />
                 </div>
               </div>
             )}
@@ -393,10 +431,11 @@ export const CreateLock = () => {
                     value={quest.data.amount || ''}
                     onChange={(e) => updateQuestData(quest.id, { ...quest.data, amount: e.target.value })}
                     placeholder="50.00"
-                    className="w-full p-3 border border-shire-bark rounded-md focus:ring-2 focus:ring-shire-sun focus:border-transparent"
+                    className={fieldClass(questError(quest, 'amount'))}
                     min="1"
                     step="0.01"
                   />
+                  <FieldError message={questError(quest, 'amount')} />
                   <p className="text-xs text-shire-bark mt-1">The amount you must pay to unlock this share</p>
                 </div>
 
@@ -409,8 +448,9 @@ export const CreateLock = () => {
                     value={quest.data.charity || ''}
                     onChange={(e) => updateQuestData(quest.id, { ...quest.data, charity: e.target.value })}
                     placeholder="e.g., Mind, Oxfam, Local Food Bank"
-                    className="w-full p-3 border border-shire-bark rounded-md focus:ring-2 focus:ring-shire-sun focus:border-transparent"
+                    className={fieldClass(questError(quest, 'charity'))}
                   />
+                  <FieldError message={questError(quest, 'charity')} />
                   <p className="text-xs text-shire-bark mt-1">If specified, payment will go to this charity instead of being lost</p>
                 </div>
 
@@ -422,9 +462,10 @@ export const CreateLock = () => {
                     value={quest.data.purpose || ''}
                     onChange={(e) => updateQuestData(quest.id, { ...quest.data, purpose: e.target.value })}
                     placeholder="e.g., The cost of mindless scrolling, Investment in my future self..."
-                    className="w-full p-3 border border-shire-bark rounded-md focus:ring-2 focus:ring-shire-sun focus:border-transparent"
+                    className={fieldClass(questError(quest, 'purpose'))}
                     rows={3}
                   />
+                  <FieldError message={questError(quest, 'purpose')} />
                 </div>
               </div>
             )}
@@ -461,8 +502,9 @@ export const CreateLock = () => {
                 value={label}
                 onChange={(e) => setLabel(e.target.value)}
                 placeholder="e.g., The Instagram Exile, Twitter Sabbatical, Digital Detox Challenge"
-                className="w-full rounded-md bg-white p-3 border border-shire-bark focus:ring-2 focus:ring-shire-sun focus:border-transparent"
+                className={`bg-white ${fieldClass(fieldErrors['/label'])}`}
               />
+              <FieldError message={fieldErrors['/label']} />
             </div>
 
             <div>
@@ -4,13 +4,15 @@ import AppShell from '@/components/AppShell'
 import QuestLoader from '@/components/QuestLoader'
 import { MapPin, Clock, User, DollarSign, Lock, Unlock, Shield, Sword, Trophy, Plus, ChevronRight, Calendar, Mail, Coins } from 'lucide-react'
 
-// TODO these will go in a client
+// TODO these will go in a client, generated from backend/openapi.json
+type QuestStatus = 'PENDING' | 'IN_PROGRESS' | 'ABANDONED' | 'COMPLETED'
+
 type QuestDTO = {
   id: string
   lock_id: string
   share: string | null
   quest_type: string
-  status: string
+  status: QuestStatus
   data: Record<string, string>
 }
 
@@ -19,9 +21,17 @@ type LockDTO = {
   label: string | null
   total_shares: number
   threshold: number
+  role: 'OWNER' | 'VIEWER'
   quests: QuestDTO[]
 }
 
+type ApiResponse<T> = {
+  status: number
+  message: string
+  data: T | null
+  error: { code: string; details: { field: string; message: string }[] } | null
+}
+
 const QuestTypeInfo = {
   GEO: {
     icon: MapPin,
@@ -54,25 +64,21 @@ const QuestTypeInfo = {
 }
 
 // Helper function to determine the color of the quest status
-const getStatusColor = (status: string) => {
-  switch (status.toUpperCase()) {
-    case 'COMPLETE':
+const getStatusColor = (status: QuestStatus) => {
+  switch (status) {
+    case 'COMPLETED':
       return 'text-green-600 bg-green-100 border-green-300'
-    case 'IN_PROGRESS':
-      return 'text-yellow-600 bg-yellow-100 border-yellow-300'
-    case 'INCOMPLETE':
+    case 'PENDING':
     default:
       return 'text-red-600 bg-red-100 border-red-300'
   }
 }
 
-const getStatusIcon = (status: string) => {
-  switch (status.toUpperCase()) {
-    case 'COMPLETE':
+const getStatusIcon = (status: QuestStatus) => {
+  switch (status) {
+    case 'COMPLETED':
       return Trophy
-    case 'IN_PROGRESS':
-      return Sword
-    case 'INCOMPLETE':
+    case 'PENDING':
     default:
       return Shield
   }
@@ -123,15 +129,14 @@ const Vault = () => {
           }
         })
 
-        if (!res.ok) {
-          const errorResponse = await res.text()
+        const body: ApiResponse<LockDTO[]> = await res.json()
+        if (!res.ok || body.error) {
           throw new Error(
-            `Failed to fetch vault entries: ${res.status} ${res.statusText}. ${errorResponse}`
+            `Failed to fetch vault entries: ${body.error?.code ?? res.status}. ${body.message}`
           )
         }
 
-        const data: LockDTO[] = await res.json()
-        setLocks(data)
+        setLocks(body.data ?? [])
       } catch (err) {
         setError(
           err instanceof Error ? err.message : 'An unknown error occurred'
@@ -155,7 +160,7 @@ const Vault = () => {
   }
 
   const completedQuestsCount = (lock: LockDTO) =>
-    lock.quests.filter((q) => q.status.toUpperCase() === 'COMPLETE').length
+    lock.quests.filter((q) => q.status === 'COMPLETED').length
 
   const canUnlock = (lock: LockDTO) =>
     completedQuestsCount(lock) >= lock.threshold
@@ -324,7 +329,7 @@ const Vault = () => {
                             <div
                               key={quest.id}
                               className={`rounded-lg border-2 p-4 transition-all ${
-                                quest.status.toUpperCase() === 'COMPLETE'
+                                quest.status === 'COMPLETED'
                                   ? questInfo?.bgColor + ' ' + questInfo?.borderColor
                                   : 'bg-white border-gray-200 hover:border-shire-sun'
                               }`}
@@ -372,9 +377,9 @@ const Vault = () => {
                                 </div>
                                 <div className="flex items-center space-x-3">
                                   <span className={`px-3 py-1 rounded-full text-sm font-semibold border ${getStatusColor(quest.status)}`}>
-                                    {quest.status.toUpperCase()}
+                                    {quest.status}
                                   </span>
-                                  {quest.status.toUpperCase() !== 'COMPLETE' && (
+                                  {quest.status !== 'COMPLETED' && (
                                     <button
                                       onClick={() => handleAttemptQuest(quest.id)}
                                       className="px-4 py-2 bg-shire-sun text-shire-dark rounded-lg font-semibold hover:bg-shire-bark hover:text-shire-light transition-colors"